@@ -0,0 +1,6 @@
+DROP INDEX IF EXISTS idx_houses_slug;
+
+ALTER TABLE houses DROP COLUMN crest;
+ALTER TABLE houses DROP COLUMN secondary_color;
+ALTER TABLE houses DROP COLUMN primary_color;
+ALTER TABLE houses DROP COLUMN slug;
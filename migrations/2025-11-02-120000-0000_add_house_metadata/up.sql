@@ -0,0 +1,16 @@
+-- Display metadata for houses, so the UI can be themed from data instead of
+-- hard-coded house ids.
+ALTER TABLE houses ADD COLUMN slug TEXT NOT NULL DEFAULT '';
+ALTER TABLE houses ADD COLUMN primary_color TEXT NOT NULL DEFAULT '#538d4e';
+ALTER TABLE houses ADD COLUMN secondary_color TEXT NOT NULL DEFAULT '#ffffff';
+ALTER TABLE houses ADD COLUMN crest TEXT;
+
+UPDATE houses SET slug = 'gryffindor', primary_color = '#d32f2f', secondary_color = '#ffd700', crest = '/crests/gryffindor.svg' WHERE name = 'Gryffindor';
+UPDATE houses SET slug = 'hufflepuff', primary_color = '#ffca28', secondary_color = '#000000', crest = '/crests/hufflepuff.svg' WHERE name = 'Hufflepuff';
+UPDATE houses SET slug = 'ravenclaw', primary_color = '#1976d2', secondary_color = '#432818', crest = '/crests/ravenclaw.svg' WHERE name = 'Ravenclaw';
+UPDATE houses SET slug = 'slytherin', primary_color = '#388e3c', secondary_color = '#c0c0c0', crest = '/crests/slytherin.svg' WHERE name = 'Slytherin';
+
+-- Any other house gets a slug derived from its name.
+UPDATE houses SET slug = lower(replace(name, ' ', '-')) WHERE slug = '';
+
+CREATE UNIQUE INDEX idx_houses_slug ON houses (slug);
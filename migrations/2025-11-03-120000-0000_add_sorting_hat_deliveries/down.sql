@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_sorting_hat_deliveries_status;
+DROP TABLE IF EXISTS sorting_hat_deliveries;
@@ -0,0 +1,14 @@
+-- One row per request to light up the Sorting Hat. The server retries pending deliveries in the
+-- background; failed ones can be re-flashed from the admin dashboard.
+CREATE TABLE sorting_hat_deliveries (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  guest_id INTEGER REFERENCES guests(id) ON DELETE SET NULL,
+  house_id INTEGER NOT NULL REFERENCES houses(id) ON DELETE CASCADE,
+  status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'delivered', 'failed')),
+  attempts INTEGER NOT NULL DEFAULT 0,
+  last_error TEXT,
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_sorting_hat_deliveries_status ON sorting_hat_deliveries(status);
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_point_awards_reverts_award_id;
+
+ALTER TABLE point_awards DROP COLUMN reverts_award_id;
@@ -0,0 +1,4 @@
+-- A reversing entry points at the award it cancels. Each award can be reverted at most once.
+ALTER TABLE point_awards ADD COLUMN reverts_award_id INTEGER REFERENCES point_awards(id);
+
+CREATE UNIQUE INDEX idx_point_awards_reverts_award_id ON point_awards(reverts_award_id);
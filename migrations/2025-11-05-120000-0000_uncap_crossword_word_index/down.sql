@@ -0,0 +1,13 @@
+-- Completions of words past the seventh are dropped.
+CREATE TABLE house_crossword_completions_old (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  house_id INTEGER NOT NULL REFERENCES houses(id) ON DELETE CASCADE,
+  word_index INTEGER NOT NULL CHECK (word_index >= 0 AND word_index <=6),
+  completed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  UNIQUE(house_id, word_index)
+);
+INSERT INTO house_crossword_completions_old (id, house_id, word_index, completed_at)
+  SELECT id, house_id, word_index, completed_at FROM house_crossword_completions
+  WHERE word_index <= 6;
+DROP TABLE house_crossword_completions;
+ALTER TABLE house_crossword_completions_old RENAME TO house_crossword_completions;
@@ -0,0 +1,13 @@
+-- Puzzles may have any number of words, so word_index is no longer capped at 6. SQLite can't
+-- alter a CHECK constraint, so the table is rebuilt.
+CREATE TABLE house_crossword_completions_new (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  house_id INTEGER NOT NULL REFERENCES houses(id) ON DELETE CASCADE,
+  word_index INTEGER NOT NULL CHECK (word_index >= 0),
+  completed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  UNIQUE(house_id, word_index)
+);
+INSERT INTO house_crossword_completions_new (id, house_id, word_index, completed_at)
+  SELECT id, house_id, word_index, completed_at FROM house_crossword_completions;
+DROP TABLE house_crossword_completions;
+ALTER TABLE house_crossword_completions_new RENAME TO house_crossword_completions;
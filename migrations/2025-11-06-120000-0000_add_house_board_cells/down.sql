@@ -0,0 +1 @@
+DROP TABLE house_board_cells;
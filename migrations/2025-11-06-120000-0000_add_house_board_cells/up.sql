@@ -0,0 +1,13 @@
+-- The shared crossword grid of each house, used in house board mode. One row per cell that has
+-- ever been written; version goes up with every write so that stale edits can be rejected.
+CREATE TABLE house_board_cells (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  house_id INTEGER NOT NULL REFERENCES houses(id) ON DELETE CASCADE,
+  row_index INTEGER NOT NULL CHECK (row_index >= 0),
+  col_index INTEGER NOT NULL CHECK (col_index >= 0),
+  letter TEXT,
+  guest_id INTEGER REFERENCES guests(id) ON DELETE SET NULL,
+  version INTEGER NOT NULL DEFAULT 0,
+  updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  UNIQUE(house_id, row_index, col_index)
+);
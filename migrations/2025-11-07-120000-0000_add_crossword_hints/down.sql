@@ -0,0 +1,2 @@
+DROP TABLE crossword_hint_limits;
+DROP TABLE crossword_hints;
@@ -0,0 +1,18 @@
+-- Crossword hints taken by guests, each paid for with a (negative) house point award.
+CREATE TABLE crossword_hints (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  house_id INTEGER NOT NULL REFERENCES houses(id) ON DELETE CASCADE,
+  guest_id INTEGER REFERENCES guests(id) ON DELETE SET NULL,
+  word_index INTEGER NOT NULL CHECK (word_index >= 0),
+  kind TEXT NOT NULL CHECK (kind IN ('letter', 'word')),
+  cost INTEGER NOT NULL,
+  point_award_id INTEGER REFERENCES point_awards(id) ON DELETE SET NULL,
+  used_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- How many hints each house may take, set by the admin. Houses without a row get the default
+-- from the configuration.
+CREATE TABLE crossword_hint_limits (
+  house_id INTEGER PRIMARY KEY NOT NULL REFERENCES houses(id) ON DELETE CASCADE,
+  hint_limit INTEGER NOT NULL CHECK (hint_limit >= 0)
+);
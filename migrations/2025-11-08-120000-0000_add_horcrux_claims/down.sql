@@ -0,0 +1 @@
+DROP TABLE horcrux_claims;
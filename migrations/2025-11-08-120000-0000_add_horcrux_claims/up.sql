@@ -0,0 +1,18 @@
+-- A guest telling the admin their house found the horcrux a crossword word leads to, optionally
+-- with a photo (a file name in the configured photo directory). Approved claims score points.
+CREATE TABLE horcrux_claims (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  house_id INTEGER NOT NULL REFERENCES houses(id) ON DELETE CASCADE,
+  guest_id INTEGER REFERENCES guests(id) ON DELETE SET NULL,
+  word_index INTEGER NOT NULL CHECK (word_index >= 0),
+  horcrux TEXT NOT NULL,
+  photo_path TEXT,
+  status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'approved', 'rejected')),
+  point_award_id INTEGER REFERENCES point_awards(id) ON DELETE SET NULL,
+  claimed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  reviewed_at TIMESTAMP
+);
+
+-- A house may only have one pending or approved claim per horcrux.
+CREATE UNIQUE INDEX idx_horcrux_claims_open ON horcrux_claims(house_id, word_index)
+  WHERE status <> 'rejected';
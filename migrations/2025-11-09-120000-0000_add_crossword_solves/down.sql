@@ -0,0 +1,2 @@
+DROP TABLE crossword_solves;
+ALTER TABLE crossword_states DROP COLUMN started_at;
@@ -0,0 +1,18 @@
+-- When each guest first opened the crossword, so solves can be timed. Existing states were last
+-- saved at updated_at, which is the best guess left.
+ALTER TABLE crossword_states ADD COLUMN started_at TIMESTAMP;
+UPDATE crossword_states SET started_at = updated_at;
+
+-- Every crossword word solved by a guest, whether or not their house had solved it already.
+-- elapsed_seconds counts from started_at of the guest's crossword state.
+CREATE TABLE crossword_solves (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  guest_id INTEGER NOT NULL REFERENCES guests(id) ON DELETE CASCADE,
+  house_id INTEGER NOT NULL REFERENCES houses(id) ON DELETE CASCADE,
+  word_index INTEGER NOT NULL CHECK (word_index >= 0),
+  solved_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  elapsed_seconds INTEGER,
+  UNIQUE(guest_id, word_index)
+);
+
+CREATE INDEX idx_crossword_solves_house ON crossword_solves(house_id, word_index);
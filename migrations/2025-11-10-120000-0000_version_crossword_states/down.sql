@@ -0,0 +1,3 @@
+DROP INDEX idx_crossword_guest;
+CREATE INDEX idx_crossword_guest ON crossword_states(guest_id);
+ALTER TABLE crossword_states DROP COLUMN version;
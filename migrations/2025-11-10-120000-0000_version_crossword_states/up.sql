@@ -0,0 +1,9 @@
+-- Crossword states are now edited in place, a few cells at a time. Each save bumps the version, so
+-- that edits based on an older state (say, from the guest's second device) can be turned away.
+ALTER TABLE crossword_states ADD COLUMN version INTEGER NOT NULL DEFAULT 0;
+
+-- One state per guest. Keep the latest where there are more.
+DELETE FROM crossword_states
+WHERE id NOT IN (SELECT MAX(id) FROM crossword_states GROUP BY guest_id);
+DROP INDEX idx_crossword_guest;
+CREATE UNIQUE INDEX idx_crossword_guest ON crossword_states(guest_id);
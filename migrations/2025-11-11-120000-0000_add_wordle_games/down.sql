@@ -0,0 +1 @@
+DROP TABLE wordle_games;
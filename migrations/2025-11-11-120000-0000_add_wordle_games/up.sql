@@ -0,0 +1,14 @@
+-- One Wordle game per guest per day. The target word is picked by the server and never sent to
+-- the browser while the game is on; guesses holds the words guessed so far as a JSON array.
+CREATE TABLE wordle_games (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  guest_id INTEGER NOT NULL REFERENCES guests(id) ON DELETE CASCADE,
+  game_date DATE NOT NULL,
+  target TEXT NOT NULL,
+  guesses TEXT NOT NULL DEFAULT '[]',
+  status TEXT NOT NULL DEFAULT 'playing' CHECK (status IN ('playing', 'won', 'lost')),
+  point_award_id INTEGER REFERENCES point_awards(id) ON DELETE SET NULL,
+  started_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  finished_at TIMESTAMP,
+  UNIQUE(guest_id, game_date)
+);
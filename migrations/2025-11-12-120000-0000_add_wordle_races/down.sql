@@ -0,0 +1,2 @@
+DROP TABLE wordle_round_games;
+DROP TABLE wordle_rounds;
@@ -0,0 +1,27 @@
+-- Wordle races: rounds started by the admin in which every guest plays the same word, and the
+-- first house with enough solvers scores. The target is picked by the server and never sent to the
+-- browser while the round is on; a round ends when a house wins or the admin stops it.
+CREATE TABLE wordle_rounds (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  target TEXT NOT NULL,
+  solvers_needed INTEGER NOT NULL CHECK (solvers_needed > 0),
+  points INTEGER NOT NULL,
+  winning_house_id INTEGER REFERENCES houses(id) ON DELETE SET NULL,
+  point_award_id INTEGER REFERENCES point_awards(id) ON DELETE SET NULL,
+  started_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  ended_at TIMESTAMP
+);
+
+-- A guest's game in a round. house_id is the house they joined the race for, so that re-sorting
+-- them later doesn't move their solve.
+CREATE TABLE wordle_round_games (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  round_id INTEGER NOT NULL REFERENCES wordle_rounds(id) ON DELETE CASCADE,
+  guest_id INTEGER NOT NULL REFERENCES guests(id) ON DELETE CASCADE,
+  house_id INTEGER NOT NULL REFERENCES houses(id) ON DELETE CASCADE,
+  guesses TEXT NOT NULL DEFAULT '[]',
+  status TEXT NOT NULL DEFAULT 'playing' CHECK (status IN ('playing', 'won', 'lost')),
+  started_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  finished_at TIMESTAMP,
+  UNIQUE(round_id, guest_id)
+);
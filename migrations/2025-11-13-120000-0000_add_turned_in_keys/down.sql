@@ -0,0 +1,2 @@
+DROP TABLE key_completions;
+DROP TABLE turned_in_keys;
@@ -0,0 +1,16 @@
+-- Catch the Keys: every key a house turned in, recorded by Hagrid or Harry. key is a letter of the
+-- house's name, upper case, or the wildcard mark.
+CREATE TABLE turned_in_keys (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  house_id INTEGER NOT NULL REFERENCES houses(id) ON DELETE CASCADE,
+  key TEXT NOT NULL,
+  turned_in_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Houses that turned in all their keys, in the order they finished, with the points it scored.
+CREATE TABLE key_completions (
+  house_id INTEGER PRIMARY KEY REFERENCES houses(id) ON DELETE CASCADE,
+  place INTEGER NOT NULL UNIQUE,
+  point_award_id INTEGER REFERENCES point_awards(id) ON DELETE SET NULL,
+  completed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1 @@
+DROP TABLE physical_keys;
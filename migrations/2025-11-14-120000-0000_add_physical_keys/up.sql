@@ -0,0 +1,11 @@
+-- Catch the Keys: the physical keys hidden around the party, each printed with a QR code of its
+-- claim URL. key is an upper case letter or the wildcard mark. turned_in_key_id is set once a
+-- guest scans the key for their house, and cleared if that is taken back, so the key can be
+-- claimed again.
+CREATE TABLE physical_keys (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  key TEXT NOT NULL,
+  token TEXT NOT NULL UNIQUE,
+  turned_in_key_id INTEGER UNIQUE REFERENCES turned_in_keys(id) ON DELETE SET NULL,
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,26 @@
+-- Guest awards lose the house they were counted for. The table is rebuilt as in up.sql.
+PRAGMA foreign_keys = OFF;
+BEGIN;
+
+CREATE TABLE point_awards_old (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  guest_id INTEGER REFERENCES guests (id) ON DELETE SET NULL,
+  house_id INTEGER REFERENCES houses (id) ON DELETE SET NULL,
+  amount INTEGER NOT NULL,
+  reason TEXT NOT NULL,
+  awarded_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+  reverts_award_id INTEGER REFERENCES point_awards(id),
+  CHECK ((guest_id IS NOT NULL AND house_id IS NULL) OR (guest_id IS NULL AND house_id IS NOT NULL))
+);
+INSERT INTO point_awards_old (id, guest_id, house_id, amount, reason, awarded_at, reverts_award_id)
+  SELECT id, guest_id, CASE WHEN guest_id IS NULL THEN house_id END, amount, reason, awarded_at,
+    reverts_award_id
+  FROM point_awards;
+DROP TABLE point_awards;
+ALTER TABLE point_awards_old RENAME TO point_awards;
+
+CREATE UNIQUE INDEX idx_point_awards_reverts_award_id ON point_awards(reverts_award_id);
+
+PRAGMA foreign_key_check;
+COMMIT;
+PRAGMA foreign_keys = ON;
@@ -0,0 +1,28 @@
+-- Guest awards also record the house they were counted for, so re-sorting a guest doesn't move
+-- their earlier points. Awards logged before this keep a NULL house and count for the guest's
+-- current house. SQLite can't alter a CHECK constraint, so the table is rebuilt, with foreign keys
+-- off so that dropping the old table doesn't clear the links other tables hold to its awards
+-- (see https://www.sqlite.org/lang_altertable.html#otheralter).
+PRAGMA foreign_keys = OFF;
+BEGIN;
+
+CREATE TABLE point_awards_new (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  guest_id INTEGER REFERENCES guests (id) ON DELETE SET NULL,
+  house_id INTEGER REFERENCES houses (id) ON DELETE SET NULL,
+  amount INTEGER NOT NULL,
+  reason TEXT NOT NULL,
+  awarded_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+  reverts_award_id INTEGER REFERENCES point_awards(id),
+  CHECK (guest_id IS NOT NULL OR house_id IS NOT NULL)
+);
+INSERT INTO point_awards_new (id, guest_id, house_id, amount, reason, awarded_at, reverts_award_id)
+  SELECT id, guest_id, house_id, amount, reason, awarded_at, reverts_award_id FROM point_awards;
+DROP TABLE point_awards;
+ALTER TABLE point_awards_new RENAME TO point_awards;
+
+CREATE UNIQUE INDEX idx_point_awards_reverts_award_id ON point_awards(reverts_award_id);
+
+PRAGMA foreign_key_check;
+COMMIT;
+PRAGMA foreign_keys = ON;
@@ -0,0 +1 @@
+DROP TABLE wrong_key_claims;
@@ -0,0 +1,11 @@
+-- Catch the Keys: physical keys a guest scanned that aren't in their house's name. Each costs the
+-- guest points once; scanning the same key again repeats the earlier outcome.
+CREATE TABLE wrong_key_claims (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  guest_id INTEGER NOT NULL REFERENCES guests(id) ON DELETE CASCADE,
+  physical_key_id INTEGER NOT NULL REFERENCES physical_keys(id) ON DELETE CASCADE,
+  penalty INTEGER NOT NULL,
+  point_award_id INTEGER REFERENCES point_awards(id) ON DELETE SET NULL,
+  claimed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  UNIQUE(guest_id, physical_key_id)
+);
@@ -9,32 +9,59 @@ use leptos_router::{
     path, NavigateOptions,
 };
 use qrcode::render::svg;
-use rand::prelude::*;
-use rand::rng;
 use std::collections::HashMap;
 use std::env;
+use std::time::Duration;
 #[cfg(feature = "hydrate")]
 use wasm_bindgen::JsCast;
 
-#[cfg(feature = "ssr")]
-use crate::{
-    award_points_to_house, close_voting, create_admin_session, get_all_active_guests,
-    get_all_houses, get_all_point_awards, get_all_unregistered_guests, get_games_enabled,
-    get_guest_by_token, get_guest_token, get_house_crossword_progress, get_or_init_crossword_state,
-    get_rcv_result, get_user_vote, get_voting_stats, has_voted, init_voting_status, open_voting,
-    register_guest, reregister_guest, reset_votes, submit_vote, toggle_games_enabled,
-    unregister_guest, update_crossword_state, validate_admin_token, voting_is_open,
+use crate::model::{
+    BoardEdit, BoardEditResult, CellEdit, ClaimStatus, CrosswordHintLog, CrosswordSolveLog, Guest,
+    GuestCrossword, HintKind, HintStatus, HorcruxClaimLog, House, HouseBoard, HouseHintUsage,
+    HouseKeys, HouseLeaderboard, KeyClaim, KeyQr, PhotoUpload, PointAwardLog, RcvResult,
+    RosterImportReport, ScoreMismatch, Scoreboard, SortingHatOverview, WordleGame, WordleGuess,
+    WordleRace, WordleStatus,
 };
+#[cfg(feature = "ssr")]
 use crate::{
-    model::{CrosswordState, Guest, House, PointAwardLog, RcvResult, SparseState},
-    Direction, WordDef, CROSSWORD_DEFS,
+    award_points_to_guest, award_points_to_house, claim_physical_key, close_voting,
+    create_admin_session, create_horcrux_claim, create_sorting_hat_delivery, edit_crossword_state,
+    edit_house_board, get_all_active_guests, get_all_houses, get_all_point_awards,
+    get_all_unregistered_guests, get_crossword_hint_log, get_crossword_leaderboard,
+    get_crossword_timeline, get_games_enabled, get_guest_by_token, get_guest_details,
+    get_guest_point_awards, get_guest_token, get_hint_usage, get_horcrux_claim_photo,
+    get_horcrux_claims, get_house_board, get_house_crossword_progress, get_house_horcrux_claims,
+    get_house_keys, get_or_init_crossword_state, get_or_join_wordle_race, get_or_start_wordle_game,
+    get_physical_keys, get_rcv_result, get_recent_point_awards, get_sorting_hat_deliveries,
+    get_sorting_hat_delivery, get_user_vote, get_voting_stats, get_wordle_race, has_voted,
+    import_guest_roster, init_voting_status, load_horcrux_photo, open_voting, parse_roster,
+    reconcile_scores, register_guest_with_plan, reregister_guest, reset_votes, revert_point_award,
+    review_horcrux_claim, save_horcrux_photo, set_house_hint_limit, sorting_hat::SortingHatQueue,
+    start_wordle_race, stop_wordle_race, submit_vote, submit_wordle_guess,
+    submit_wordle_race_guess, take_back_key, take_crossword_hint, toggle_games_enabled,
+    turn_in_key, unregister_guest, validate_admin_token, verify_scores, voting_is_open,
 };
 
+#[cfg(feature = "ssr")]
+use crate::config::AppConfig;
+use crate::config::ClientConfig;
+#[cfg(feature = "ssr")]
+use crate::config::GameToggles;
+use crate::crossword::{Direction, Puzzle, PuzzleLayout};
+#[cfg(feature = "ssr")]
+use crate::events::publish;
+use crate::events::{use_live_events, LiveEvent};
+use crate::keys::{key_tiles, ordinal, WILDCARD};
+#[cfg(feature = "ssr")]
+use crate::wordle::WordList;
+use crate::wordle::{LetterStatus, MAX_GUESSES, WORD_LENGTH};
 #[cfg(feature = "ssr")]
 use diesel::r2d2::{ConnectionManager, Pool};
 #[cfg(feature = "ssr")]
 use diesel::SqliteConnection;
 #[cfg(feature = "ssr")]
+use std::sync::Arc;
+#[cfg(feature = "ssr")]
 pub type DbPool = Pool<ConnectionManager<SqliteConnection>>;
 
 #[derive(Debug, Clone, thiserror::Error, serde::Serialize, serde::Deserialize)]
@@ -45,6 +72,8 @@ pub enum AppError {
     HttpError(String),
     #[error("Authorization error: {0}")]
     AuthError(String),
+    #[error("Invalid input: {0}")]
+    InputError(String),
     #[error("Server framework error: {0}")]
     ServerFnError(#[from] ServerFnErrorErr),
 }
@@ -74,23 +103,32 @@ pub async fn get_qr_for_guest(guest_id: i32) -> Result<String, AppError> {
     .await
     .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))??;
 
-    let base_url = "http://192.168.1.165:3000";
-    let login_url = format!("{}/login?guest_id={}&token={}", base_url, guest_id, token);
+    let config: AppConfig = expect_context();
+    qr_svg(&config.login_url(guest_id, &token))
+}
 
-    let qr_code = qrcode::QrCode::new(login_url.as_bytes()).map_err(|e| {
+// Renders a QR code for the given URL (a login or key claim URL) as an SVG string.
+#[cfg(feature = "ssr")]
+fn qr_svg(url: &str) -> Result<String, AppError> {
+    let qr_code = qrcode::QrCode::new(url.as_bytes()).map_err(|e| {
         AppError::ServerFnError(ServerFnErrorErr::ServerError(format!(
             "QR generation failed: {}",
             e
         )))
     })?;
-    let qr_svg = qr_code
+    Ok(qr_code
         .render::<svg::Color<'_>>()
         .min_dimensions(200, 200)
         .dark_color(svg::Color("#000000"))
         .light_color(svg::Color("#FFFFFF"))
-        .build();
+        .build())
+}
 
-    Ok(qr_svg)
+// Returns the configuration the browser needs. Requires no login.
+#[server(GetClientConfig)]
+pub async fn get_client_config() -> Result<ClientConfig, AppError> {
+    let config: AppConfig = expect_context();
+    Ok(config.client_config())
 }
 
 #[server(GetHouses)]
@@ -218,6 +256,20 @@ async fn check_admin() -> Result<(), AppError> {
     }
 }
 
+// Returns an empty result if the game picked out by `enabled` is part of this party, or an error
+// otherwise. Pages for games switched off aren't linked, but the server functions stay callable.
+#[cfg(feature = "ssr")]
+fn check_game(enabled: fn(&GameToggles) -> bool) -> Result<(), AppError> {
+    let config: AppConfig = expect_context();
+    if enabled(&config.games) {
+        Ok(())
+    } else {
+        Err(AppError::AuthError(
+            "That game isn't part of this party".to_string(),
+        ))
+    }
+}
+
 #[server(AdminLogin)]
 pub async fn admin_login(password: String) -> Result<(), AppError> {
     let pool: DbPool = expect_context();
@@ -236,10 +288,12 @@ pub async fn admin_login(password: String) -> Result<(), AppError> {
     .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))??;
 
     use leptos_axum::ResponseOptions;
+    let config: AppConfig = expect_context();
     let resp: ResponseOptions = expect_context();
     let cookie = format!(
-        "admin_token={}; Max-Age=86400; Path=/; HttpOnly; SameSite=Strict",
-        token
+        "admin_token={}; Max-Age={}; Path=/; HttpOnly; SameSite=Strict",
+        token,
+        u64::from(config.admin_session_hours) * 3600
     );
     resp.insert_header(
         axum::http::header::SET_COOKIE,
@@ -272,34 +326,46 @@ pub async fn register_guest_handler(
     check_admin().await?;
 
     let pool: DbPool = expect_context();
+    let config: AppConfig = expect_context();
+    let sorting_config = config.clone();
+    let sorting_hat = use_context::<SortingHatQueue>();
+    let notify_hat = sorting_hat.is_some();
 
-    let (token, assigned_house_id) = tokio::task::spawn_blocking(move || {
+    let (token, assigned_house_id, delivery_id) = tokio::task::spawn_blocking(move || {
         let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
         let effective_house_id = if house_id == 0 { None } else { Some(house_id) };
-        let (guest, token) = register_guest(&mut conn, guest_id, effective_house_id, &character)
-            .map_err(|e| AppError::DbError(e.to_string()))?;
+        let houses = get_all_houses(&mut conn).map_err(|e| AppError::DbError(e.to_string()))?;
+        let plan = sorting_config.sorting_plan(&houses);
+        let (guest, token) =
+            register_guest_with_plan(&mut conn, guest_id, effective_house_id, &character, &plan)
+                .map_err(|e| AppError::DbError(e.to_string()))?;
         // Registered guests should have a house assigned. Panic if they don't.
         let assigned_house_id = guest.house_id.unwrap();
-        Ok::<(std::string::String, i32), AppError>((token, assigned_house_id))
+        // The guest is registered at this point, so a failure to log the delivery only costs the
+        // hat's announcement.
+        let delivery_id = notify_hat
+            .then(|| {
+                create_sorting_hat_delivery(&mut conn, Some(guest_id), assigned_house_id)
+                    .map_err(|e| log!("Failed to log Sorting Hat delivery: {}", e))
+                    .ok()
+            })
+            .flatten()
+            .map(|delivery| delivery.id);
+        Ok::<(std::string::String, i32, Option<i32>), AppError>((
+            token,
+            assigned_house_id,
+            delivery_id,
+        ))
     })
     .await
     .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))??;
 
-    let base_url = "http://192.168.1.165:3000";
-    let login_url = format!("{}/login?guest_id={}&token={}", base_url, guest_id, token);
+    if let (Some(queue), Some(delivery_id)) = (sorting_hat, delivery_id) {
+        queue.enqueue(delivery_id);
+    }
+    publish(LiveEvent::GuestsChanged);
 
-    let qr_code = qrcode::QrCode::new(login_url.as_bytes()).map_err(|e| {
-        AppError::ServerFnError(ServerFnErrorErr::ServerError(format!(
-            "QR generation failed: {}",
-            e
-        )))
-    })?;
-    let qr_svg = qr_code
-        .render::<svg::Color<'_>>()
-        .min_dimensions(200, 200)
-        .dark_color(svg::Color("#000000"))
-        .light_color(svg::Color("#FFFFFF"))
-        .build();
+    let qr_svg = qr_svg(&config.login_url(guest_id, &token))?;
 
     Ok((token, assigned_house_id, qr_svg))
 }
@@ -317,6 +383,7 @@ pub async fn unregister_guest_handler(guest_id: i32) -> Result<(), AppError> {
     })
     .await
     .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
+    .inspect(|_| publish(LiveEvent::GuestsChanged))
 }
 
 #[server(ReregisterGuest)]
@@ -338,6 +405,76 @@ pub async fn reregister_guest_handler(
     })
     .await
     .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
+    .inspect(|_| publish(LiveEvent::GuestsChanged))
+}
+
+#[server(ImportGuestRoster)]
+pub async fn import_guest_roster_handler(
+    contents: String,
+    replace: bool,
+) -> Result<RosterImportReport, AppError> {
+    check_admin().await?;
+
+    let names = parse_roster(&contents).map_err(AppError::InputError)?;
+
+    let pool: DbPool = expect_context();
+
+    tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        import_guest_roster(&mut conn, &names, replace)
+            .map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
+    .inspect(|_| publish(LiveEvent::GuestsChanged))
+}
+
+// Returns the configured Sorting Hat endpoint (if any) and the most recent deliveries to it.
+#[server(GetSortingHatDeliveries)]
+pub async fn get_sorting_hat_deliveries_handler() -> Result<SortingHatOverview, AppError> {
+    check_admin().await?;
+
+    let pool: DbPool = expect_context();
+    let config: AppConfig = expect_context();
+
+    let deliveries = tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        get_sorting_hat_deliveries(&mut conn, 50).map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))??;
+
+    Ok(SortingHatOverview {
+        endpoint: config.sorting_hat_url,
+        deliveries,
+    })
+}
+
+// Asks the Sorting Hat to announce the house of an earlier delivery again, as a new delivery.
+#[server(ReflashSortingHat)]
+pub async fn reflash_sorting_hat_handler(delivery_id: i32) -> Result<(), AppError> {
+    check_admin().await?;
+
+    let Some(queue) = use_context::<SortingHatQueue>() else {
+        return Err(AppError::HttpError(
+            "The Sorting Hat is not configured".to_string(),
+        ));
+    };
+    let pool: DbPool = expect_context();
+
+    let new_delivery_id = tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        let delivery = get_sorting_hat_delivery(&mut conn, delivery_id)
+            .map_err(|e| AppError::DbError(e.to_string()))?;
+        create_sorting_hat_delivery(&mut conn, delivery.guest_id, delivery.house_id)
+            .map(|delivery| delivery.id)
+            .map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))??;
+
+    queue.enqueue(new_delivery_id);
+    Ok(())
 }
 
 #[server(AwardPointsToHouse)]
@@ -358,6 +495,112 @@ pub async fn award_points_to_house_handler(
     })
     .await
     .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
+    .inspect(|_| publish(LiveEvent::PointsAwarded { house_id, amount }))
+}
+
+#[server(AwardPointsToGuest)]
+pub async fn award_points_to_guest_handler(
+    guest_id: i32,
+    amount: i32,
+    reason: String,
+) -> Result<(), AppError> {
+    check_admin().await?;
+
+    let pool: DbPool = expect_context();
+
+    let house_id = tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        award_points_to_guest(&mut conn, guest_id, amount, &reason)
+            .map_err(|e| AppError::DbError(e.to_string()))?;
+        get_guest_details(&mut conn, guest_id)
+            .map(|(_, house)| house.id)
+            .map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))??;
+
+    publish(LiveEvent::PointsAwarded { house_id, amount });
+    Ok(())
+}
+
+// Reverts a point award with a linked reversing entry, taking the points back.
+#[server(RevertPointAward)]
+pub async fn revert_point_award_handler(award_id: i32) -> Result<(), AppError> {
+    check_admin().await?;
+
+    let pool: DbPool = expect_context();
+
+    let (house_id, amount) = tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        let reversal = revert_point_award(&mut conn, award_id)
+            .map_err(|e| AppError::DbError(e.to_string()))?;
+        let house_id = match (reversal.house_id, reversal.guest_id) {
+            (Some(house_id), _) => Some(house_id),
+            (None, Some(guest_id)) => get_guest_details(&mut conn, guest_id)
+                .ok()
+                .map(|(_, house)| house.id),
+            (None, None) => None,
+        };
+        Ok::<_, AppError>((house_id, reversal.amount))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))??;
+
+    match house_id {
+        Some(house_id) => publish(LiveEvent::PointsAwarded { house_id, amount }),
+        None => publish(LiveEvent::Resync),
+    }
+    Ok(())
+}
+
+// Returns the cached scores that disagree with the point award ledger.
+#[server(GetScoreMismatches)]
+pub async fn get_score_mismatches() -> Result<Vec<ScoreMismatch>, AppError> {
+    check_admin().await?;
+
+    let pool: DbPool = expect_context();
+
+    tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        verify_scores(&mut conn).map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
+}
+
+// Overwrites the mismatched cached scores with their ledger totals.
+#[server(ReconcileScores)]
+pub async fn reconcile_scores_handler() -> Result<Vec<ScoreMismatch>, AppError> {
+    check_admin().await?;
+
+    let pool: DbPool = expect_context();
+
+    tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        reconcile_scores(&mut conn).map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
+    .inspect(|fixed| {
+        if !fixed.is_empty() {
+            publish(LiveEvent::Resync);
+        }
+    })
+}
+
+// Returns the logged-in guest's own point awards, newest first.
+#[server(GetMyPointAwards)]
+pub async fn get_my_point_awards() -> Result<Vec<PointAwardLog>, AppError> {
+    let maybe_current_user = get_current_user().await?;
+    let guest = maybe_current_user.ok_or(AppError::AuthError("Must be logged in".to_string()))?;
+    let pool: DbPool = expect_context();
+
+    tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        get_guest_point_awards(&mut conn, guest.id).map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
 }
 
 #[server(GetPointAwards)]
@@ -379,10 +622,67 @@ pub async fn get_house_crossword_progress_handler() -> Result<Vec<Vec<bool>>, Ap
     check_admin().await?;
 
     let pool: DbPool = expect_context();
+    let puzzle: Arc<Puzzle> = expect_context();
+
+    tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        get_house_crossword_progress(&mut conn, puzzle.words.len())
+            .map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
+}
+
+// Number of recent point awards in the scoreboard ticker.
+#[cfg(feature = "ssr")]
+const SCOREBOARD_AWARDS: i64 = 12;
+
+// Returns everything the projector scoreboard shows. Requires no login.
+#[server(GetScoreboard)]
+pub async fn get_scoreboard() -> Result<Scoreboard, AppError> {
+    let pool: DbPool = expect_context();
+    let puzzle: Arc<Puzzle> = expect_context();
+
+    tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        let db_error = |e: diesel::result::Error| AppError::DbError(e.to_string());
+        init_voting_status(&mut conn).map_err(db_error)?;
+        let (votes_cast, eligible_voters) = get_voting_stats(&mut conn).map_err(db_error)?;
+        Ok(Scoreboard {
+            houses: get_all_houses(&mut conn).map_err(db_error)?,
+            recent_awards: get_recent_point_awards(&mut conn, SCOREBOARD_AWARDS)
+                .map_err(db_error)?,
+            crossword_progress: get_house_crossword_progress(&mut conn, puzzle.words.len())
+                .map_err(db_error)?,
+            voting_open: voting_is_open(&mut conn).map_err(db_error)?,
+            votes_cast,
+            eligible_voters,
+        })
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
+}
+
+// Every guest's crossword solves in the order they happened, for the admin report.
+#[server(GetCrosswordTimeline)]
+pub async fn get_crossword_timeline_handler() -> Result<Vec<CrosswordSolveLog>, AppError> {
+    check_admin().await?;
+    let pool: DbPool = expect_context();
+    tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        get_crossword_timeline(&mut conn).map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
+}
 
+// The fastest crossword solvers of each house. Public, like the scoreboard.
+#[server(GetCrosswordLeaderboard)]
+pub async fn get_crossword_leaderboard_handler() -> Result<Vec<HouseLeaderboard>, AppError> {
+    let pool: DbPool = expect_context();
     tokio::task::spawn_blocking(move || {
         let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
-        get_house_crossword_progress(&mut conn).map_err(|e| AppError::DbError(e.to_string()))
+        get_crossword_leaderboard(&mut conn).map_err(|e| AppError::DbError(e.to_string()))
     })
     .await
     .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
@@ -410,10 +710,12 @@ pub async fn login_handler(guest_id: i32, token: String) -> Result<(), AppError>
     .await
     .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))??;
 
+    let config: AppConfig = expect_context();
     let resp: ResponseOptions = expect_context();
     let cookie = format!(
-        "session_token={}; Max-Age=86400; Path=/; HttpOnly; SameSite=Strict",
-        token
+        "session_token={}; Max-Age={}; Path=/; HttpOnly; SameSite=Strict",
+        token,
+        u64::from(config.guest_session_hours) * 3600
     );
     resp.insert_header(
         axum::http::header::SET_COOKIE,
@@ -424,774 +726,1459 @@ pub async fn login_handler(guest_id: i32, token: String) -> Result<(), AppError>
     Ok(())
 }
 
+// Returns the whole crossword puzzle, answers included, for the admin dashboard.
+#[server(GetCrosswordPuzzle)]
+pub async fn get_crossword_puzzle() -> Result<Puzzle, AppError> {
+    check_admin().await?;
+
+    let puzzle: Arc<Puzzle> = expect_context();
+    Ok(puzzle.as_ref().clone())
+}
+
+// Returns the crossword grid and clues for guests, without the answers.
+#[server(GetCrosswordLayout)]
+pub async fn get_crossword_layout() -> Result<PuzzleLayout, AppError> {
+    check_game(|games| games.crossword)?;
+    let puzzle: Arc<Puzzle> = expect_context();
+    Ok(puzzle.layout())
+}
+
+// In house board mode this is the guest's house board rather than their own grid.
 #[server(GetCrosswordState)]
-pub async fn get_crossword_state() -> Result<CrosswordState, AppError> {
+pub async fn get_crossword_state() -> Result<GuestCrossword, AppError> {
+    check_game(|games| games.crossword)?;
     let pool: DbPool = expect_context();
+    let puzzle: Arc<Puzzle> = expect_context();
+    let config: AppConfig = expect_context();
     let maybe_current_user = get_current_user().await?;
     let guest = maybe_current_user.ok_or(AppError::AuthError("Must be logged in".to_string()))?;
     tokio::task::spawn_blocking(move || {
         let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
-        get_or_init_crossword_state(&mut conn, guest.id)
+        if config.crossword_house_board {
+            let house_id = guest.house_id.ok_or(AppError::AuthError(
+                "Must be sorted into a house".to_string(),
+            ))?;
+            // The guest's own state goes unused, but starts the clock their solves are timed by.
+            get_or_init_crossword_state(&mut conn, &puzzle, guest.id)
+                .map_err(|e| AppError::DbError(e.to_string()))?;
+            return get_house_board(&mut conn, &puzzle, house_id)
+                .map_err(|e| AppError::DbError(e.to_string()));
+        }
+        get_or_init_crossword_state(&mut conn, &puzzle, guest.id)
+            .map(|state| GuestCrossword::new(state, &puzzle))
             .map_err(|e| AppError::DbError(e.to_string()))
     })
     .await
     .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
 }
 
-#[server(UpdateCrosswordState)]
-pub async fn update_crossword_state_handler(sparse_state: SparseState) -> Result<(), AppError> {
+// Writes letters on the guest's own crossword and returns it afterwards, with the words the server
+// found solved. `version` is the version of the crossword the letters were typed on; if it has been
+// saved since, from another device, all the edits are rejected.
+#[server(EditCrossword)]
+pub async fn edit_crossword_handler(
+    version: i32,
+    edits: Vec<CellEdit>,
+) -> Result<BoardEditResult, AppError> {
+    check_game(|games| games.crossword)?;
     let pool: DbPool = expect_context();
+    let puzzle: Arc<Puzzle> = expect_context();
+    let config: AppConfig = expect_context();
+    if config.crossword_house_board {
+        return Err(AppError::AuthError(
+            "The crossword is played on house boards".to_string(),
+        ));
+    }
     let maybe_current_user = get_current_user().await?;
     let guest = maybe_current_user.ok_or(AppError::AuthError("Must be logged in".to_string()))?;
-    tokio::task::spawn_blocking(move || {
+    let guest_id = guest.id;
+    let (result, new_house_words) = tokio::task::spawn_blocking(move || {
         let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
-        let mut grid = vec![vec![None; 12]; 15];
-        for (r, c, ch) in &sparse_state.filled {
-            if *r < 15 && *c < 12 {
-                grid[*r][*c] = Some(*ch);
-            }
-        }
-        let full_state = CrosswordState::new_full_grid(grid, sparse_state.completions);
-        update_crossword_state(&mut conn, guest.id, &full_state)
+        edit_crossword_state(&mut conn, &puzzle, guest_id, version, &edits)
+            .map(|update| {
+                (
+                    BoardEditResult {
+                        crossword: GuestCrossword::new(update.state, &puzzle),
+                        rejected: update.rejected,
+                    },
+                    update.new_house_words,
+                )
+            })
             .map_err(|e| AppError::DbError(e.to_string()))
     })
     .await
-    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))??;
+
+    if let Some(house_id) = guest.house_id {
+        for word_index in new_house_words {
+            publish(LiveEvent::CrosswordWordCompleted {
+                house_id,
+                word_index,
+            });
+        }
+    }
+    Ok(result)
 }
 
-#[server(VotingIsOpen)]
-pub async fn voting_is_open_handler() -> Result<bool, AppError> {
+// Writes letters on the guest's house board (house board mode) and returns the board afterwards,
+// with the edits that lost to a teammate's.
+#[server(EditHouseBoard)]
+pub async fn edit_house_board_handler(edits: Vec<BoardEdit>) -> Result<BoardEditResult, AppError> {
+    check_game(|games| games.crossword)?;
     let pool: DbPool = expect_context();
-    tokio::task::spawn_blocking(move || {
+    let puzzle: Arc<Puzzle> = expect_context();
+    let config: AppConfig = expect_context();
+    if !config.crossword_house_board {
+        return Err(AppError::AuthError(
+            "House boards are not enabled".to_string(),
+        ));
+    }
+    let maybe_current_user = get_current_user().await?;
+    let guest = maybe_current_user.ok_or(AppError::AuthError("Must be logged in".to_string()))?;
+    let (update, crossword) = tokio::task::spawn_blocking(move || {
         let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
-        init_voting_status(&mut conn).map_err(|e| AppError::DbError(e.to_string()))?;
-        voting_is_open(&mut conn).map_err(|e| AppError::DbError(e.to_string()))
+        let update = edit_house_board(&mut conn, &puzzle, guest.id, &edits)
+            .map_err(|e| AppError::DbError(e.to_string()))?;
+        let crossword = get_house_board(&mut conn, &puzzle, update.house_id)
+            .map_err(|e| AppError::DbError(e.to_string()))?;
+        Ok::<_, AppError>((update, crossword))
     })
     .await
-    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
-}
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))??;
 
-#[server(OpenVoting)]
-pub async fn open_voting_handler() -> Result<(), AppError> {
-    check_admin().await?;
-    let pool: DbPool = expect_context();
-    tokio::task::spawn_blocking(move || {
-        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
-        init_voting_status(&mut conn).map_err(|e| AppError::DbError(e.to_string()))?;
-        open_voting(&mut conn).map_err(|e| AppError::DbError(e.to_string()))
+    if update.applied > 0 {
+        publish(LiveEvent::HouseBoardChanged {
+            house_id: update.house_id,
+        });
+    }
+    for word_index in update.new_house_words {
+        publish(LiveEvent::CrosswordWordCompleted {
+            house_id: update.house_id,
+            word_index,
+        });
+    }
+    Ok(BoardEditResult {
+        crossword,
+        rejected: update.rejected,
     })
-    .await
-    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
 }
 
-#[server(CloseVoting)]
-pub async fn close_voting_handler() -> Result<RcvResult, AppError> {
-    check_admin().await?;
+// The hint prices and how many hints the guest's house has left.
+#[server(GetHintStatus)]
+pub async fn get_hint_status() -> Result<HintStatus, AppError> {
+    check_game(|games| games.crossword)?;
     let pool: DbPool = expect_context();
+    let config: AppConfig = expect_context();
+    let maybe_current_user = get_current_user().await?;
+    let guest = maybe_current_user.ok_or(AppError::AuthError("Must be logged in".to_string()))?;
+    let house_id = guest.house_id.ok_or(AppError::AuthError(
+        "Must be sorted into a house".to_string(),
+    ))?;
     tokio::task::spawn_blocking(move || {
         let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
-        init_voting_status(&mut conn).map_err(|e| AppError::DbError(e.to_string()))?;
-        close_voting(&mut conn).map_err(|e| AppError::DbError(e.to_string()))
+        let hints = &config.crossword_hints;
+        let usage = get_hint_usage(&mut conn, hints.default_limit)
+            .map_err(|e| AppError::DbError(e.to_string()))?
+            .into_iter()
+            .find(|usage| usage.house_id == house_id)
+            .ok_or(AppError::DbError("House not found".to_string()))?;
+        Ok(HintStatus {
+            letter_cost: hints.letter_cost,
+            word_cost: hints.word_cost,
+            used: usage.used,
+            limit: usage.limit,
+        })
     })
     .await
     .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
 }
 
-#[server(HasVoted)]
-pub async fn has_voted_handler() -> Result<bool, AppError> {
-    let maybe_user = get_current_user().await?;
-    let guest = maybe_user.ok_or(AppError::AuthError("Must be logged in".to_string()))?;
+// Reveals a letter or the whole of a word at the cost of house points, and returns the guest's
+// crossword with it filled in.
+#[server(TakeCrosswordHint)]
+pub async fn take_crossword_hint_handler(
+    word_index: usize,
+    kind: HintKind,
+) -> Result<GuestCrossword, AppError> {
+    check_game(|games| games.crossword)?;
     let pool: DbPool = expect_context();
-    tokio::task::spawn_blocking(move || {
+    let puzzle: Arc<Puzzle> = expect_context();
+    let config: AppConfig = expect_context();
+    let maybe_current_user = get_current_user().await?;
+    let guest = maybe_current_user.ok_or(AppError::AuthError("Must be logged in".to_string()))?;
+    let house_board = config.crossword_house_board;
+    let (taken, crossword) = tokio::task::spawn_blocking(move || {
         let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
-        has_voted(&mut conn, guest.id).map_err(|e| AppError::DbError(e.to_string()))
+        let taken = take_crossword_hint(
+            &mut conn,
+            &puzzle,
+            &config.crossword_hints,
+            guest.id,
+            word_index,
+            kind,
+            house_board,
+        )
+        .map_err(|e| AppError::DbError(e.to_string()))?;
+        let crossword = if house_board {
+            get_house_board(&mut conn, &puzzle, taken.house_id)
+        } else {
+            get_or_init_crossword_state(&mut conn, &puzzle, guest.id)
+                .map(|state| GuestCrossword::new(state, &puzzle))
+        }
+        .map_err(|e| AppError::DbError(e.to_string()))?;
+        Ok::<_, AppError>((taken, crossword))
     })
     .await
-    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))??;
+
+    publish(LiveEvent::PointsAwarded {
+        house_id: taken.house_id,
+        amount: -taken.cost,
+    });
+    if house_board {
+        publish(LiveEvent::HouseBoardChanged {
+            house_id: taken.house_id,
+        });
+    }
+    for word_index in taken.new_house_words {
+        publish(LiveEvent::CrosswordWordCompleted {
+            house_id: taken.house_id,
+            word_index,
+        });
+    }
+    Ok(crossword)
 }
 
-#[server(SubmitVote)]
-pub async fn submit_vote_handler(first: i32, second: i32, third: i32) -> Result<(), AppError> {
-    let maybe_user = get_current_user().await?;
-    let guest = maybe_user.ok_or(AppError::AuthError("Must be logged in".to_string()))?;
+#[server(GetHintUsage)]
+pub async fn get_hint_usage_handler() -> Result<Vec<HouseHintUsage>, AppError> {
+    check_admin().await?;
     let pool: DbPool = expect_context();
+    let config: AppConfig = expect_context();
     tokio::task::spawn_blocking(move || {
         let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
-        init_voting_status(&mut conn).map_err(|e| AppError::DbError(e.to_string()))?;
-        submit_vote(&mut conn, guest.id, first, second, third)
+        get_hint_usage(&mut conn, config.crossword_hints.default_limit)
             .map_err(|e| AppError::DbError(e.to_string()))
     })
     .await
     .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
 }
 
-#[server(GetRcvResult)]
-pub async fn get_rcv_result_handler() -> Result<RcvResult, AppError> {
+#[server(SetHouseHintLimit)]
+pub async fn set_house_hint_limit_handler(house_id: i32, limit: i32) -> Result<(), AppError> {
+    check_admin().await?;
     let pool: DbPool = expect_context();
     tokio::task::spawn_blocking(move || {
         let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
-        init_voting_status(&mut conn).map_err(|e| AppError::DbError(e.to_string()))?;
-        get_rcv_result(&mut conn).map_err(|e| AppError::DbError(e.to_string()))
+        set_house_hint_limit(&mut conn, house_id, limit)
+            .map_err(|e| AppError::DbError(e.to_string()))
     })
     .await
     .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
 }
 
-#[server(GetActiveGuestsForVoting)]
-pub async fn get_active_guests_for_voting() -> Result<Vec<Guest>, AppError> {
+#[server(GetCrosswordHintLog)]
+pub async fn get_crossword_hint_log_handler() -> Result<Vec<CrosswordHintLog>, AppError> {
+    check_admin().await?;
     let pool: DbPool = expect_context();
     tokio::task::spawn_blocking(move || {
         let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
-        get_all_active_guests(&mut conn).map_err(|e| AppError::DbError(e.to_string()))
+        get_crossword_hint_log(&mut conn).map_err(|e| AppError::DbError(e.to_string()))
     })
     .await
     .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
 }
 
-#[server(GetUserVote)]
-pub async fn get_user_vote_handler() -> Result<Option<(Guest, Guest, Guest)>, AppError> {
-    let maybe_user = get_current_user().await?;
-    let guest = maybe_user.ok_or(AppError::AuthError("Must be logged in".to_string()))?;
+// Claims that the guest's house found the horcrux hidden behind a solved word, optionally with a
+// photo of it, for an admin to review.
+#[server(ClaimHorcrux)]
+pub async fn claim_horcrux(
+    word_index: usize,
+    photo: Option<PhotoUpload>,
+) -> Result<Vec<Option<ClaimStatus>>, AppError> {
+    check_game(|games| games.crossword)?;
+    let pool: DbPool = expect_context();
+    let puzzle: Arc<Puzzle> = expect_context();
+    let config: AppConfig = expect_context();
+    let maybe_current_user = get_current_user().await?;
+    let guest = maybe_current_user.ok_or(AppError::AuthError("Must be logged in".to_string()))?;
+    let house_id = guest.house_id.ok_or(AppError::AuthError(
+        "Must be sorted into a house".to_string(),
+    ))?;
+    let statuses = tokio::task::spawn_blocking(move || {
+        let photo_dir = config.horcrux_photo_dir;
+        let photo_path = photo
+            .map(|photo| save_horcrux_photo(&photo_dir, &photo))
+            .transpose()
+            .map_err(AppError::DbError)?;
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        if let Err(e) =
+            create_horcrux_claim(&mut conn, &puzzle, guest.id, word_index, photo_path.clone())
+        {
+            // Nothing refers to the photo without its claim.
+            if let Some(file_name) = photo_path {
+                let _ = std::fs::remove_file(std::path::Path::new(&photo_dir).join(file_name));
+            }
+            return Err(AppError::DbError(e.to_string()));
+        }
+        get_house_horcrux_claims(&mut conn, house_id, puzzle.words.len())
+            .map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))??;
+
+    publish(LiveEvent::HorcruxClaimsChanged { house_id });
+    Ok(statuses)
+}
+
+// Where the guest's house stands with each word's horcrux: unclaimed, or the status of its claim.
+#[server(GetHorcruxClaims)]
+pub async fn get_horcrux_claims_handler() -> Result<Vec<Option<ClaimStatus>>, AppError> {
     let pool: DbPool = expect_context();
+    let puzzle: Arc<Puzzle> = expect_context();
+    let maybe_current_user = get_current_user().await?;
+    let guest = maybe_current_user.ok_or(AppError::AuthError("Must be logged in".to_string()))?;
+    let Some(house_id) = guest.house_id else {
+        return Ok(vec![None; puzzle.words.len()]);
+    };
     tokio::task::spawn_blocking(move || {
         let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
-        get_user_vote(&mut conn, guest.id).map_err(|e| AppError::DbError(e.to_string()))
+        get_house_horcrux_claims(&mut conn, house_id, puzzle.words.len())
+            .map_err(|e| AppError::DbError(e.to_string()))
     })
     .await
     .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
 }
 
-#[server(ResetVotes)]
-pub async fn reset_votes_handler() -> Result<(), AppError> {
+#[server(GetHorcruxClaimQueue)]
+pub async fn get_horcrux_claim_queue() -> Result<Vec<HorcruxClaimLog>, AppError> {
     check_admin().await?;
     let pool: DbPool = expect_context();
+    let puzzle: Arc<Puzzle> = expect_context();
     tokio::task::spawn_blocking(move || {
         let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
-        reset_votes(&mut conn).map_err(|e| AppError::DbError(e.to_string()))
+        get_horcrux_claims(&mut conn, &puzzle).map_err(|e| AppError::DbError(e.to_string()))
     })
     .await
     .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
 }
 
-#[server(GetVotingStats)]
-pub async fn get_voting_stats_handler() -> Result<(i64, i64), AppError> {
+// Approves a horcrux claim, scoring the configured points for its house, or rejects it so that the
+// house may claim that horcrux again.
+#[server(ReviewHorcruxClaim)]
+pub async fn review_horcrux_claim_handler(claim_id: i32, approve: bool) -> Result<(), AppError> {
+    check_admin().await?;
+    let pool: DbPool = expect_context();
+    let config: AppConfig = expect_context();
+    let claim = tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        review_horcrux_claim(&mut conn, claim_id, approve, config.horcrux_points)
+            .map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))??;
+
+    publish(LiveEvent::HorcruxClaimsChanged {
+        house_id: claim.house_id,
+    });
+    if approve {
+        publish(LiveEvent::PointsAwarded {
+            house_id: claim.house_id,
+            amount: config.horcrux_points,
+        });
+    }
+    Ok(())
+}
+
+// The photo sent with a horcrux claim, as a data URL.
+#[server(GetHorcruxClaimPhoto)]
+pub async fn get_horcrux_claim_photo_handler(claim_id: i32) -> Result<String, AppError> {
     check_admin().await?;
     let pool: DbPool = expect_context();
+    let config: AppConfig = expect_context();
     tokio::task::spawn_blocking(move || {
         let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
-        get_voting_stats(&mut conn).map_err(|e| AppError::DbError(e.to_string()))
+        let file_name = get_horcrux_claim_photo(&mut conn, claim_id)
+            .map_err(|e| AppError::DbError(e.to_string()))?
+            .ok_or(AppError::DbError("The claim has no photo".to_string()))?;
+        load_horcrux_photo(&config.horcrux_photo_dir, &file_name).map_err(AppError::DbError)
     })
     .await
     .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
 }
 
-#[server(GetGamesEnabled)]
-pub async fn get_games_enabled_handler() -> Result<bool, AppError> {
+// The day a Wordle game belongs to, in the server's time zone.
+#[cfg(feature = "ssr")]
+fn wordle_day() -> chrono::NaiveDate {
+    chrono::Local::now().date_naive()
+}
+
+// The guest's Wordle game of the day; the first call of the day picks their word.
+#[server(GetWordleGame)]
+pub async fn get_wordle_game() -> Result<WordleGame, AppError> {
+    check_game(|games| games.wordle)?;
     let pool: DbPool = expect_context();
+    let words: Arc<WordList> = expect_context();
+    let maybe_current_user = get_current_user().await?;
+    let guest = maybe_current_user.ok_or(AppError::AuthError("Must be logged in".to_string()))?;
     tokio::task::spawn_blocking(move || {
         let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
-        init_voting_status(&mut conn).map_err(|e| AppError::DbError(e.to_string()))?;
-        get_games_enabled(&mut conn).map_err(|e| AppError::DbError(e.to_string()))
+        get_or_start_wordle_game(&mut conn, guest.id, wordle_day(), &words)
+            .map_err(|e| AppError::DbError(e.to_string()))
     })
     .await
     .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
 }
 
-#[server(ToggleGamesEnabled)]
-pub async fn toggle_games_enabled_handler() -> Result<bool, AppError> {
+#[server(SubmitWordleGuess)]
+pub async fn submit_wordle_guess_handler(guess: String) -> Result<WordleGame, AppError> {
+    check_game(|games| games.wordle)?;
+    let pool: DbPool = expect_context();
+    let config: AppConfig = expect_context();
+    let words: Arc<WordList> = expect_context();
+    let maybe_current_user = get_current_user().await?;
+    let guest = maybe_current_user.ok_or(AppError::AuthError("Must be logged in".to_string()))?;
+    let house_id = guest.house_id.ok_or(AppError::AuthError(
+        "Must be sorted into a house".to_string(),
+    ))?;
+    let game = tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        submit_wordle_guess(
+            &mut conn,
+            guest.id,
+            wordle_day(),
+            &guess,
+            &words,
+            &config.wordle_points,
+            config.wordle_hard_mode,
+        )
+        .map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))??;
+
+    if let Some(amount) = game.points {
+        publish(LiveEvent::PointsAwarded { house_id, amount });
+    }
+    Ok(game)
+}
+
+// The Wordle race that is on, or else the last one, as the race view shows it.
+#[server(GetWordleRace)]
+pub async fn get_wordle_race_handler() -> Result<Option<WordleRace>, AppError> {
     let pool: DbPool = expect_context();
     tokio::task::spawn_blocking(move || {
         let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
-        init_voting_status(&mut conn).map_err(|e| AppError::DbError(e.to_string()))?;
-        toggle_games_enabled(&mut conn).map_err(|e| AppError::DbError(e.to_string()))
+        get_wordle_race(&mut conn).map_err(|e| AppError::DbError(e.to_string()))
     })
     .await
     .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
 }
 
-const WORDS: &[&str] = &[
-    "apple", "bread", "break", "broad", "tread", "bleed", "dreab",
-];
+// The guest's game in the latest Wordle race, joining it if it is on.
+#[server(GetWordleRaceGame)]
+pub async fn get_wordle_race_game() -> Result<WordleGame, AppError> {
+    check_game(|games| games.wordle)?;
+    let pool: DbPool = expect_context();
+    let maybe_current_user = get_current_user().await?;
+    let guest = maybe_current_user.ok_or(AppError::AuthError("Must be logged in".to_string()))?;
+    tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        get_or_join_wordle_race(&mut conn, guest.id).map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
+}
 
-pub fn shell(options: LeptosOptions) -> impl IntoView {
-    view! {
-        <!DOCTYPE html>
-        <html lang="en">
-            <head>
-                <meta charset="utf-8" />
-                <meta name="viewport" content="width=device-width, initial-scale=1" />
-                <AutoReload options=options.clone() />
-                <HydrationScripts options />
-                <MetaTags />
-            </head>
-            <body>
-                <App />
-            </body>
-        </html>
+#[server(SubmitWordleRaceGuess)]
+pub async fn submit_wordle_race_guess_handler(guess: String) -> Result<WordleGame, AppError> {
+    check_game(|games| games.wordle)?;
+    let pool: DbPool = expect_context();
+    let config: AppConfig = expect_context();
+    let words: Arc<WordList> = expect_context();
+    let maybe_current_user = get_current_user().await?;
+    let guest = maybe_current_user.ok_or(AppError::AuthError("Must be logged in".to_string()))?;
+    if guest.house_id.is_none() {
+        return Err(AppError::AuthError(
+            "Must be sorted into a house".to_string(),
+        ));
+    }
+    let (game, award) = tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        submit_wordle_race_guess(&mut conn, guest.id, &guess, &words, config.wordle_hard_mode)
+            .map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))??;
+
+    publish(LiveEvent::WordleRaceChanged);
+    if let Some(award) = award {
+        if let Some(house_id) = award.house_id {
+            publish(LiveEvent::PointsAwarded {
+                house_id,
+                amount: award.amount,
+            });
+        }
     }
+    Ok(game)
 }
 
-#[component]
-pub fn App() -> impl IntoView {
-    // Provides context that manages stylesheets, titles, meta tags, etc.
-    provide_meta_context();
+#[server(StartWordleRace)]
+pub async fn start_wordle_race_handler() -> Result<WordleRace, AppError> {
+    check_admin().await?;
+    check_game(|games| games.wordle)?;
+    let pool: DbPool = expect_context();
+    let config: AppConfig = expect_context();
+    let words: Arc<WordList> = expect_context();
+    tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        start_wordle_race(
+            &mut conn,
+            &words,
+            config.wordle_race_solvers,
+            config.wordle_race_points,
+        )
+        .map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
+    .inspect(|_| publish(LiveEvent::WordleRaceChanged))
+}
 
-    view! {
-        // injects a stylesheet into the document <head>
-        // id=leptos means cargo-leptos will hot-reload this stylesheet
-        <Stylesheet id="leptos" href="/pkg/hp-halloween-25.css" />
+#[server(StopWordleRace)]
+pub async fn stop_wordle_race_handler() -> Result<WordleRace, AppError> {
+    check_admin().await?;
+    let pool: DbPool = expect_context();
+    tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        stop_wordle_race(&mut conn).map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
+    .inspect(|_| publish(LiveEvent::WordleRaceChanged))
+}
 
-        // sets the document title
-        <Title text="Hogwarts Halloween Party" />
+// Every house's Catch the Keys progress.
+#[server(GetHouseKeys)]
+pub async fn get_house_keys_handler() -> Result<Vec<HouseKeys>, AppError> {
+    let pool: DbPool = expect_context();
+    tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        get_house_keys(&mut conn).map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
+}
 
-        // content for this welcome page
-        <Router>
-            <main>
-                <Routes fallback=|| "Page not found.".into_view()>
-                    <Route path=path!("/") view=Home />
-                    <Route path=path!("/login") view=Login />
-                    <Route path=path!("/admin/login") view=AdminLogin />
-                    <Route path=path!("/admin") view=AdminDashboard />
-                    <Route path=path!("/games/wordle") view=Wordle />
-                    <Route path=path!("/games/crossword") view=Crossword />
-                    <Route path=path!("/games/catch_the_keys") view=CatchTheKeys />
-                    <Route path=path!("/games/best_dressed") view=BestDressed />
-                </Routes>
-            </main>
-        </Router>
+// Records a key a house turned in to Hagrid or Harry; `key` is its letter or the wildcard mark.
+#[server(TurnInKey)]
+pub async fn turn_in_key_handler(house_id: i32, key: String) -> Result<HouseKeys, AppError> {
+    check_admin().await?;
+    check_game(|games| games.catch_the_keys)?;
+    let pool: DbPool = expect_context();
+    let config: AppConfig = expect_context();
+    let key = key
+        .chars()
+        .next()
+        .ok_or(AppError::DbError("Which key?".to_string()))?;
+    let keys = tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        turn_in_key(&mut conn, house_id, key, &config.catch_the_keys_points)
+            .map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))??;
+
+    publish(LiveEvent::KeysTurnedIn { house_id });
+    // Only the key that completes a house scores.
+    if let (true, Some(amount)) = (keys.missing.is_empty(), keys.points) {
+        publish(LiveEvent::PointsAwarded { house_id, amount });
     }
+    Ok(keys)
 }
 
-#[component]
-fn Home() -> impl IntoView {
-    let houses_fetcher = Resource::new(|| (), |_| get_houses());
-    let current_user_fetcher = Resource::new(|| (), |_| get_current_user());
-    let is_admin_fetcher = Resource::new(|| (), |_| is_admin());
+#[server(TakeBackKey)]
+pub async fn take_back_key_handler(house_id: i32, key: String) -> Result<HouseKeys, AppError> {
+    check_admin().await?;
+    let pool: DbPool = expect_context();
+    let key = key
+        .chars()
+        .next()
+        .ok_or(AppError::DbError("Which key?".to_string()))?;
+    tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        take_back_key(&mut conn, house_id, key).map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
+    .inspect(|_| publish(LiveEvent::KeysTurnedIn { house_id }))
+}
 
-    let house_class = RwSignal::new(String::new());
+// Every physical Catch the Keys key with the QR code a guest scans to claim it, for printing.
+#[server(GetKeySheet)]
+pub async fn get_key_sheet() -> Result<Vec<KeyQr>, AppError> {
+    check_admin().await?;
+    let pool: DbPool = expect_context();
+    let config: AppConfig = expect_context();
+    let wildcards = config.catch_the_keys_wildcards;
+    let keys = tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        get_physical_keys(&mut conn, wildcards).map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))??;
 
-    // Signal for house color class when logged in.
-    Effect::new(move |_| {
-        if let Some(Ok(Some(guest))) = current_user_fetcher.get() {
-            if let Some(Ok(houses)) = houses_fetcher.get() {
-                if let Some(h) = houses.iter().find(|h| Some(h.id) == guest.house_id) {
-                    house_class.set(match h.id {
-                        1 => "house-gryffindor".to_string(),
-                        2 => "house-hufflepuff".to_string(),
-                        3 => "house-ravenclaw".to_string(),
-                        4 => "house-slytherin".to_string(),
-                        _ => String::new(),
-                    });
-                } else {
-                    house_class.set(String::new());
-                }
-            } else {
-                house_class.set(String::new());
+    keys.into_iter()
+        .map(|(key, claimed_by)| {
+            Ok(KeyQr {
+                id: key.id,
+                key: key.key.chars().next().unwrap_or(WILDCARD),
+                qr_svg: qr_svg(&config.key_claim_url(key.id, &key.token))?,
+                claimed_by,
+            })
+        })
+        .collect()
+}
+
+// Turns in the physical key a guest scanned for their house.
+#[server(ClaimKey)]
+pub async fn claim_key_handler(key_id: i32, token: String) -> Result<KeyClaim, AppError> {
+    check_game(|games| games.catch_the_keys)?;
+    let guest = get_current_user()
+        .await?
+        .ok_or(AppError::AuthError("Must be logged in".to_string()))?;
+    let pool: DbPool = expect_context();
+    let config: AppConfig = expect_context();
+    let claim = tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        claim_physical_key(
+            &mut conn,
+            guest.id,
+            key_id,
+            &token,
+            &config.catch_the_keys_points,
+            config.catch_the_keys_wrong_house_penalty,
+        )
+        .map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))??;
+
+    let house_id = claim.house.house_id;
+    match claim.penalty {
+        Some(_) if claim.repeat => {}
+        Some(penalty) => {
+            if penalty != 0 {
+                publish(LiveEvent::PointsAwarded {
+                    house_id,
+                    amount: -penalty,
+                });
             }
-        } else {
-            house_class.set(String::new());
         }
-    });
+        None => {
+            publish(LiveEvent::KeysTurnedIn { house_id });
+            if let (true, Some(amount)) = (claim.house.missing.is_empty(), claim.house.points) {
+                publish(LiveEvent::PointsAwarded { house_id, amount });
+            }
+        }
+    }
+    Ok(claim)
+}
 
-    let voting_open_fetcher = Resource::new(|| (), |_| voting_is_open_handler());
-    let games_enabled_fetcher = Resource::new(|| (), |_| get_games_enabled_handler());
+#[server(VotingIsOpen)]
+pub async fn voting_is_open_handler() -> Result<bool, AppError> {
+    let pool: DbPool = expect_context();
+    tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        init_voting_status(&mut conn).map_err(|e| AppError::DbError(e.to_string()))?;
+        voting_is_open(&mut conn).map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
+}
 
-    let games_section = move || {
-        current_user_fetcher
-            .get()
-            .and_then(|res| res.ok())
-            .flatten()
-            .and_then(|_| {
-                games_enabled_fetcher
-                    .get()
-                    .and_then(|res| res.ok())
-                    .and_then(|enabled| if enabled { Some(()) } else { None })
-            })
-            .map(|_| {
-                view! {
-                    <section class="home-section centered">
-                        <h3>"Games and Activities"</h3>
-                        <div class="games-buttons">
-                            <a class="btn-game" href="/games/crossword">
-                                "Horcrux Hunt"
-                            </a>
-                            <a class="btn-game" href="/games/catch_the_keys">
-                                "Catch the Keys"
-                            </a>
-                            <Suspense fallback=|| {
-                                view! { <></> }
-                            }>
-                                {move || {
-                                    voting_open_fetcher
-                                        .get()
-                                        .and_then(|open_res| open_res.ok())
-                                        .map(|is_open| {
-                                            if is_open {
-                                                view! {
-                                                    <a class="btn-game" href="/games/best_dressed">
-                                                        "Best Dressed"
-                                                    </a>
-                                                }
-                                                    .into_any()
-                                            } else {
-                                                view! { <></> }.into_any()
-                                            }
-                                        })
-                                        .unwrap_or_else(|| view! { <></> }.into_any())
-                                }}
-                            </Suspense>
-                        </div>
-                    </section>
-                }
-                .into_any()
-            })
-            .unwrap_or_else(|| view! { <></> }.into_any())
-    };
+#[server(OpenVoting)]
+pub async fn open_voting_handler() -> Result<(), AppError> {
+    check_admin().await?;
+    check_game(|games| games.best_dressed)?;
+    let pool: DbPool = expect_context();
+    tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        init_voting_status(&mut conn).map_err(|e| AppError::DbError(e.to_string()))?;
+        open_voting(&mut conn).map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
+    .inspect(|_| publish(LiveEvent::VotingChanged))
+}
 
-    view! {
-        <div class=move || format!("home-container {}", house_class.get())>
-            // Main header.
-            <h1 class="main-header">"Hogwarts Halloween"</h1>
+#[server(CloseVoting)]
+pub async fn close_voting_handler() -> Result<RcvResult, AppError> {
+    check_admin().await?;
+    let pool: DbPool = expect_context();
+    tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        init_voting_status(&mut conn).map_err(|e| AppError::DbError(e.to_string()))?;
+        close_voting(&mut conn).map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
+    .inspect(|_| publish(LiveEvent::VotingChanged))
+}
 
-            // Welcome/login at the top.
-            <Suspense fallback=|| {
-                view! { <p class="centered">"Checking login..."</p> }
-            }>
-                {move || {
-                    current_user_fetcher
-                        .with(|u_res| match u_res {
-                            Some(Ok(Some(guest))) => {
-                                houses_fetcher
-                                    .with(|h_res| match h_res {
-                                        Some(Ok(houses)) => {
-                                            let house_opt = houses
-                                                .iter()
-                                                .find(|h| Some(h.id) == guest.house_id);
-                                            let house_name = house_opt
-                                                .map(|h| h.name.clone())
-                                                .unwrap_or("Unknown".to_string());
-                                            // Welcome section: split into two centered lines.
-                                            view! {
-                                                <section class="welcome-section centered">
-                                                    <h2 class="welcome-text">
-                                                        "Welcome, " {guest.name.clone()}
-                                                    </h2>
-                                                    <h2 class="to-house-text">"to " {house_name}</h2>
-                                                </section>
-                                            }
-                                                .into_any()
-                                        }
-                                        _ => {
-                                            view! {
-                                                <section class="welcome-section centered">
-                                                    <p>"Error loading houses"</p>
-                                                </section>
-                                            }
-                                                .into_any()
-                                        }
-                                    })
-                            }
-                            _ => {
-                                view! {
-                                    <section class="login-section centered">
-                                        <p>
-                                            <a class="btn-primary" href="/login">
-                                                "Login"
-                                            </a>
-                                        </p>
-                                    </section>
-                                }
-                                    .into_any()
-                            }
-                        })
-                }}
-            </Suspense>
+#[server(HasVoted)]
+pub async fn has_voted_handler() -> Result<bool, AppError> {
+    let maybe_user = get_current_user().await?;
+    let guest = maybe_user.ok_or(AppError::AuthError("Must be logged in".to_string()))?;
+    let pool: DbPool = expect_context();
+    tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        has_voted(&mut conn, guest.id).map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
+}
 
-            // House scores section, rendered unconditionally.
-            <Suspense fallback=|| {
-                view! {
-                    <section class="house-scores centered">
-                        <p>"Loading house scores"</p>
-                    </section>
-                }
-            }>
-                {move || {
-                    houses_fetcher
-                        .with(|h_res| match h_res {
-                            Some(Ok(houses)) => {
-                                view! { <HouseScores houses=houses.clone() /> }.into_any()
-                            }
-                            _ => {
-                                view! {
-                                    <section class="house-scores centered">
-                                        <p>"Error loading houses"</p>
-                                    </section>
-                                }
-                                    .into_any()
-                            }
-                        })
-                }}
-            </Suspense>
+#[server(SubmitVote)]
+pub async fn submit_vote_handler(first: i32, second: i32, third: i32) -> Result<(), AppError> {
+    check_game(|games| games.best_dressed)?;
+    let maybe_user = get_current_user().await?;
+    let guest = maybe_user.ok_or(AppError::AuthError("Must be logged in".to_string()))?;
+    let pool: DbPool = expect_context();
+    tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        init_voting_status(&mut conn).map_err(|e| AppError::DbError(e.to_string()))?;
+        submit_vote(&mut conn, guest.id, first, second, third)
+            .map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
+    .inspect(|_| publish(LiveEvent::VoteSubmitted))
+}
 
-            // Games section, rendered conditionally if user is logged in.
-            <Suspense fallback=|| view! { <></> }>{games_section}</Suspense>
+#[server(GetRcvResult)]
+pub async fn get_rcv_result_handler() -> Result<RcvResult, AppError> {
+    let pool: DbPool = expect_context();
+    tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        init_voting_status(&mut conn).map_err(|e| AppError::DbError(e.to_string()))?;
+        get_rcv_result(&mut conn).map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
+}
 
-            // Admin link.
-            <Suspense>
-                {move || {
-                    is_admin_fetcher
-                        .with(|admin| match admin {
-                            Some(Ok(true)) => {
-                                view! {
-                                    <section class="home-section centered">
-                                        <p>
-                                            <a class="btn-primary" href="/admin">
-                                                "Admin Dashboard"
-                                            </a>
-                                        </p>
-                                    </section>
-                                }
-                                    .into_any()
-                            }
-                            _ => view! {}.into_any(),
-                        })
-                }}
-            </Suspense>
-        </div>
-    }
+#[server(GetActiveGuestsForVoting)]
+pub async fn get_active_guests_for_voting() -> Result<Vec<Guest>, AppError> {
+    let pool: DbPool = expect_context();
+    tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        get_all_active_guests(&mut conn).map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
 }
 
-#[component]
-fn Login() -> impl IntoView {
-    let selected_guest = RwSignal::new(0i32);
-    let token = RwSignal::new(String::new());
-    let error = RwSignal::new(String::new());
+#[server(GetUserVote)]
+pub async fn get_user_vote_handler() -> Result<Option<(Guest, Guest, Guest)>, AppError> {
+    let maybe_user = get_current_user().await?;
+    let guest = maybe_user.ok_or(AppError::AuthError("Must be logged in".to_string()))?;
+    let pool: DbPool = expect_context();
+    tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        get_user_vote(&mut conn, guest.id).map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
+}
 
-    let guests_fetcher = Resource::new(|| (), |_| get_active_guests());
-    let current_user_fetcher = Resource::new(|| (), |_| get_current_user());
-    let is_admin_fetcher = Resource::new(|| (), |_| is_admin());
+#[server(ResetVotes)]
+pub async fn reset_votes_handler() -> Result<(), AppError> {
+    check_admin().await?;
+    let pool: DbPool = expect_context();
+    tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        reset_votes(&mut conn).map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
+    .inspect(|_| publish(LiveEvent::VotingChanged))
+}
 
-    let is_auto_login = RwSignal::new(false);
-    let query_map = use_query_map();
-    let navigate = use_navigate();
+#[server(GetVotingStats)]
+pub async fn get_voting_stats_handler() -> Result<(i64, i64), AppError> {
+    check_admin().await?;
+    let pool: DbPool = expect_context();
+    tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        get_voting_stats(&mut conn).map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
+}
 
-    Effect::new(move |_| {
-        let current_params = query_map.read();
-        if let (Some(guest_id_str), Some(token_str)) =
-            (current_params.get("guest_id"), current_params.get("token"))
-        {
-            if let Ok(guest_id) = guest_id_str.as_str().parse::<i32>() {
-                let token = token_str.clone();
-                is_auto_login.set(true);
-                let nav = navigate.clone();
-                spawn_local(async move {
-                    is_auto_login.set(false);
-                    match login_handler(guest_id, token).await {
-                        Ok(_) => {
-                            nav("/", NavigateOptions::default());
-                        }
-                        Err(e) => {
-                            error.set(format!("Auto-login failed: {}", e));
-                        }
-                    }
-                });
-            }
-        }
-    });
+#[server(GetGamesEnabled)]
+pub async fn get_games_enabled_handler() -> Result<bool, AppError> {
+    let pool: DbPool = expect_context();
+    tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        init_voting_status(&mut conn).map_err(|e| AppError::DbError(e.to_string()))?;
+        get_games_enabled(&mut conn).map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
+}
 
-    let submit = move |ev: SubmitEvent| {
-        ev.prevent_default();
-        let g = selected_guest.get();
-        let t = token.get();
-        if g == 0 || t.is_empty() {
-            error.set("Please select a guest and enter a token.".to_string());
-            return;
-        }
-        spawn_local(async move {
-            match login_handler(g, t).await {
-                Ok(_) => {
-                    error.set(String::new());
-                    let navigate = use_navigate();
-                    navigate("/", NavigateOptions::default());
-                }
-                Err(e) => error.set(e.to_string()),
-            }
-        });
-    };
-
-    // Redirect if already logged in as a guest or admin.
-    let navigate = use_navigate();
-    Effect::new(move || {
-        // First check guest, then admin. We don't want to redirect a guest the admin dashboard.
-        current_user_fetcher.with(|maybe_result| {
-            if let Some(Ok(Some(_))) = maybe_result {
-                navigate("/", NavigateOptions::default());
-            }
-        });
-        is_admin_fetcher.with(|maybe_result| {
-            if let Some(Ok(true)) = maybe_result {
-                navigate("/admin", NavigateOptions::default());
-            }
-        });
-    });
+#[server(ToggleGamesEnabled)]
+pub async fn toggle_games_enabled_handler() -> Result<bool, AppError> {
+    let pool: DbPool = expect_context();
+    tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get().map_err(|e| AppError::DbError(e.to_string()))?;
+        init_voting_status(&mut conn).map_err(|e| AppError::DbError(e.to_string()))?;
+        toggle_games_enabled(&mut conn).map_err(|e| AppError::DbError(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::DbError(format!("Task joining error: {}", e)))?
+    .inspect(|enabled| publish(LiveEvent::GamesToggled { enabled: *enabled }))
+}
 
+pub fn shell(options: LeptosOptions) -> impl IntoView {
     view! {
-        <div class="login-container">
-            <a class="back-link" href="/">
-                "← Home"
-            </a>
-            <h1>"Login"</h1>
-            {move || {
-                if is_auto_login.get() {
-                    view! { <p class="centered">"Auto-logging in..."</p> }.into_any()
-                } else {
-                    view! {
-                        <form class="admin-form" on:submit=submit>
-                            <div class="form-group">
-                                <label>
-                                    "Guest: "
-                                    <select
-                                        class="form-select"
-                                        on:change=move |ev| {
-                                            let val = event_target_value(&ev)
-                                                .parse::<i32>()
-                                                .unwrap_or(0);
-                                            selected_guest.set(val);
-                                        }
-                                    >
-                                        <option value="0">"Select your name"</option>
-                                        <Suspense fallback=|| {
-                                            view! { "Loading..." }
-                                        }>
-                                            {move || {
-                                                guests_fetcher
-                                                    .with(move |opt_res| {
-                                                        match opt_res {
-                                                            None => view! { "Loading..." }.into_any(),
-                                                            Some(res) => {
-                                                                match res {
-                                                                    Ok(guests) => {
-                                                                        guests
-                                                                            .iter()
-                                                                            .map(|guest| {
-                                                                                view! {
-                                                                                    <option value=guest
-                                                                                        .id
-                                                                                        .to_string()>{guest.name.clone()}</option>
-                                                                                }
-                                                                            })
-                                                                            .collect_view()
-                                                                            .into_any()
-                                                                    }
-                                                                    Err(e) => {
-                                                                        view! {
-                                                                            "Error loading guests: "
-                                                                            {e.to_string()}
-                                                                        }
-                                                                            .into_any()
-                                                                    }
-                                                                }
-                                                            }
-                                                        }
-                                                    })
-                                            }}
-                                        </Suspense>
-                                    </select>
-                                </label>
-                            </div>
-                            <div class="form-group">
-                                <label>
-                                    "Token: "
-                                    <input
-                                        class="form-input"
-                                        type="text"
-                                        on:input=move |ev| token.set(event_target_value(&ev))
-                                    />
-                                </label>
-                            </div>
-                            <button class="btn-primary" type="submit">
-                                "Login"
-                            </button>
-                        </form>
-                    }
-                        .into_any()
-                }
-            }}
-            {move || (!error.get().is_empty()).then(|| view! { <p>{error.get()}</p> })}
-        </div>
+        <!DOCTYPE html>
+        <html lang="en">
+            <head>
+                <meta charset="utf-8" />
+                <meta name="viewport" content="width=device-width, initial-scale=1" />
+                <AutoReload options=options.clone() />
+                <HydrationScripts options />
+                <MetaTags />
+            </head>
+            <body>
+                <App />
+            </body>
+        </html>
     }
 }
 
 #[component]
-fn AdminLogin() -> impl IntoView {
-    let password = RwSignal::new(String::new());
-    let error = RwSignal::new(String::new());
+pub fn App() -> impl IntoView {
+    // Provides context that manages stylesheets, titles, meta tags, etc.
+    provide_meta_context();
+
+    view! {
+        // injects a stylesheet into the document <head>
+        // id=leptos means cargo-leptos will hot-reload this stylesheet
+        <Stylesheet id="leptos" href="/pkg/hp-halloween-25.css" />
+
+        // sets the document title
+        <Title text="Hogwarts Halloween Party" />
+
+        // content for this welcome page
+        <Router>
+            <main>
+                <Routes fallback=|| "Page not found.".into_view()>
+                    <Route path=path!("/") view=Home />
+                    <Route path=path!("/login") view=Login />
+                    <Route path=path!("/admin/login") view=AdminLogin />
+                    <Route path=path!("/admin") view=AdminDashboard />
+                    <Route path=path!("/scoreboard") view=ScoreboardPage />
+                    <Route path=path!("/leaderboard") view=LeaderboardPage />
+                    <Route path=path!("/games/wordle") view=|| view! { <Wordle /> } />
+                    <Route
+                        path=path!("/games/wordle/race")
+                        view=|| view! { <Wordle race=true /> }
+                    />
+                    <Route path=path!("/games/crossword") view=Crossword />
+                    <Route path=path!("/games/catch_the_keys") view=CatchTheKeys />
+                    <Route path=path!("/games/catch_the_keys/claim") view=ClaimKey />
+                    <Route path=path!("/admin/keys") view=KeySheet />
+                    <Route path=path!("/games/best_dressed") view=BestDressed />
+                </Routes>
+            </main>
+        </Router>
+    }
+}
 
+#[component]
+fn Home() -> impl IntoView {
+    let houses_fetcher = Resource::new(|| (), |_| get_houses());
     let current_user_fetcher = Resource::new(|| (), |_| get_current_user());
     let is_admin_fetcher = Resource::new(|| (), |_| is_admin());
 
-    let submit = move |ev: SubmitEvent| {
-        ev.prevent_default();
-        let p = password.get();
-        if p.is_empty() {
-            error.set("Please enter password.".to_string());
-            return;
-        }
-        spawn_local(async move {
-            match admin_login(p).await {
-                Ok(_) => {
-                    error.set(String::new());
-                    let navigate = use_navigate();
-                    navigate("/admin", NavigateOptions::default());
-                }
-                Err(e) => error.set(e.to_string()),
-            }
-        });
-    };
+    let house_class = RwSignal::new(String::new());
+    let house_style = RwSignal::new(String::new());
 
-    let navigate = use_navigate();
-    Effect::new(move || {
-        current_user_fetcher.with(|maybe_result| {
-            if let Some(Ok(Some(_))) = maybe_result {
-                navigate("/", NavigateOptions::default());
+    // Signals for house color class and colors when logged in.
+    Effect::new(move |_| {
+        let house = current_user_fetcher
+            .get()
+            .and_then(|res| res.ok())
+            .flatten()
+            .and_then(|guest| {
+                houses_fetcher
+                    .get()
+                    .and_then(|res| res.ok())
+                    .and_then(|houses| houses.into_iter().find(|h| Some(h.id) == guest.house_id))
+            });
+        match house {
+            Some(h) => {
+                house_class.set(format!("house-themed house-{}", h.slug));
+                house_style.set(h.color_style());
             }
-        });
-        is_admin_fetcher.with(|maybe_result| {
-            if let Some(Ok(true)) = maybe_result {
-                navigate("/admin", NavigateOptions::default());
+            None => {
+                house_class.set(String::new());
+                house_style.set(String::new());
             }
-        });
+        }
     });
 
-    view! {
-        <div class="login-container">
-            <a class="back-link" href="/">
-                "← Home"
-            </a>
-            <h1>"Admin Login"</h1>
-            <form class="admin-form" on:submit=submit>
-                <div class="form-group">
-                    <label>
-                        "Password: "
-                        <input
-                            class="form-input"
-                            type="password"
-                            on:input=move |ev| password.set(event_target_value(&ev))
-                        />
-                    </label>
-                </div>
-                <button class="btn-primary" type="submit">
-                    "Login"
-                </button>
-            </form>
-            {move || {
-                if !error.get().is_empty() {
-                    view! { <p>{error.get()}</p> }.into_any()
-                } else {
-                    view! {}.into_any()
-                }
-            }}
-        </div>
-    }
-}
+    let voting_open_fetcher = Resource::new(|| (), |_| voting_is_open_handler());
+    let games_enabled_fetcher = Resource::new(|| (), |_| get_games_enabled_handler());
+    let client_config_fetcher = Resource::new(|| (), |_| get_client_config());
+    let my_awards_fetcher = Resource::new(|| (), |_| get_my_point_awards());
+
+    // Keeps scores and game links current without reloading the page.
+    use_live_events(move |event| {
+        if event.changes_scores() {
+            houses_fetcher.refetch();
+            current_user_fetcher.refetch();
+            my_awards_fetcher.refetch();
+        }
+        if matches!(event, LiveEvent::Resync | LiveEvent::VotingChanged) {
+            voting_open_fetcher.refetch();
+        }
+        if event.changes_games() {
+            games_enabled_fetcher.refetch();
+        }
+    });
 
-#[component]
-fn AdminDashboard() -> impl IntoView {
-    // Fetchers for various resources (state).
+    let games_section = move || {
+        current_user_fetcher
+            .get()
+            .and_then(|res| res.ok())
+            .flatten()
+            .and_then(|_| {
+                games_enabled_fetcher
+                    .get()
+                    .and_then(|res| res.ok())
+                    .and_then(|enabled| if enabled { Some(()) } else { None })
+            })
+            .map(|_| {
+                // Only offer the games configured for this party.
+                let games = client_config_fetcher
+                    .get()
+                    .and_then(|res| res.ok())
+                    .map(|config| config.games)
+                    .unwrap_or_default();
+                view! {
+                    <section class="home-section centered">
+                        <h3>"Games and Activities"</h3>
+                        <div class="games-buttons">
+                            {games
+                                .crossword
+                                .then(|| {
+                                    view! {
+                                        <a class="btn-game" href="/games/crossword">
+                                            "Horcrux Hunt"
+                                        </a>
+                                    }
+                                })}
+                            {games
+                                .catch_the_keys
+                                .then(|| {
+                                    view! {
+                                        <a class="btn-game" href="/games/catch_the_keys">
+                                            "Catch the Keys"
+                                        </a>
+                                    }
+                                })}
+                            {games
+                                .wordle
+                                .then(|| {
+                                    view! {
+                                        <a class="btn-game" href="/games/wordle">
+                                            "Wordle"
+                                        </a>
+                                    }
+                                })}
+                            <Suspense fallback=|| {
+                                view! { <></> }
+                            }>
+                                {move || {
+                                    voting_open_fetcher
+                                        .get()
+                                        .and_then(|open_res| open_res.ok())
+                                        .map(|is_open| {
+                                            if is_open && games.best_dressed {
+                                                view! {
+                                                    <a class="btn-game" href="/games/best_dressed">
+                                                        "Best Dressed"
+                                                    </a>
+                                                }
+                                                    .into_any()
+                                            } else {
+                                                view! { <></> }.into_any()
+                                            }
+                                        })
+                                        .unwrap_or_else(|| view! { <></> }.into_any())
+                                }}
+                            </Suspense>
+                        </div>
+                    </section>
+                }
+                .into_any()
+            })
+            .unwrap_or_else(|| view! { <></> }.into_any())
+    };
+
+    view! {
+        <div
+            class=move || format!("home-container {}", house_class.get())
+            style=move || house_style.get()
+        >
+            // Main header.
+            <h1 class="main-header">"Hogwarts Halloween"</h1>
+
+            // Welcome/login at the top.
+            <Suspense fallback=|| {
+                view! { <p class="centered">"Checking login..."</p> }
+            }>
+                {move || {
+                    current_user_fetcher
+                        .with(|u_res| match u_res {
+                            Some(Ok(Some(guest))) => {
+                                houses_fetcher
+                                    .with(|h_res| match h_res {
+                                        Some(Ok(houses)) => {
+                                            let house_opt = houses
+                                                .iter()
+                                                .find(|h| Some(h.id) == guest.house_id);
+                                            let house_name = house_opt
+                                                .map(|h| h.name.clone())
+                                                .unwrap_or("Unknown".to_string());
+                                            // Welcome section: split into two centered lines.
+                                            view! {
+                                                <section class="welcome-section centered">
+                                                    <h2 class="welcome-text">
+                                                        "Welcome, " {guest.name.clone()}
+                                                    </h2>
+                                                    <h2 class="to-house-text">"to " {house_name}</h2>
+                                                </section>
+                                            }
+                                                .into_any()
+                                        }
+                                        _ => {
+                                            view! {
+                                                <section class="welcome-section centered">
+                                                    <p>"Error loading houses"</p>
+                                                </section>
+                                            }
+                                                .into_any()
+                                        }
+                                    })
+                            }
+                            _ => {
+                                view! {
+                                    <section class="login-section centered">
+                                        <p>
+                                            <a class="btn-primary" href="/login">
+                                                "Login"
+                                            </a>
+                                        </p>
+                                    </section>
+                                }
+                                    .into_any()
+                            }
+                        })
+                }}
+            </Suspense>
+
+            // The logged-in guest's own points.
+            <GuestPoints
+                current_user_fetcher=current_user_fetcher
+                my_awards_fetcher=my_awards_fetcher
+            />
+
+            // House scores section, rendered unconditionally.
+            <Suspense fallback=|| {
+                view! {
+                    <section class="house-scores centered">
+                        <p>"Loading house scores"</p>
+                    </section>
+                }
+            }>
+                {move || {
+                    houses_fetcher
+                        .with(|h_res| match h_res {
+                            Some(Ok(houses)) => {
+                                view! { <HouseScores houses=houses.clone() /> }.into_any()
+                            }
+                            _ => {
+                                view! {
+                                    <section class="house-scores centered">
+                                        <p>"Error loading houses"</p>
+                                    </section>
+                                }
+                                    .into_any()
+                            }
+                        })
+                }}
+            </Suspense>
+
+            // Games section, rendered conditionally if user is logged in.
+            <Suspense fallback=|| view! { <></> }>{games_section}</Suspense>
+
+            // Admin link.
+            <Suspense>
+                {move || {
+                    is_admin_fetcher
+                        .with(|admin| match admin {
+                            Some(Ok(true)) => {
+                                view! {
+                                    <section class="home-section centered">
+                                        <p>
+                                            <a class="btn-primary" href="/admin">
+                                                "Admin Dashboard"
+                                            </a>
+                                        </p>
+                                    </section>
+                                }
+                                    .into_any()
+                            }
+                            _ => view! {}.into_any(),
+                        })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+fn Login() -> impl IntoView {
+    let selected_guest = RwSignal::new(0i32);
+    let token = RwSignal::new(String::new());
+    let error = RwSignal::new(String::new());
+
+    let guests_fetcher = Resource::new(|| (), |_| get_active_guests());
+    let current_user_fetcher = Resource::new(|| (), |_| get_current_user());
     let is_admin_fetcher = Resource::new(|| (), |_| is_admin());
-    let houses_fetcher = Resource::new(|| (), |_| get_houses());
-    let active_guests_fetcher = Resource::new(|| (), |_| get_active_guests());
-    let unregistered_guests_fetcher = Resource::new(|| (), |_| get_unregistered_guests());
-    let point_awards_fetcher = Resource::new(|| (), |_| get_point_awards());
-    let house_crossword_progress_fetcher =
-        Resource::new(|| (), |_| get_house_crossword_progress_handler());
-    let voting_status_fetcher = Resource::new(|| (), |_| voting_is_open_handler());
-    let rcv_result_fetcher = Resource::new(|| (), |_| get_rcv_result_handler());
-    let voting_stats_fetcher = Resource::new(|| (), |_| get_voting_stats_handler());
-    let games_enabled_fetcher = Resource::new(|| (), |_| get_games_enabled_handler());
 
-    // Redirects to the home page if a user who isn't logged in as an admin tries to visit the
-    // admin dashboard.
-    // NOTE: This effect does not capture any reactive values, so it won't run again.
+    let is_auto_login = RwSignal::new(false);
+    let query_map = use_query_map();
     let navigate = use_navigate();
-    Effect::new(move || {
-        is_admin_fetcher.with(|maybe_result| {
-            if let Some(Ok(false)) = maybe_result {
-                navigate("/", NavigateOptions::default());
-            }
-        });
-    });
-
-    // Signals related to registering a new guest.
-    let selected_guest_id = RwSignal::new(0i32);
-    let new_guest_character = RwSignal::new(String::new());
-    let new_guest_house = RwSignal::new(0i32);
-    let register_error = RwSignal::new(String::new());
-    let registered_token = RwSignal::new(String::new());
-    let qr_svg = RwSignal::new(String::new());
 
     Effect::new(move |_| {
-        if register_error.get_untracked().is_empty() && registered_token.get_untracked().is_empty()
+        let current_params = query_map.read();
+        if let (Some(guest_id_str), Some(token_str)) =
+            (current_params.get("guest_id"), current_params.get("token"))
         {
-            qr_svg.set(String::new()); // Clears QR when form is reset.
+            if let Ok(guest_id) = guest_id_str.as_str().parse::<i32>() {
+                let token = token_str.clone();
+                is_auto_login.set(true);
+                let nav = navigate.clone();
+                spawn_local(async move {
+                    is_auto_login.set(false);
+                    match login_handler(guest_id, token).await {
+                        Ok(_) => {
+                            nav("/", NavigateOptions::default());
+                        }
+                        Err(e) => {
+                            error.set(format!("Auto-login failed: {}", e));
+                        }
+                    }
+                });
+            }
         }
     });
 
-    // A handler for the register new guest submit button. Attempts to register a new guest with
-    // the provided details. On success, it clears any errors and sets the session token.
-    let register_submit = move |ev: SubmitEvent| {
+    let submit = move |ev: SubmitEvent| {
         ev.prevent_default();
-        let guest_id = selected_guest_id.get();
-        let character = new_guest_character.get();
-        let house_id = new_guest_house.get();
-        if guest_id == 0 || character.is_empty() {
-            register_error.set("Guest, character, and house are required.".to_string());
+        let g = selected_guest.get();
+        let t = token.get();
+        if g == 0 || t.is_empty() {
+            error.set("Please select a guest and enter a token.".to_string());
             return;
         }
         spawn_local(async move {
-            match register_guest_handler(guest_id, house_id, character).await {
-                Ok((token, assigned_house_id, qr_svg_str)) => {
-                    register_error.set(String::new());
-                    registered_token.set(token.clone());
-                    qr_svg.set(qr_svg_str);
-                    selected_guest_id.set(0i32);
-                    new_guest_character.set(String::new());
+            match login_handler(g, t).await {
+                Ok(_) => {
+                    error.set(String::new());
+                    let navigate = use_navigate();
+                    navigate("/", NavigateOptions::default());
+                }
+                Err(e) => error.set(e.to_string()),
+            }
+        });
+    };
 
-                    // Refetch the unregistered and active guests resources to update the dropdown
-                    // and active guests table without requiring a page refresh.
-                    unregistered_guests_fetcher.refetch();
-                    active_guests_fetcher.refetch();
+    // Redirect if already logged in as a guest or admin.
+    let navigate = use_navigate();
+    Effect::new(move || {
+        // First check guest, then admin. We don't want to redirect a guest the admin dashboard.
+        current_user_fetcher.with(|maybe_result| {
+            if let Some(Ok(Some(_))) = maybe_result {
+                navigate("/", NavigateOptions::default());
+            }
+        });
+        is_admin_fetcher.with(|maybe_result| {
+            if let Some(Ok(true)) = maybe_result {
+                navigate("/admin", NavigateOptions::default());
+            }
+        });
+    });
 
-                    #[cfg(feature = "hydrate")]
-                    {
-                        // Trigger the sort server.
-                        let sort_url =
-                            format!("http://192.168.1.176/flash?house={}", assigned_house_id);
-                        let window = web_sys::window().expect("window");
-
-                        let init = web_sys::RequestInit::new();
-                        init.set_method("GET");
-                        init.set_mode(web_sys::RequestMode::NoCors);
-
-                        let request =
-                            web_sys::Request::new_with_str_and_init(&sort_url, &init).unwrap();
-
-                        let resp_promise = window.fetch_with_request(&request);
-                        let future = wasm_bindgen_futures::JsFuture::from(resp_promise);
-                        log!(
-                            "Sending request to Sorting Hat for house {}",
-                            assigned_house_id
-                        );
-                        wasm_bindgen_futures::spawn_local(async move {
-                            match future.await {
-                                Ok(_) => log!(
-                                    "Sort request sent successfully for house {}",
-                                    assigned_house_id
-                                ),
-                                Err(e) => log!("Fetch error: {:?}", e),
-                            }
-                        });
+    view! {
+        <div class="login-container">
+            <a class="back-link" href="/">
+                "← Home"
+            </a>
+            <h1>"Login"</h1>
+            {move || {
+                if is_auto_login.get() {
+                    view! { <p class="centered">"Auto-logging in..."</p> }.into_any()
+                } else {
+                    view! {
+                        <form class="admin-form" on:submit=submit>
+                            <div class="form-group">
+                                <label>
+                                    "Guest: "
+                                    <select
+                                        class="form-select"
+                                        on:change=move |ev| {
+                                            let val = event_target_value(&ev)
+                                                .parse::<i32>()
+                                                .unwrap_or(0);
+                                            selected_guest.set(val);
+                                        }
+                                    >
+                                        <option value="0">"Select your name"</option>
+                                        <Suspense fallback=|| {
+                                            view! { "Loading..." }
+                                        }>
+                                            {move || {
+                                                guests_fetcher
+                                                    .with(move |opt_res| {
+                                                        match opt_res {
+                                                            None => view! { "Loading..." }.into_any(),
+                                                            Some(res) => {
+                                                                match res {
+                                                                    Ok(guests) => {
+                                                                        guests
+                                                                            .iter()
+                                                                            .map(|guest| {
+                                                                                view! {
+                                                                                    <option value=guest
+                                                                                        .id
+                                                                                        .to_string()>{guest.name.clone()}</option>
+                                                                                }
+                                                                            })
+                                                                            .collect_view()
+                                                                            .into_any()
+                                                                    }
+                                                                    Err(e) => {
+                                                                        view! {
+                                                                            "Error loading guests: "
+                                                                            {e.to_string()}
+                                                                        }
+                                                                            .into_any()
+                                                                    }
+                                                                }
+                                                            }
+                                                        }
+                                                    })
+                                            }}
+                                        </Suspense>
+                                    </select>
+                                </label>
+                            </div>
+                            <div class="form-group">
+                                <label>
+                                    "Token: "
+                                    <input
+                                        class="form-input"
+                                        type="text"
+                                        on:input=move |ev| token.set(event_target_value(&ev))
+                                    />
+                                </label>
+                            </div>
+                            <button class="btn-primary" type="submit">
+                                "Login"
+                            </button>
+                        </form>
                     }
+                        .into_any()
+                }
+            }}
+            {move || (!error.get().is_empty()).then(|| view! { <p>{error.get()}</p> })}
+        </div>
+    }
+}
+
+#[component]
+fn AdminLogin() -> impl IntoView {
+    let password = RwSignal::new(String::new());
+    let error = RwSignal::new(String::new());
+
+    let current_user_fetcher = Resource::new(|| (), |_| get_current_user());
+    let is_admin_fetcher = Resource::new(|| (), |_| is_admin());
+
+    let submit = move |ev: SubmitEvent| {
+        ev.prevent_default();
+        let p = password.get();
+        if p.is_empty() {
+            error.set("Please enter password.".to_string());
+            return;
+        }
+        spawn_local(async move {
+            match admin_login(p).await {
+                Ok(_) => {
+                    error.set(String::new());
+                    let navigate = use_navigate();
+                    navigate("/admin", NavigateOptions::default());
+                }
+                Err(e) => error.set(e.to_string()),
+            }
+        });
+    };
+
+    let navigate = use_navigate();
+    Effect::new(move || {
+        current_user_fetcher.with(|maybe_result| {
+            if let Some(Ok(Some(_))) = maybe_result {
+                navigate("/", NavigateOptions::default());
+            }
+        });
+        is_admin_fetcher.with(|maybe_result| {
+            if let Some(Ok(true)) = maybe_result {
+                navigate("/admin", NavigateOptions::default());
+            }
+        });
+    });
+
+    view! {
+        <div class="login-container">
+            <a class="back-link" href="/">
+                "← Home"
+            </a>
+            <h1>"Admin Login"</h1>
+            <form class="admin-form" on:submit=submit>
+                <div class="form-group">
+                    <label>
+                        "Password: "
+                        <input
+                            class="form-input"
+                            type="password"
+                            on:input=move |ev| password.set(event_target_value(&ev))
+                        />
+                    </label>
+                </div>
+                <button class="btn-primary" type="submit">
+                    "Login"
+                </button>
+            </form>
+            {move || {
+                if !error.get().is_empty() {
+                    view! { <p>{error.get()}</p> }.into_any()
+                } else {
+                    view! {}.into_any()
+                }
+            }}
+        </div>
+    }
+}
+
+#[component]
+fn AdminDashboard() -> impl IntoView {
+    // Fetchers for various resources (state).
+    let is_admin_fetcher = Resource::new(|| (), |_| is_admin());
+    let houses_fetcher = Resource::new(|| (), |_| get_houses());
+    let sorting_hat_fetcher = Resource::new(|| (), |_| get_sorting_hat_deliveries_handler());
+    let active_guests_fetcher = Resource::new(|| (), |_| get_active_guests());
+    let unregistered_guests_fetcher = Resource::new(|| (), |_| get_unregistered_guests());
+    let point_awards_fetcher = Resource::new(|| (), |_| get_point_awards());
+    let crossword_puzzle_fetcher = Resource::new(|| (), |_| get_crossword_puzzle());
+    let house_crossword_progress_fetcher =
+        Resource::new(|| (), |_| get_house_crossword_progress_handler());
+    let voting_status_fetcher = Resource::new(|| (), |_| voting_is_open_handler());
+    let rcv_result_fetcher = Resource::new(|| (), |_| get_rcv_result_handler());
+    let voting_stats_fetcher = Resource::new(|| (), |_| get_voting_stats_handler());
+    let games_enabled_fetcher = Resource::new(|| (), |_| get_games_enabled_handler());
+
+    // Refetches whatever another admin, a guest or the server itself changed.
+    use_live_events(move |event| {
+        if event.changes_scores() {
+            houses_fetcher.refetch();
+            point_awards_fetcher.refetch();
+        }
+        if event.changes_guests() {
+            active_guests_fetcher.refetch();
+            unregistered_guests_fetcher.refetch();
+            sorting_hat_fetcher.refetch();
+        }
+        if event.changes_crossword() {
+            house_crossword_progress_fetcher.refetch();
+        }
+        if event.changes_voting() {
+            voting_status_fetcher.refetch();
+            rcv_result_fetcher.refetch();
+            voting_stats_fetcher.refetch();
+        }
+        if event.changes_games() {
+            games_enabled_fetcher.refetch();
+        }
+    });
+
+    // Redirects to the home page if a user who isn't logged in as an admin tries to visit the
+    // admin dashboard.
+    // NOTE: This effect does not capture any reactive values, so it won't run again.
+    let navigate = use_navigate();
+    Effect::new(move || {
+        is_admin_fetcher.with(|maybe_result| {
+            if let Some(Ok(false)) = maybe_result {
+                navigate("/", NavigateOptions::default());
+            }
+        });
+    });
+
+    // Signals related to registering a new guest.
+    let selected_guest_id = RwSignal::new(0i32);
+    let new_guest_character = RwSignal::new(String::new());
+    let new_guest_house = RwSignal::new(0i32);
+    let register_error = RwSignal::new(String::new());
+    let registered_token = RwSignal::new(String::new());
+    let qr_svg = RwSignal::new(String::new());
+
+    Effect::new(move |_| {
+        if register_error.get_untracked().is_empty() && registered_token.get_untracked().is_empty()
+        {
+            qr_svg.set(String::new()); // Clears QR when form is reset.
+        }
+    });
+
+    // A handler for the register new guest submit button. Attempts to register a new guest with
+    // the provided details. On success, it clears any errors and sets the session token.
+    let register_submit = move |ev: SubmitEvent| {
+        ev.prevent_default();
+        let guest_id = selected_guest_id.get();
+        let character = new_guest_character.get();
+        let house_id = new_guest_house.get();
+        if guest_id == 0 || character.is_empty() {
+            register_error.set("Guest, character, and house are required.".to_string());
+            return;
+        }
+        spawn_local(async move {
+            match register_guest_handler(guest_id, house_id, character).await {
+                Ok((token, _, qr_svg_str)) => {
+                    register_error.set(String::new());
+                    registered_token.set(token.clone());
+                    qr_svg.set(qr_svg_str);
+                    selected_guest_id.set(0i32);
+                    new_guest_character.set(String::new());
+
+                    // Refetch the unregistered and active guests resources to update the dropdown
+                    // and active guests table without requiring a page refresh.
+                    unregistered_guests_fetcher.refetch();
+                    active_guests_fetcher.refetch();
+                    sorting_hat_fetcher.refetch();
                 }
                 Err(e) => register_error.set(e.to_string()),
             }
@@ -1339,6 +2326,8 @@ fn AdminDashboard() -> impl IntoView {
                                 <h1>"Admin Dashboard"</h1>
                             </header>
 
+                            <ScoreDriftWarning />
+
                             <section class="admin-section centered">
                                 <h2>"Register New Guest"</h2>
                                 <form class="admin-form register-form" on:submit=register_submit>
@@ -1467,6 +2456,10 @@ fn AdminDashboard() -> impl IntoView {
                                 }}
                             </section>
 
+                            <SortingHatDeliveries sorting_hat_fetcher=sorting_hat_fetcher />
+
+                            <RosterImport unregistered_guests_fetcher=unregistered_guests_fetcher />
+
                             <section class="admin-section centered">
                                 <h2>"Award Points to House"</h2>
                                 <form class="admin-form award-form" on:submit=award_house_submit>
@@ -1537,6 +2530,8 @@ fn AdminDashboard() -> impl IntoView {
                                 }}
                             </section>
 
+                            <GuestAwardForm active_guests_fetcher=active_guests_fetcher />
+
                             // House scores section, rendered unconditionally.
                             <Suspense fallback=|| {
                                 view! {
@@ -1686,18 +2681,35 @@ fn AdminDashboard() -> impl IntoView {
                                 </Suspense>
                             </section>
 
+                            <WordleRaceAdmin />
+
+                            <CatchTheKeysAdmin />
+
                             <section class="admin-section centered">
                                 <h2>"Horcrux Hunt"</h2>
-                                <div class="table-responisive">
+                                <div class="table-responsive">
                                     <table class="admin-table horcrux-table">
                                         <thead>
                                             <tr>
                                                 <th>"Location"</th>
                                                 <th>"Horcrux"</th>
-                                                <th>"G"</th>
-                                                <th>"H"</th>
-                                                <th>"R"</th>
-                                                <th>"S"</th>
+                                                {move || {
+                                                    houses_fetcher
+                                                        .get()
+                                                        .and_then(|res| res.ok())
+                                                        .unwrap_or_default()
+                                                        .into_iter()
+                                                        .map(|house| {
+                                                            let initial = house
+                                                                .name
+                                                                .chars()
+                                                                .next()
+                                                                .map(|c| c.to_string())
+                                                                .unwrap_or_default();
+                                                            view! { <th title=house.name>{initial}</th> }
+                                                        })
+                                                        .collect_view()
+                                                }}
                                             </tr>
                                         </thead>
                                         <tbody>
@@ -1712,16 +2724,26 @@ fn AdminDashboard() -> impl IntoView {
                                                     house_crossword_progress_fetcher
                                                         .with(|maybe_result| match maybe_result {
                                                             Some(Ok(matrix)) => {
-                                                                (0..7u8)
-                                                                    .map(|word_idx| {
+                                                                let words = crossword_puzzle_fetcher
+                                                                    .get()
+                                                                    .and_then(|res| res.ok())
+                                                                    .map(|puzzle| puzzle.words)
+                                                                    .unwrap_or_default();
+                                                                words
+                                                                    .into_iter()
+                                                                    .enumerate()
+                                                                    .map(|(word_idx, word)| {
                                                                         view! {
                                                                             <tr>
-                                                                                <td>{CROSSWORD_DEFS[word_idx as usize].location}</td>
-                                                                                <td>{CROSSWORD_DEFS[word_idx as usize].horcrux}</td>
+                                                                                <td>{word.location}</td>
+                                                                                <td>{word.horcrux}</td>
                                                                                 {matrix
                                                                                     .iter()
                                                                                     .map(|house_row| {
-                                                                                        let completed = house_row[word_idx as usize];
+                                                                                        let completed = house_row
+                                                                                            .get(word_idx)
+                                                                                            .copied()
+                                                                                            .unwrap_or(false);
                                                                                         view! {
                                                                                             <td>
                                                                                                 {completed
@@ -1751,6 +2773,9 @@ fn AdminDashboard() -> impl IntoView {
                                         </tbody>
                                     </table>
                                 </div>
+                                <CrosswordHints />
+                                <HorcruxClaimQueue />
+                                <CrosswordTimeline />
                             </section>
 
                             <section class="admin-section centered">
@@ -1943,344 +2968,1868 @@ fn AdminDashboard() -> impl IntoView {
                                 </Suspense>
                             </section>
 
-                            <section class="admin-section centered">
-                                <h2>"Point Awards History"</h2>
-                                <div class="table-responsive">
-                                    <table class="admin-table">
-                                        <tbody>
-                                            <tr>
-                                                <th>House</th>
-                                                <th>Amount</th>
-                                                <th>Time</th>
-                                            </tr>
-                                            <Suspense>
-                                                {move || {
-                                                    point_awards_fetcher
-                                                        .with(|maybe_result| match maybe_result {
-                                                            Some(Ok(awards)) => {
-                                                                awards
-                                                                    .iter()
-                                                                    .map(|award| {
-                                                                        view! {
-                                                                            <tr>
-                                                                                <td>
-                                                                                    {award.house_name.clone().unwrap_or("N/A".to_string())}
-                                                                                </td>
-                                                                                <td>{award.amount}</td>
-                                                                                <td>{award.awarded_at.format("%H:%M:%S").to_string()}</td>
-                                                                            </tr>
-                                                                        }
-                                                                    })
-                                                                    .collect_view()
-                                                                    .into_any()
-                                                            }
-                                                            _ => view! {}.into_view().into_any(),
-                                                        })
-                                                }}
-                                            </Suspense>
-                                        </tbody>
-                                    </table>
-                                </div>
-                            </section>
+                            <PointAwardsHistory point_awards_fetcher=point_awards_fetcher />
                         </div>
                         {show_qr_modal
                             .get()
                             .then(|| {
                                 view! {
-                                    <div
-                                        class="qr-modal-overlay"
-                                        on:click=move |_| show_qr_modal.set(false)
-                                    >
-                                        <div
-                                            class="qr-modal"
-                                            on:click=move |ev| ev.stop_propagation()
-                                        >
-                                            <h3>"QR for " {current_guest_name.get()}</h3>
-                                            <p>"Scan to auto-login"</p>
-                                            <div inner_html=current_qr_svg.get() />
-                                            <button
-                                                class="btn-secondary"
-                                                on:click=move |_| show_qr_modal.set(false)
-                                            >
-                                                "Close"
-                                            </button>
-                                        </div>
-                                    </div>
+                                    <div
+                                        class="qr-modal-overlay"
+                                        on:click=move |_| show_qr_modal.set(false)
+                                    >
+                                        <div
+                                            class="qr-modal"
+                                            on:click=move |ev| ev.stop_propagation()
+                                        >
+                                            <h3>"QR for " {current_guest_name.get()}</h3>
+                                            <p>"Scan to auto-login"</p>
+                                            <div inner_html=current_qr_svg.get() />
+                                            <button
+                                                class="btn-secondary"
+                                                on:click=move |_| show_qr_modal.set(false)
+                                            >
+                                                "Close"
+                                            </button>
+                                        </div>
+                                    </div>
+                                }
+                            })}
+                        <div class="logout-footer">
+                            <button class="btn-logout" on:click=logout>
+                                "Logout"
+                            </button>
+                        </div>
+                    }
+                        .into_any()
+                } else {
+                    view! { "Loading..." }.into_any()
+                }
+            }}
+        </Suspense>
+    }
+}
+
+// How long the guess row shakes when a guess is turned down.
+const WORDLE_SHAKE_DURATION: Duration = Duration::from_millis(600);
+// Delay between the tiles of a row flipping over, left to right, when a guess is revealed.
+const WORDLE_REVEAL_STAGGER_MS: usize = 150;
+// Local storage key remembering the colourblind palette on this device.
+#[cfg(feature = "hydrate")]
+const WORDLE_COLORBLIND_KEY: &str = "wordle-colorblind";
+
+// A Wordle key, pressed on screen or typed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum WordleKey {
+    Letter(char),
+    Enter,
+    Backspace,
+}
+
+#[cfg(any(feature = "hydrate", test))]
+impl WordleKey {
+    // The key a `KeyboardEvent.key` value stands for; None for keys the game doesn't use.
+    fn from_key_name(name: &str) -> Option<Self> {
+        match name {
+            "Enter" => Some(WordleKey::Enter),
+            "Backspace" => Some(WordleKey::Backspace),
+            _ => {
+                let mut chars = name.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) if c.is_ascii_alphabetic() => {
+                        Some(WordleKey::Letter(c.to_ascii_uppercase()))
+                    }
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+// How a letter's status is read out.
+fn letter_status_label(status: LetterStatus) -> &'static str {
+    match status {
+        LetterStatus::Correct => "correct",
+        LetterStatus::Present => "in the word, wrong place",
+        LetterStatus::Absent => "not in the word",
+        LetterStatus::Unused => "not tried",
+    }
+}
+
+// A revealed guess as read out to screen readers, e.g. "GHOST: G correct, H not in the word, ...".
+fn describe_guess(guess: &WordleGuess) -> String {
+    let letters: Vec<String> = guess
+        .word
+        .chars()
+        .zip(&guess.statuses)
+        .map(|(c, &status)| format!("{} {}", c, letter_status_label(status)))
+        .collect();
+    format!("{}: {}", guess.word, letters.join(", "))
+}
+
+/// The daily Wordle or, with `race`, the guest's game in the latest Wordle race along with the
+/// race standings. The server picks the word and checks every guess, so the page only ever knows
+/// the statuses of the guesses made so far. Guesses can be typed on the on-screen keyboard or a
+/// physical one.
+#[component]
+fn Wordle(#[prop(optional)] race: bool) -> impl IntoView {
+    let game_fetcher = Resource::new(
+        || (),
+        move |_| async move {
+            if race {
+                get_wordle_race_game().await
+            } else {
+                get_wordle_game().await
+            }
+        },
+    );
+    let race_fetcher = Resource::new(|| (), |_| get_wordle_race_handler());
+    let client_config_fetcher = Resource::new(|| (), |_| get_client_config());
+    let guesses = RwSignal::new(Vec::<WordleGuess>::new());
+    let current_guess = RwSignal::new(String::new());
+    let keyboard_status = RwSignal::new(HashMap::<char, LetterStatus>::new());
+    let game_over = RwSignal::new(false);
+    let message = RwSignal::new(String::new());
+    // What screen readers are told after each guess.
+    let announcement = RwSignal::new(String::new());
+    // Set while a guess is with the server, so it isn't sent twice.
+    let submitting = RwSignal::new(false);
+    // Why the last guess was turned down; the guess row shakes for a moment when it is.
+    let error = RwSignal::new(String::new());
+    let shake = RwSignal::new(false);
+    let shake_timer = StoredValue::new(None::<TimeoutHandle>);
+    // Orange and blue instead of green and yellow.
+    let colorblind = RwSignal::new(false);
+    // Set once the race is over, even for racers who hadn't finished.
+    let race_over = RwSignal::new(false);
+    // The race last seen, and whether it was on, to reload the game when a race starts or ends.
+    let seen_race = StoredValue::new(None::<Option<(i32, bool)>>);
+
+    use_live_events(move |event| {
+        if event.changes_wordle_race() {
+            race_fetcher.refetch();
+        }
+    });
+    Effect::new(move |_| {
+        if !race {
+            return;
+        }
+        if let Some(Ok(latest)) = race_fetcher.get() {
+            race_over.set(!latest.as_ref().is_some_and(WordleRace::is_on));
+            let key = latest.map(|latest| (latest.id, latest.is_on()));
+            if seen_race.get_value().is_some_and(|seen| seen != key) {
+                game_fetcher.refetch();
+            }
+            seen_race.set_value(Some(key));
+        }
+    });
+
+    let reject = move |reason: String| {
+        error.set(reason);
+        shake.set(true);
+        if let Some(timer) = shake_timer.get_value() {
+            timer.clear();
+        }
+        shake_timer.set_value(
+            set_timeout_with_handle(move || shake.set(false), WORDLE_SHAKE_DURATION).ok(),
+        );
+    };
+    on_cleanup(move || {
+        if let Some(timer) = shake_timer.get_value() {
+            timer.clear();
+        }
+    });
+
+    Effect::new(move |_| {
+        if let Some(Ok(game)) = game_fetcher.get() {
+            process_guess(
+                game,
+                guesses,
+                current_guess,
+                keyboard_status,
+                game_over,
+                message,
+                announcement,
+            );
+        }
+    });
+
+    let can_type = move || {
+        !game_over.get()
+            && !race_over.get()
+            && !submitting.get()
+            && guesses.get().len() < MAX_GUESSES
+    };
+
+    let submit = move || {
+        let guess = current_guess.get();
+        if guess.len() != WORD_LENGTH {
+            reject("Not enough letters".to_string());
+            return;
+        }
+        submitting.set(true);
+        spawn_local(async move {
+            let result = if race {
+                submit_wordle_race_guess_handler(guess).await
+            } else {
+                submit_wordle_guess_handler(guess).await
+            };
+            match result {
+                Ok(game) => {
+                    error.set(String::new());
+                    process_guess(
+                        game,
+                        guesses,
+                        current_guess,
+                        keyboard_status,
+                        game_over,
+                        message,
+                        announcement,
+                    );
+                }
+                // The server's reason, e.g. "Not in the word list", without the error kind.
+                Err(AppError::DbError(reason)) => reject(reason),
+                Err(e) => reject(e.to_string()),
+            }
+            submitting.set(false);
+        });
+    };
+
+    let press = move |key: WordleKey| {
+        if !can_type() {
+            return;
+        }
+        match key {
+            WordleKey::Letter(c) => {
+                if current_guess.get().len() < WORD_LENGTH {
+                    current_guess.update(|g| g.push(c));
+                    error.set(String::new());
+                }
+            }
+            WordleKey::Backspace => {
+                current_guess.update(|g| {
+                    g.pop();
+                });
+                error.set(String::new());
+            }
+            WordleKey::Enter => submit(),
+        }
+    };
+
+    // Typing on a physical keyboard, anywhere on the page.
+    #[cfg(feature = "hydrate")]
+    {
+        let handle = window_event_listener(leptos::ev::keydown, move |ev| {
+            if ev.ctrl_key() || ev.meta_key() || ev.alt_key() {
+                return;
+            }
+            if let Some(key) = WordleKey::from_key_name(&ev.key()) {
+                // Enter would also press whichever button has the focus.
+                ev.prevent_default();
+                press(key);
+            }
+        });
+        on_cleanup(move || handle.remove());
+    }
+
+    // The palette choice is kept on the device.
+    #[cfg(feature = "hydrate")]
+    let storage = || window().local_storage().ok().flatten();
+    Effect::new(move |_| {
+        #[cfg(feature = "hydrate")]
+        if let Some(storage) = storage() {
+            let saved = storage.get_item(WORDLE_COLORBLIND_KEY).ok().flatten();
+            colorblind.set(saved.as_deref() == Some("1"));
+        }
+    });
+    let toggle_colorblind = move |_| {
+        colorblind.update(|on| *on = !*on);
+        #[cfg(feature = "hydrate")]
+        if let Some(storage) = storage() {
+            let value = if colorblind.get_untracked() { "1" } else { "0" };
+            let _ = storage.set_item(WORDLE_COLORBLIND_KEY, value);
+        }
+    };
+
+    let grid = move || {
+        (0..MAX_GUESSES)
+            .map(|i| {
+                // Only the row being typed in changes with each key, so only a newly revealed
+                // row flips over.
+                let row = Memo::new(move |_| {
+                    guesses.with(|guesses| match guesses.get(i) {
+                        Some(guess) => Some(guess.clone()),
+                        None if i == guesses.len() => Some(WordleGuess {
+                            word: current_guess.get(),
+                            statuses: vec![LetterStatus::Unused; WORD_LENGTH],
+                        }),
+                        None => None,
+                    })
+                });
+                let shaking = move || shake.get() && guesses.with(|guesses| guesses.len() == i);
+                let label = move || match row.get() {
+                    Some(guess) if guess.statuses[0] != LetterStatus::Unused => {
+                        format!("Guess {}, {}", i + 1, describe_guess(&guess))
+                    }
+                    Some(guess) if !guess.word.is_empty() => {
+                        format!("Guess {}, typing {}", i + 1, guess.word)
+                    }
+                    _ => format!("Guess {}, empty", i + 1),
+                };
+                view! {
+                    <div class="row" class:shake=shaking role="group" aria-label=label>
+                        {move || {
+                            let (word, statuses) = row
+                                .get()
+                                .map(|guess| (guess.word, guess.statuses))
+                                .unwrap_or_default();
+                            (0..WORD_LENGTH)
+                                .map(|j| {
+                                    let letter = word.chars().nth(j).unwrap_or(' ');
+                                    let status = statuses
+                                        .get(j)
+                                        .cloned()
+                                        .unwrap_or(LetterStatus::Unused);
+                                    let class = match status {
+                                        LetterStatus::Correct => "correct reveal",
+                                        LetterStatus::Present => "present reveal",
+                                        LetterStatus::Absent => "absent reveal",
+                                        LetterStatus::Unused => "",
+                                    };
+                                    let delay = format!(
+                                        "animation-delay: {}ms",
+                                        j * WORDLE_REVEAL_STAGGER_MS
+                                    );
+                                    view! {
+                                        <div class=class style=delay aria-hidden="true">
+                                            {letter}
+                                        </div>
+                                    }
+                                })
+                                .collect::<Vec<_>>()
+                        }}
+                    </div>
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let keyboard = move || {
+        let rows = ["QWERTYUIOP", "ASDFGHJKL", "ZXCVBNM"];
+        view! {
+            <div class="keyboard">
+                {rows
+                    .iter()
+                    .map(|&row_str| {
+                        view! {
+                            <div class="keyboard-row">
+                                {move || {
+                                    if row_str == "ZXCVBNM" {
+                                        view! {
+                                            <button
+                                                class="special"
+                                                on:click=move |_| press(WordleKey::Enter)
+                                            >
+                                                "Enter"
+                                            </button>
+                                        }
+                                            .into_any()
+                                    } else {
+                                        view! { <div style="width: 60px;" /> }.into_any()
+                                    }
+                                }}
+                                {row_str
+                                    .chars()
+                                    .map(|k| {
+                                        let status = move || {
+                                            keyboard_status
+                                                .get()
+                                                .get(&k)
+                                                .cloned()
+                                                .unwrap_or(LetterStatus::Unused)
+                                        };
+                                        let class = move || match status() {
+                                            LetterStatus::Correct => "correct",
+                                            LetterStatus::Present => "present",
+                                            LetterStatus::Absent => "absent",
+                                            LetterStatus::Unused => "",
+                                        };
+                                        let label = move || match status() {
+                                            LetterStatus::Unused => k.to_string(),
+                                            status => {
+                                                format!("{}, {}", k, letter_status_label(status))
+                                            }
+                                        };
+                                        view! {
+                                            <button
+                                                class=class
+                                                aria-label=label
+                                                on:click=move |_| press(WordleKey::Letter(k))
+                                            >
+                                                {k}
+                                            </button>
+                                        }
+                                    })
+                                    .collect::<Vec<_>>()}
+                                {move || {
+                                    if row_str == "ZXCVBNM" {
+                                        view! {
+                                            <button
+                                                class="special"
+                                                aria-label="Backspace"
+                                                on:click=move |_| press(WordleKey::Backspace)
+                                            >
+                                                "⌫"
+                                            </button>
+                                        }
+                                            .into_any()
+                                    } else {
+                                        view! { <div style="width: 60px;" /> }.into_any()
+                                    }
+                                }}
+                            </div>
+                        }
+                    })
+                    .collect::<Vec<_>>()}
+            </div>
+        }
+    };
+
+    view! {
+        <div class="wordle" class:colorblind=move || colorblind.get()>
+            <a class="back-link" href="/">
+                "← Home"
+            </a>
+            <h1>{if race { "Wordle Race" } else { "Wordle" }}</h1>
+            <Suspense fallback=|| view! { <></> }>
+                {move || {
+                    race_fetcher
+                        .get()
+                        .and_then(|result| result.ok())
+                        .and_then(|latest| match latest {
+                            Some(latest) if race => {
+                                Some(view! { <WordleRaceStandings race=latest /> }.into_any())
+                            }
+                            None if race => {
+                                Some(
+                                    view! {
+                                        <p class="wordle-race-note">
+                                            "No race yet. Keep this page open: it starts here as soon as one is on."
+                                        </p>
+                                    }
+                                        .into_any(),
+                                )
+                            }
+                            Some(latest) if latest.is_on() => {
+                                Some(
+                                    view! {
+                                        <p class="wordle-race-note">
+                                            "A Wordle race is on! "
+                                            <a href="/games/wordle/race">"Race for your house"</a>
+                                        </p>
+                                    }
+                                        .into_any(),
+                                )
+                            }
+                            _ => None,
+                        })
+                }}
+            </Suspense>
+            <button
+                class="wordle-palette"
+                aria-pressed=move || colorblind.get().to_string()
+                on:click=toggle_colorblind
+            >
+                "Colourblind colours"
+            </button>
+            <Suspense fallback=|| view! { <></> }>
+                {move || {
+                    client_config_fetcher
+                        .get()
+                        .and_then(|config| config.ok())
+                        .filter(|config| config.wordle_hard_mode)
+                        .map(|_| {
+                            view! {
+                                <p class="wordle-hard-mode">
+                                    "Hard mode: every letter you have found must be used in your next guesses."
+                                </p>
+                            }
+                        })
+                }}
+            </Suspense>
+            <Suspense fallback=|| {
+                view! { <p>"Loading..."</p> }
+            }>
+                {move || {
+                    game_fetcher
+                        .get()
+                        .map(|result| match result {
+                            Ok(_) => {
+                                view! {
+                                    <div class="grid">{grid}</div>
+                                    {move || {
+                                        (!error.get().is_empty())
+                                            .then(|| {
+                                                view! {
+                                                    <p class="error" role="alert">
+                                                        {error.get()}
+                                                    </p>
+                                                }
+                                            })
+                                    }}
+                                    <p>{move || message.get()}</p>
+                                    <p class="visually-hidden" aria-live="polite">
+                                        {move || announcement.get()}
+                                    </p>
+                                    {keyboard}
+                                }
+                                    .into_any()
+                            }
+                            // E.g. "No Wordle race has been started yet", without the error kind.
+                            Err(AppError::DbError(reason)) => {
+                                view! { <p class="error">{reason}</p> }.into_any()
+                            }
+                            Err(e) => view! { <p class="error">{e.to_string()}</p> }.into_any(),
+                        })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+/// How a Wordle race is going: each house's solvers against the number needed to win, and every
+/// racer's guesses as coloured squares without the letters. Once the race is over, the winner and
+/// the word.
+#[component]
+fn WordleRaceStandings(race: WordleRace) -> impl IntoView {
+    let house_name = |house_id: i32| {
+        race.houses
+            .iter()
+            .find(|house| house.house_id == house_id)
+            .map(|house| house.name.clone())
+            .unwrap_or_default()
+    };
+    let summary = match (race.is_on(), race.winning_house_id) {
+        (true, _) => format!(
+            "First house with {} solvers wins {} points.",
+            race.solvers_needed, race.points
+        ),
+        (false, Some(house_id)) => format!(
+            "{} won the race and {} points! The word was {}.",
+            house_name(house_id),
+            race.points,
+            race.answer.clone().unwrap_or_default()
+        ),
+        (false, None) => format!(
+            "The race was stopped. The word was {}.",
+            race.answer.clone().unwrap_or_default()
+        ),
+    };
+    let solvers_needed = race.solvers_needed;
+    let winning_house_id = race.winning_house_id;
+
+    view! {
+        <div class="wordle-race">
+            <p class="wordle-race-summary">{summary}</p>
+            <ul class="wordle-race-houses">
+                {race
+                    .houses
+                    .into_iter()
+                    .map(|house| {
+                        view! {
+                            <li class:winner=winning_house_id == Some(house.house_id)>
+                                <strong>{house.name}</strong>
+                                {format!(
+                                    " {}/{} solved, {} racing",
+                                    house.solvers,
+                                    solvers_needed,
+                                    house.racers,
+                                )}
+                            </li>
+                        }
+                    })
+                    .collect_view()}
+            </ul>
+            <div class="wordle-racers">
+                {race
+                    .racers
+                    .into_iter()
+                    .map(|racer| {
+                        let label = match racer.status {
+                            WordleStatus::Won => "solved",
+                            WordleStatus::Lost => "out of guesses",
+                            WordleStatus::Playing => "playing",
+                        };
+                        view! {
+                            <div class="wordle-racer">
+                                <span>{format!("{} ({})", racer.guest_name, label)}</span>
+                                <div class="wordle-racer-grid" aria-hidden="true">
+                                    {racer
+                                        .statuses
+                                        .into_iter()
+                                        .map(|row| {
+                                            view! {
+                                                <div class="wordle-racer-row">
+                                                    {row
+                                                        .into_iter()
+                                                        .map(|status| {
+                                                            let class = match status {
+                                                                LetterStatus::Correct => "correct",
+                                                                LetterStatus::Present => "present",
+                                                                _ => "absent",
+                                                            };
+                                                            view! { <span class=class></span> }
+                                                        })
+                                                        .collect_view()}
+                                                </div>
+                                            }
+                                        })
+                                        .collect_view()}
+                                </div>
+                            </div>
+                        }
+                    })
+                    .collect_view()}
+            </div>
+        </div>
+    }
+}
+
+/// Admin dashboard Wordle race controls: starts a race (with the configured number of solvers and
+/// points) or stops the one that is on, and shows how it is going.
+#[component]
+fn WordleRaceAdmin() -> impl IntoView {
+    let race_fetcher = Resource::new(|| (), |_| get_wordle_race_handler());
+    let race_error = RwSignal::new(String::new());
+
+    use_live_events(move |event| {
+        if event.changes_wordle_race() {
+            race_fetcher.refetch();
+        }
+    });
+
+    let start = move |_| {
+        spawn_local(async move {
+            match start_wordle_race_handler().await {
+                Ok(_) => race_error.set(String::new()),
+                Err(e) => race_error.set(e.to_string()),
+            }
+            race_fetcher.refetch();
+        });
+    };
+
+    let stop = move |_| {
+        spawn_local(async move {
+            if leptos::leptos_dom::helpers::window()
+                .confirm_with_message("Stop the Wordle race without a winner?")
+                .unwrap_or(false)
+            {
+                match stop_wordle_race_handler().await {
+                    Ok(_) => race_error.set(String::new()),
+                    Err(e) => race_error.set(e.to_string()),
+                }
+                race_fetcher.refetch();
+            }
+        });
+    };
+
+    view! {
+        <section class="admin-section centered">
+            <h2>"Wordle Race"</h2>
+            <Suspense fallback=|| {
+                view! { <p>"Loading..."</p> }
+            }>
+                {move || {
+                    race_fetcher
+                        .get()
+                        .and_then(|result| result.ok())
+                        .map(|latest| {
+                            let is_on = latest.as_ref().is_some_and(WordleRace::is_on);
+                            view! {
+                                {if is_on {
+                                    view! {
+                                        <button class="btn-danger" on:click=stop>
+                                            "Stop Race"
+                                        </button>
+                                    }
+                                        .into_any()
+                                } else {
+                                    view! {
+                                        <button class="btn-primary" on:click=start>
+                                            "Start Race"
+                                        </button>
+                                    }
+                                        .into_any()
+                                }}
+                                {latest.map(|latest| view! { <WordleRaceStandings race=latest /> })}
+                            }
+                        })
+                }}
+            </Suspense>
+            {move || {
+                (!race_error.get().is_empty())
+                    .then(|| view! { <p class="error">{race_error.get()}</p> })
+            }}
+        </section>
+    }
+}
+
+/// Brings the page up to date with the game as the server last sent it: the guesses so far, the
+/// keyboard colours they earn, the outcome once the game is over, and what screen readers are told
+/// about the guesses they haven't heard yet.
+fn process_guess(
+    game: WordleGame,
+    guesses: RwSignal<Vec<WordleGuess>>,
+    current_guess: RwSignal<String>,
+    keyboard_status: RwSignal<HashMap<char, LetterStatus>>,
+    game_over: RwSignal<bool>,
+    message: RwSignal<String>,
+    announcement: RwSignal<String>,
+) {
+    current_guess.set(String::new());
+
+    // Update keyboard statuses.
+    keyboard_status.update(|ks| {
+        ks.clear();
+        for guess in &game.guesses {
+            for (c, &new_status) in guess.word.chars().zip(&guess.statuses) {
+                let current = ks.get(&c).cloned().unwrap_or(LetterStatus::Unused);
+                // Priority: Correct > Present > Absent.
+                let updated = match (current, new_status) {
+                    (_, LetterStatus::Correct) => LetterStatus::Correct,
+                    (LetterStatus::Unused, LetterStatus::Present) => LetterStatus::Present,
+                    (LetterStatus::Absent, LetterStatus::Present) => LetterStatus::Present,
+                    (_, LetterStatus::Absent)
+                        if current != LetterStatus::Correct && current != LetterStatus::Present =>
+                    {
+                        LetterStatus::Absent
+                    }
+                    _ => current,
+                };
+                ks.insert(c, updated);
+            }
+        }
+    });
+
+    // Check win/loss.
+    game_over.set(game.status != WordleStatus::Playing);
+    let outcome = match (game.status, game.points) {
+        (WordleStatus::Won, Some(points)) => {
+            format!("You win! {} points for your house.", points)
+        }
+        // Racers score for their house, which may or may not win the race.
+        (WordleStatus::Won, None) => "You got it!".to_string(),
+        (WordleStatus::Lost, _) => match game.answer {
+            Some(answer) => format!("Game over! The word was {}", answer),
+            // A race only gives the word away once every house is done.
+            None => "Game over! The word is revealed when the race is over.".to_string(),
+        },
+        (WordleStatus::Playing, _) => String::new(),
+    };
+
+    let heard = guesses.with_untracked(|guesses| guesses.len());
+    let news: Vec<String> = game
+        .guesses
+        .iter()
+        .skip(heard)
+        .map(describe_guess)
+        .chain((!outcome.is_empty()).then(|| outcome.clone()))
+        .collect();
+    if !news.is_empty() {
+        announcement.set(news.join(". "));
+    }
+    message.set(outcome);
+    guesses.set(game.guesses);
+}
+
+/// Admin dashboard section for uploading a guest roster (CSV or JSON) as unregistered guests.
+#[component]
+fn RosterImport(
+    unregistered_guests_fetcher: Resource<Result<Vec<Guest>, AppError>>,
+) -> impl IntoView {
+    let roster_contents = RwSignal::new(String::new());
+    let roster_file_name = RwSignal::new(String::new());
+    let replace_roster = RwSignal::new(false);
+    let import_error = RwSignal::new(String::new());
+    let import_report = RwSignal::new(None::<RosterImportReport>);
+
+    // Reads the selected file into memory so it can be sent with the form.
+    let on_file_change = move |ev: leptos::ev::Event| {
+        #[cfg(feature = "hydrate")]
+        {
+            let input: web_sys::HtmlInputElement = event_target(&ev);
+            if let Some(file) = input.files().and_then(|files| files.get(0)) {
+                roster_file_name.set(file.name());
+                let text_future = wasm_bindgen_futures::JsFuture::from(file.text());
+                spawn_local(async move {
+                    match text_future.await {
+                        Ok(text) => roster_contents.set(text.as_string().unwrap_or_default()),
+                        Err(e) => import_error.set(format!("Failed to read file: {:?}", e)),
+                    }
+                });
+            }
+        }
+        #[cfg(not(feature = "hydrate"))]
+        let _ = ev;
+    };
+
+    let import_submit = move |ev: SubmitEvent| {
+        ev.prevent_default();
+        let contents = roster_contents.get();
+        if contents.trim().is_empty() {
+            import_error.set("Choose a non-empty roster file.".to_string());
+            return;
+        }
+        let replace = replace_roster.get();
+        if replace
+            && !leptos::leptos_dom::helpers::window()
+                .confirm_with_message("Remove all guests who have never been registered first?")
+                .unwrap_or(false)
+        {
+            return;
+        }
+        spawn_local(async move {
+            match import_guest_roster_handler(contents, replace).await {
+                Ok(report) => {
+                    if report.invalid.is_empty() {
+                        import_error.set(String::new());
+                    } else {
+                        import_error.set("Roster rejected, nothing was imported.".to_string());
+                    }
+                    import_report.set(Some(report));
+                    unregistered_guests_fetcher.refetch();
+                }
+                Err(e) => {
+                    import_report.set(None);
+                    import_error.set(e.to_string());
+                }
+            }
+        });
+    };
+
+    view! {
+        <section class="admin-section centered">
+            <h2>"Import Guest Roster"</h2>
+            <form class="admin-form roster-form" on:submit=import_submit>
+                <div class="form-group">
+                    <label>
+                        <span class="label-text">"Roster (CSV or JSON): "</span>
+                        <input
+                            class="form-input"
+                            type="file"
+                            accept=".csv,.json,.txt"
+                            on:change=on_file_change
+                        />
+                    </label>
+                </div>
+                <div class="form-group">
+                    <label>
+                        <input
+                            type="checkbox"
+                            prop:checked=move || replace_roster.get()
+                            on:change=move |ev| replace_roster.set(event_target_checked(&ev))
+                        />
+                        <span class="label-text">" Replace guests who never registered"</span>
+                    </label>
+                </div>
+                <button type="submit" class="btn-primary">
+                    "Import"
+                </button>
+            </form>
+            {move || {
+                (!roster_file_name.get().is_empty())
+                    .then(|| view! { <p class="roster-file">{roster_file_name.get()}</p> })
+            }}
+            {move || {
+                (!import_error.get().is_empty())
+                    .then(|| view! { <p class="error">{import_error.get()}</p> })
+            }}
+            {move || {
+                import_report
+                    .get()
+                    .map(|report| {
+                        view! {
+                            <div class="roster-report">
+                                {(report.removed > 0)
+                                    .then(|| {
+                                        view! { <p>"Removed " {report.removed} " unregistered guests."</p> }
+                                    })}
+                                <p>"Imported " {report.imported.len()} " guests."</p>
+                                <ul>
+                                    {report
+                                        .duplicates
+                                        .iter()
+                                        .map(|name| view! { <li>"Skipped duplicate: " {name.clone()}</li> })
+                                        .collect_view()}
+                                    {report
+                                        .invalid
+                                        .iter()
+                                        .map(|problem| view! { <li class="error">{problem.clone()}</li> })
+                                        .collect_view()}
+                                </ul>
+                            </div>
+                        }
+                    })
+            }}
+        </section>
+    }
+}
+
+// Reasons offered when awarding points to a guest. Anything else can be typed in.
+const GUEST_AWARD_REASONS: &[&str] = &[
+    "Costume",
+    "Trivia",
+    "House spirit",
+    "Helping out",
+    "Mischief managed",
+];
+
+/// Admin dashboard warning shown while cached house or guest scores disagree with the point award
+/// ledger, with a button to overwrite them with the ledger totals. Renders nothing otherwise.
+#[component]
+fn ScoreDriftWarning() -> impl IntoView {
+    let mismatches_fetcher = Resource::new(|| (), |_| get_score_mismatches());
+    let reconcile_error = RwSignal::new(String::new());
+
+    use_live_events(move |event| {
+        if event.changes_scores() || event.changes_guests() {
+            mismatches_fetcher.refetch();
+        }
+    });
+
+    let reconcile = move |_| {
+        spawn_local(async move {
+            if leptos::leptos_dom::helpers::window()
+                .confirm_with_message("Overwrite the mismatched scores with the ledger totals?")
+                .unwrap_or(false)
+            {
+                match reconcile_scores_handler().await {
+                    Ok(_) => {
+                        reconcile_error.set(String::new());
+                        mismatches_fetcher.refetch();
+                    }
+                    Err(e) => reconcile_error.set(e.to_string()),
+                }
+            }
+        });
+    };
+
+    view! {
+        <Suspense>
+            {move || {
+                mismatches_fetcher
+                    .get()
+                    .and_then(|result| result.ok())
+                    .filter(|mismatches| !mismatches.is_empty())
+                    .map(|mismatches| {
+                        view! {
+                            <section class="admin-section centered score-drift">
+                                <h2>"Scores Out of Sync"</h2>
+                                <p>
+                                    "These cached scores disagree with the point award log. Reconciling sets them to the log totals."
+                                </p>
+                                <ul>
+                                    {mismatches
+                                        .into_iter()
+                                        .map(|mismatch| {
+                                            let kind = if mismatch.house_id.is_some() {
+                                                "House"
+                                            } else {
+                                                "Guest"
+                                            };
+                                            view! {
+                                                <li>
+                                                    {format!(
+                                                        "{} {}: shows {}, log says {}",
+                                                        kind,
+                                                        mismatch.name,
+                                                        mismatch.cached,
+                                                        mismatch.ledger,
+                                                    )}
+                                                </li>
+                                            }
+                                        })
+                                        .collect_view()}
+                                </ul>
+                                {move || {
+                                    (!reconcile_error.get().is_empty())
+                                        .then(|| view! { <p class="error">{reconcile_error.get()}</p> })
+                                }}
+                                <button class="btn-danger" on:click=reconcile>
+                                    "Reconcile"
+                                </button>
+                            </section>
+                        }
+                    })
+            }}
+        </Suspense>
+    }
+}
+
+/// Admin dashboard crossword hint usage: each house's hints against its limit, which can be changed
+/// here, and the log of hints taken.
+#[component]
+fn CrosswordHints() -> impl IntoView {
+    let usage_fetcher = Resource::new(|| (), |_| get_hint_usage_handler());
+    let log_fetcher = Resource::new(|| (), |_| get_crossword_hint_log_handler());
+    let limit_error = RwSignal::new(String::new());
+
+    // Hints are paid for with point awards.
+    use_live_events(move |event| {
+        if event.changes_scores() {
+            usage_fetcher.refetch();
+            log_fetcher.refetch();
+        }
+    });
+
+    let set_limit = move |house_id: i32, limit: i32| {
+        spawn_local(async move {
+            match set_house_hint_limit_handler(house_id, limit).await {
+                Ok(()) => limit_error.set(String::new()),
+                Err(e) => limit_error.set(e.to_string()),
+            }
+            usage_fetcher.refetch();
+        });
+    };
+
+    view! {
+        <h3>"Hints"</h3>
+        <div class="table-responsive">
+            <table class="admin-table hint-usage-table">
+                <thead>
+                    <tr>
+                        <th>"House"</th>
+                        <th>"Used"</th>
+                        <th>"Points Spent"</th>
+                        <th>"Limit"</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    <Suspense fallback=|| {
+                        view! {
+                            <tr>
+                                <td colspan="4">"Loading..."</td>
+                            </tr>
+                        }
+                    }>
+                        {move || {
+                            usage_fetcher
+                                .get()
+                                .and_then(|result| result.ok())
+                                .unwrap_or_default()
+                                .into_iter()
+                                .map(|usage| {
+                                    let house_id = usage.house_id;
+                                    view! {
+                                        <tr>
+                                            <td>{usage.house_name}</td>
+                                            <td>{usage.used}</td>
+                                            <td>{usage.spent}</td>
+                                            <td>
+                                                <input
+                                                    class="form-input hint-limit"
+                                                    type="number"
+                                                    min="0"
+                                                    prop:value=usage.limit.to_string()
+                                                    on:change=move |ev| {
+                                                        if let Ok(limit) = event_target_value(&ev)
+                                                            .parse::<i32>()
+                                                        {
+                                                            set_limit(house_id, limit);
+                                                        }
+                                                    }
+                                                />
+                                            </td>
+                                        </tr>
+                                    }
+                                })
+                                .collect_view()
+                        }}
+                    </Suspense>
+                </tbody>
+            </table>
+        </div>
+        {move || {
+            (!limit_error.get().is_empty())
+                .then(|| view! { <p class="error">{limit_error.get()}</p> })
+        }}
+        <Suspense>
+            {move || {
+                log_fetcher
+                    .get()
+                    .and_then(|result| result.ok())
+                    .filter(|log| !log.is_empty())
+                    .map(|log| {
+                        view! {
+                            <ul class="hint-log">
+                                {log
+                                    .into_iter()
+                                    .map(|hint| {
+                                        let kind = match hint.kind {
+                                            HintKind::Letter => "a letter",
+                                            HintKind::Word => "the word",
+                                        };
+                                        view! {
+                                            <li>
+                                                {format!(
+                                                    "{} ({}) revealed {} of word {} for {} points, {}",
+                                                    hint.guest_name.unwrap_or_else(|| "A former guest".to_string()),
+                                                    hint.house_name,
+                                                    kind,
+                                                    hint.word_index + 1,
+                                                    hint.cost,
+                                                    hint.used_at.format("%H:%M"),
+                                                )}
+                                            </li>
+                                        }
+                                    })
+                                    .collect_view()}
+                            </ul>
+                        }
+                    })
+            }}
+        </Suspense>
+    }
+}
+
+/// Admin dashboard queue of horcrux claims, pending ones first. Approving a claim scores points for
+/// its house; rejecting one lets the house claim that horcrux again.
+#[component]
+fn HorcruxClaimQueue() -> impl IntoView {
+    let queue_fetcher = Resource::new(|| (), |_| get_horcrux_claim_queue());
+    let review_error = RwSignal::new(String::new());
+    // The claim whose photo is shown, and the photo as a data URL.
+    let photo = RwSignal::new(None::<(i32, String)>);
+
+    use_live_events(move |event| {
+        if event.changes_horcrux_claims() {
+            queue_fetcher.refetch();
+        }
+    });
+
+    let review = move |claim_id: i32, approve: bool| {
+        spawn_local(async move {
+            match review_horcrux_claim_handler(claim_id, approve).await {
+                Ok(()) => review_error.set(String::new()),
+                Err(e) => review_error.set(e.to_string()),
+            }
+            queue_fetcher.refetch();
+        });
+    };
+
+    let show_photo = move |claim_id: i32| {
+        if photo.with_untracked(|photo| photo.as_ref().is_some_and(|(id, _)| *id == claim_id)) {
+            photo.set(None);
+            return;
+        }
+        spawn_local(async move {
+            match get_horcrux_claim_photo_handler(claim_id).await {
+                Ok(url) => {
+                    review_error.set(String::new());
+                    photo.set(Some((claim_id, url)));
+                }
+                Err(e) => review_error.set(e.to_string()),
+            }
+        });
+    };
+
+    view! {
+        <h3>"Claims"</h3>
+        <div class="table-responsive">
+            <table class="admin-table horcrux-claims-table">
+                <thead>
+                    <tr>
+                        <th>"House"</th>
+                        <th>"Horcrux"</th>
+                        <th>"Claimed"</th>
+                        <th>"Status"</th>
+                        <th></th>
+                    </tr>
+                </thead>
+                <tbody>
+                    <Suspense fallback=|| {
+                        view! {
+                            <tr>
+                                <td colspan="5">"Loading..."</td>
+                            </tr>
+                        }
+                    }>
+                        {move || {
+                            queue_fetcher
+                                .get()
+                                .and_then(|result| result.ok())
+                                .unwrap_or_default()
+                                .into_iter()
+                                .map(|claim| {
+                                    let claim_id = claim.id;
+                                    let status = match claim.status {
+                                        ClaimStatus::Pending => "Pending",
+                                        ClaimStatus::Approved => "Approved",
+                                        ClaimStatus::Rejected => "Rejected",
+                                    };
+                                    view! {
+                                        <tr class=claim.status.as_str()>
+                                            <td>{claim.house_name}</td>
+                                            <td title=claim.location>{claim.horcrux}</td>
+                                            <td>
+                                                {format!(
+                                                    "{}, {}",
+                                                    claim.guest_name.unwrap_or_else(|| "A former guest".to_string()),
+                                                    claim.claimed_at.format("%H:%M"),
+                                                )}
+                                            </td>
+                                            <td>{status}</td>
+                                            <td class="claim-actions">
+                                                {claim
+                                                    .has_photo
+                                                    .then(|| {
+                                                        view! {
+                                                            <button
+                                                                class="btn-secondary"
+                                                                on:click=move |_| show_photo(claim_id)
+                                                            >
+                                                                "Photo"
+                                                            </button>
+                                                        }
+                                                    })}
+                                                {(claim.status == ClaimStatus::Pending)
+                                                    .then(|| {
+                                                        view! {
+                                                            <button
+                                                                class="btn-primary"
+                                                                on:click=move |_| review(claim_id, true)
+                                                            >
+                                                                "Approve"
+                                                            </button>
+                                                            <button
+                                                                class="btn-danger"
+                                                                on:click=move |_| review(claim_id, false)
+                                                            >
+                                                                "Reject"
+                                                            </button>
+                                                        }
+                                                    })}
+                                            </td>
+                                        </tr>
+                                    }
+                                })
+                                .collect_view()
+                        }}
+                    </Suspense>
+                </tbody>
+            </table>
+        </div>
+        {move || {
+            (!review_error.get().is_empty())
+                .then(|| view! { <p class="error">{review_error.get()}</p> })
+        }}
+        {move || {
+            photo
+                .get()
+                .map(|(_, url)| {
+                    view! {
+                        <img
+                            class="horcrux-photo"
+                            src=url
+                            alt="Photo sent with the claim"
+                            on:click=move |_| photo.set(None)
+                        />
+                    }
+                })
+        }}
+    }
+}
+
+/// Admin dashboard crossword report: every word each guest solved, in order, with how long they
+/// took from opening the crossword. The solves that scored a word for their house are marked.
+#[component]
+fn CrosswordTimeline() -> impl IntoView {
+    let timeline_fetcher = Resource::new(|| (), |_| get_crossword_timeline_handler());
+
+    use_live_events(move |event| {
+        if event.changes_crossword() {
+            timeline_fetcher.refetch();
+        }
+    });
+
+    view! {
+        <h3>"Solves"</h3>
+        <p>
+            <button class="btn-secondary" on:click=move |_| timeline_fetcher.refetch()>
+                "Refresh"
+            </button>
+            " "
+            <a href="/leaderboard">"Leaderboard"</a>
+        </p>
+        <div class="table-responsive">
+            <table class="admin-table crossword-timeline-table">
+                <thead>
+                    <tr>
+                        <th>"Time"</th>
+                        <th>"Guest"</th>
+                        <th>"House"</th>
+                        <th>"Word"</th>
+                        <th>"Elapsed"</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    <Suspense fallback=|| {
+                        view! {
+                            <tr>
+                                <td colspan="5">"Loading..."</td>
+                            </tr>
+                        }
+                    }>
+                        {move || {
+                            timeline_fetcher
+                                .get()
+                                .and_then(|result| result.ok())
+                                .unwrap_or_default()
+                                .into_iter()
+                                .map(|solve| {
+                                    view! {
+                                        <tr
+                                            class:first-solve=solve.first_for_house
+                                            title=solve
+                                                .first_for_house
+                                                .then_some("Scored the word for the house")
+                                        >
+                                            <td>{solve.solved_at.format("%H:%M:%S").to_string()}</td>
+                                            <td>{solve.guest_name}</td>
+                                            <td>{solve.house_name}</td>
+                                            <td>{solve.word_index + 1}</td>
+                                            <td>{format_elapsed(solve.elapsed_seconds)}</td>
+                                        </tr>
+                                    }
+                                })
+                                .collect_view()
+                        }}
+                    </Suspense>
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+/// Guest's "we found it" button for the horcrux behind a solved word, with an optional photo. Once
+/// claimed it shows how the claim stands instead.
+#[component]
+fn HorcruxClaimForm(
+    word_index: usize,
+    claims: RwSignal<Vec<Option<ClaimStatus>>>,
+) -> impl IntoView {
+    let photo = RwSignal::new(None::<PhotoUpload>);
+    let photo_name = RwSignal::new(String::new());
+    let sending = RwSignal::new(false);
+    let claim_error = RwSignal::new(String::new());
+    let status = move || claims.with(|claims| claims.get(word_index).copied().flatten());
+
+    let on_photo_change = move |ev: leptos::ev::Event| {
+        #[cfg(feature = "hydrate")]
+        {
+            use base64::Engine;
+
+            let input: web_sys::HtmlInputElement = event_target(&ev);
+            if let Some(file) = input.files().and_then(|files| files.get(0)) {
+                photo_name.set(file.name());
+                let content_type = file.type_();
+                let buffer_future = wasm_bindgen_futures::JsFuture::from(file.array_buffer());
+                spawn_local(async move {
+                    match buffer_future.await {
+                        Ok(buffer) => {
+                            let bytes = js_sys::Uint8Array::new(&buffer).to_vec();
+                            photo.set(Some(PhotoUpload {
+                                content_type,
+                                data: base64::engine::general_purpose::STANDARD.encode(bytes),
+                            }));
+                        }
+                        Err(e) => claim_error.set(format!("Failed to read photo: {:?}", e)),
+                    }
+                });
+            }
+        }
+        #[cfg(not(feature = "hydrate"))]
+        let _ = ev;
+    };
+
+    let claim = move |_| {
+        sending.set(true);
+        let upload = photo.get_untracked();
+        spawn_local(async move {
+            match claim_horcrux(word_index, upload).await {
+                Ok(statuses) => {
+                    claim_error.set(String::new());
+                    photo.set(None);
+                    photo_name.set(String::new());
+                    claims.set(statuses);
+                }
+                Err(e) => claim_error.set(e.to_string()),
+            }
+            sending.set(false);
+        });
+    };
+
+    view! {
+        <div class="horcrux-claim">
+            {move || match status() {
+                Some(ClaimStatus::Approved) => {
+                    view! { <p class="claim-status approved">"Found! Hagrid has it safe."</p> }
+                        .into_any()
+                }
+                Some(ClaimStatus::Pending) => {
+                    view! {
+                        <p class="claim-status pending">"Claimed. Hagrid is on her way to check."</p>
+                    }
+                        .into_any()
+                }
+                status => {
+                    view! {
+                        {(status == Some(ClaimStatus::Rejected))
+                            .then(|| {
+                                view! {
+                                    <p class="claim-status rejected">
+                                        "Hagrid couldn't find it there. Look again!"
+                                    </p>
                                 }
                             })}
-                        <div class="logout-footer">
-                            <button class="btn-logout" on:click=logout>
-                                "Logout"
-                            </button>
-                        </div>
+                        <label class="btn-secondary claim-photo">
+                            {move || {
+                                if photo_name.get().is_empty() {
+                                    "Add a photo".to_string()
+                                } else {
+                                    photo_name.get()
+                                }
+                            }}
+                            <input
+                                type="file"
+                                accept="image/*"
+                                capture="environment"
+                                on:change=on_photo_change
+                            />
+                        </label>
+                        <button class="btn-primary" disabled=sending on:click=claim>
+                            "We found it!"
+                        </button>
                     }
                         .into_any()
-                } else {
-                    view! { "Loading..." }.into_any()
                 }
             }}
-        </Suspense>
+            {move || {
+                (!claim_error.get().is_empty())
+                    .then(|| view! { <p class="error">{claim_error.get()}</p> })
+            }}
+        </div>
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
-enum LetterStatus {
-    Correct, // green: right letter, right position
-    Present, // yellow: right letter, wrong position
-    Absent,  // gray: wrong letter
-    Unused,  // default for keyboard
-}
-
-/// Renders the home page of your application.
+/// Admin dashboard log of every point award. Awards can be reverted from here, which logs a linked
+/// reversing entry; reverted awards stay in the log, struck through.
 #[component]
-fn Wordle() -> impl IntoView {
-    let target_word = RwSignal::new(String::new());
-    let guesses = RwSignal::new(vec![] as Vec<String>);
-    let current_guess = RwSignal::new(String::new());
-    let keyboard_status = RwSignal::new(HashMap::<char, LetterStatus>::new());
-    let game_over = RwSignal::new(false);
-    let message = RwSignal::new(String::new());
-
-    Effect::new(move || {
-        let mut rng = rng();
-        let word = WORDS.choose(&mut rng).unwrap_or(&"apple").to_uppercase();
-        target_word.set(word);
-        log!("Target word: {}", target_word.get());
-    });
+fn PointAwardsHistory(
+    point_awards_fetcher: Resource<Result<Vec<PointAwardLog>, AppError>>,
+) -> impl IntoView {
+    let revert_error = RwSignal::new(String::new());
 
-    let grid = move || {
-        let mut rows = vec![];
-        for i in 0..6 {
-            let row_guess = if i < guesses.get().len() {
-                guesses.get()[i].clone()
-            } else if i == guesses.get().len() {
-                current_guess.get()
-            } else {
-                String::from("     ")
-            };
-
-            let statuses = if i < guesses.get().len() {
-                compute_statuses(&row_guess, &target_word.get())
-            } else {
-                vec![LetterStatus::Unused; 5]
-            };
-
-            rows.push(view! {
-                <div class="row">
-                    {(0..5)
-                        .map(|j| {
-                            let letter = row_guess.chars().nth(j).unwrap_or(' ');
-                            let status = statuses.get(j).cloned().unwrap_or(LetterStatus::Unused);
-                            let class = match status {
-                                LetterStatus::Correct => "correct",
-                                LetterStatus::Present => "present",
-                                LetterStatus::Absent => "absent",
-                                _ => "",
-                            };
-                            view! { <div class=class>{letter}</div> }
-                        })
-                        .collect::<Vec<_>>()}
-                </div>
-            });
-        }
-        rows
+    let revert = move |award_id: i32| {
+        spawn_local(async move {
+            if leptos::leptos_dom::helpers::window()
+                .confirm_with_message("Revert this award and take the points back?")
+                .unwrap_or(false)
+            {
+                match revert_point_award_handler(award_id).await {
+                    Ok(_) => {
+                        revert_error.set(String::new());
+                        point_awards_fetcher.refetch();
+                    }
+                    Err(e) => revert_error.set(e.to_string()),
+                }
+            }
+        });
     };
 
-    let keyboard = move || {
-        let rows = ["QWERTYUIOP", "ASDFGHJKL", "ZXCVBNM"];
-        view! {
-            <div class="keyboard">
-                {rows
-                    .iter()
-                    .map(|&row_str| {
-                        view! {
-                            <div class="keyboard-row">
-                                {move || {
-                                    if row_str == "ZXCVBNM" {
-                                        view! {
-                                            <button
-                                                class="special"
-                                                on:click=move |_| {
-                                                    if game_over.get() || guesses.get().len() >= 6 {
-                                                        return;
-                                                    }
-                                                    let guess = current_guess.get();
-                                                    if guess.len() == 5
-                                                        && WORDS.contains(&guess.to_lowercase().as_str())
-                                                    {
-                                                        process_guess(
-                                                            guess.clone(),
-                                                            target_word.get(),
-                                                            guesses,
-                                                            current_guess,
-                                                            keyboard_status,
-                                                            game_over,
-                                                            message,
-                                                        );
-                                                    } else {
-                                                        log!("Invalid word");
-                                                    }
-                                                }
-                                            >
-                                                "Enter"
-                                            </button>
-                                        }
-                                            .into_any()
-                                    } else {
-                                        view! { <div style="width: 60px;" /> }.into_any()
-                                    }
-                                }}
-                                {row_str
-                                    .chars()
-                                    .map(|k| {
-                                        let status = move || {
-                                            keyboard_status
-                                                .get()
-                                                .get(&k)
-                                                .cloned()
-                                                .unwrap_or(LetterStatus::Unused)
-                                        };
-                                        let class = move || match status() {
-                                            LetterStatus::Correct => "correct",
-                                            LetterStatus::Present => "present",
-                                            LetterStatus::Absent => "absent",
-                                            LetterStatus::Unused => "",
-                                        };
-                                        view! {
-                                            <button
-                                                class=class
-                                                on:click=move |_| {
-                                                    if game_over.get() || guesses.get().len() >= 6 {
-                                                        return;
-                                                    }
-                                                    if current_guess.get().len() < 5 {
-                                                        current_guess.update(|g| g.push(k));
+    view! {
+        <section class="admin-section centered">
+            <h2>"Point Awards History"</h2>
+            {move || {
+                (!revert_error.get().is_empty())
+                    .then(|| view! { <p class="error">{revert_error.get()}</p> })
+            }}
+            <div class="table-responsive">
+                <table class="admin-table point-awards-log">
+                    <tbody>
+                        <tr>
+                            <th>Recipient</th>
+                            <th>Amount</th>
+                            <th>Reason</th>
+                            <th>Time</th>
+                            <th></th>
+                        </tr>
+                        <Suspense>
+                            {move || {
+                                point_awards_fetcher
+                                    .with(|maybe_result| match maybe_result {
+                                        Some(Ok(awards)) => {
+                                            awards
+                                                .iter()
+                                                .map(|award| {
+                                                    let award_id = award.id;
+                                                    let recipient = match (&award.guest_name, &award.house_name) {
+                                                        (Some(guest), Some(house)) => {
+                                                            format!("{} ({})", guest, house)
+                                                        }
+                                                        (Some(guest), None) => guest.clone(),
+                                                        (None, Some(house)) => house.clone(),
+                                                        (None, None) => "N/A".to_string(),
+                                                    };
+                                                    let revertible = award.reverts_award_id.is_none()
+                                                        && award.reverted_by.is_none();
+                                                    view! {
+                                                        <tr
+                                                            class:award-reverted=award.reverted_by.is_some()
+                                                            class:award-reversal=award
+                                                                .reverts_award_id
+                                                                .is_some()
+                                                        >
+                                                            <td>{recipient}</td>
+                                                            <td>{format!("{:+}", award.amount)}</td>
+                                                            <td>{award.reason.clone()}</td>
+                                                            <td>{award.awarded_at.format("%H:%M:%S").to_string()}</td>
+                                                            <td>
+                                                                {revertible
+                                                                    .then(|| {
+                                                                        view! {
+                                                                            <button
+                                                                                class="btn-danger"
+                                                                                on:click=move |_| revert(award_id)
+                                                                            >
+                                                                                "Revert"
+                                                                            </button>
+                                                                        }
+                                                                    })}
+                                                            </td>
+                                                        </tr>
                                                     }
-                                                }
-                                            >
-                                                {k}
-                                            </button>
+                                                })
+                                                .collect_view()
+                                                .into_any()
                                         }
+                                        _ => view! {}.into_view().into_any(),
                                     })
-                                    .collect::<Vec<_>>()}
-                                {move || {
-                                    if row_str == "ZXCVBNM" {
-                                        view! {
-                                            <button
-                                                class="special"
-                                                on:click=move |_| {
-                                                    if game_over.get() || guesses.get().len() >= 6 {
-                                                        return;
-                                                    }
-                                                    current_guess
-                                                        .update(|g| {
-                                                            if !g.is_empty() {
-                                                                g.pop();
-                                                            }
-                                                        });
-                                                }
-                                            >
-                                                "⌫"
-                                            </button>
-                                        }
-                                            .into_any()
-                                    } else {
-                                        view! { <div style="width: 60px;" /> }.into_any()
-                                    }
-                                }}
-                            </div>
-                        }
-                    })
-                    .collect::<Vec<_>>()}
+                            }}
+                        </Suspense>
+                    </tbody>
+                </table>
             </div>
+        </section>
+    }
+}
+
+/// Admin dashboard section for awarding (or deducting) points to a single guest, which also counts
+/// for their house.
+#[component]
+fn GuestAwardForm(active_guests_fetcher: Resource<Result<Vec<Guest>, AppError>>) -> impl IntoView {
+    let award_guest_id = RwSignal::new(0i32);
+    let award_guest_amount = RwSignal::new(0i32);
+    let award_reason = RwSignal::new(GUEST_AWARD_REASONS[0].to_string());
+    let custom_reason = RwSignal::new(String::new());
+    let award_guest_error = RwSignal::new(String::new());
+
+    let award_guest_submit = move |ev: SubmitEvent| {
+        ev.prevent_default();
+        let guest_id = award_guest_id.get();
+        let amount = award_guest_amount.get();
+        let reason = if award_reason.get().is_empty() {
+            custom_reason.get().trim().to_string()
+        } else {
+            award_reason.get()
+        };
+        if guest_id == 0 {
+            award_guest_error.set("Guest is required.".to_string());
+            return;
+        }
+        if amount == 0 {
+            award_guest_error.set("Amount cannot be zero.".to_string());
+            return;
+        }
+        if reason.is_empty() {
+            award_guest_error.set("Reason is required.".to_string());
+            return;
         }
+        spawn_local(async move {
+            match award_points_to_guest_handler(guest_id, amount, reason).await {
+                Ok(_) => {
+                    award_guest_error.set(String::new());
+                    award_guest_id.set(0i32);
+                    award_guest_amount.set(0i32);
+                    custom_reason.set(String::new());
+
+                    active_guests_fetcher.refetch();
+                }
+                Err(e) => award_guest_error.set(e.to_string()),
+            }
+        });
     };
 
     view! {
-        <div class="wordle">
-            <a class="back-link" href="/">
-                "← Home"
-            </a>
-            <h1>"Wordle"</h1>
-            <div class="grid">{grid}</div>
-            <p>{move || message.get()}</p>
-            {keyboard}
-        </div>
+        <section class="admin-section centered">
+            <h2>"Award Points to Guest"</h2>
+            <form class="admin-form award-form" on:submit=award_guest_submit>
+                <div class="form-group">
+                    <label>
+                        <span class="label-text">"Guest: "</span>
+                        <select
+                            class="form-select"
+                            prop:value=move || award_guest_id.get().to_string()
+                            on:change=move |ev| {
+                                award_guest_id.set(event_target_value(&ev).parse().unwrap_or(0))
+                            }
+                        >
+                            <option value="0">"Select guest"</option>
+                            <Suspense fallback=|| view! { <option>"Loading..."</option> }>
+                                {move || {
+                                    active_guests_fetcher
+                                        .with(|maybe_result| match maybe_result {
+                                            Some(Ok(guests)) => {
+                                                guests
+                                                    .iter()
+                                                    .map(|guest| {
+                                                        view! {
+                                                            <option value=guest
+                                                                .id
+                                                                .to_string()>{guest.name.clone()}</option>
+                                                        }
+                                                    })
+                                                    .collect_view()
+                                                    .into_any()
+                                            }
+                                            _ => view! { <option>"Error"</option> }.into_any(),
+                                        })
+                                }}
+                            </Suspense>
+                        </select>
+                    </label>
+                </div>
+                <div class="form-group">
+                    <label>
+                        <span class="label-text">"Amount: "</span>
+                        <input
+                            class="form-input"
+                            type="number"
+                            prop:value=move || format!("{}", award_guest_amount.get())
+                            on:input=move |ev| {
+                                if let Ok(value) = event_target_value(&ev).parse::<i32>() {
+                                    award_guest_amount.set(value);
+                                }
+                            }
+                        />
+                    </label>
+                </div>
+                <div class="form-group">
+                    <label>
+                        <span class="label-text">"Reason: "</span>
+                        <select
+                            class="form-select"
+                            prop:value=move || award_reason.get()
+                            on:change=move |ev| award_reason.set(event_target_value(&ev))
+                        >
+                            {GUEST_AWARD_REASONS
+                                .iter()
+                                .map(|&reason| view! { <option value=reason>{reason}</option> })
+                                .collect_view()}
+                            <option value="">"Other..."</option>
+                        </select>
+                    </label>
+                </div>
+                {move || {
+                    award_reason
+                        .get()
+                        .is_empty()
+                        .then(|| {
+                            view! {
+                                <div class="form-group">
+                                    <input
+                                        class="form-input"
+                                        type="text"
+                                        placeholder="e.g., Best Patronus impression"
+                                        prop:value=move || custom_reason.get()
+                                        on:input=move |ev| custom_reason.set(event_target_value(&ev))
+                                    />
+                                </div>
+                            }
+                        })
+                }}
+                <button type="submit" class="btn-primary">
+                    "Award Points"
+                </button>
+            </form>
+            {move || {
+                (!award_guest_error.get().is_empty())
+                    .then(|| view! { <p class="error">{award_guest_error.get()}</p> })
+            }}
+        </section>
     }
 }
 
-fn compute_statuses(guess: &str, target: &str) -> Vec<LetterStatus> {
-    let mut statuses = vec![LetterStatus::Absent; 5];
-    let mut target_counts: HashMap<char, usize> = HashMap::new();
-    for c in target.chars() {
-        *target_counts.entry(c).or_insert(0) += 1;
-    }
-
-    // First pass: Correct positions.
-    for (i, c) in guess.chars().enumerate() {
-        if target.chars().nth(i) == Some(c) {
-            statuses[i] = LetterStatus::Correct;
-            *target_counts.entry(c).or_insert(0) -= 1;
-        }
-    }
-
-    // Second pass: Present but wrong position.
-    for (i, c) in guess.chars().enumerate() {
-        if statuses[i] != LetterStatus::Correct && target_counts.get(&c).unwrap_or(&0) > &0 {
-            statuses[i] = LetterStatus::Present;
-            *target_counts.entry(c).or_insert(0) -= 1;
-        }
+/// Home page section showing the logged-in guest's personal score and the points they were given.
+#[component]
+fn GuestPoints(
+    current_user_fetcher: Resource<Result<Option<Guest>, AppError>>,
+    my_awards_fetcher: Resource<Result<Vec<PointAwardLog>, AppError>>,
+) -> impl IntoView {
+    view! {
+        <Suspense>
+            {move || {
+                current_user_fetcher
+                    .get()
+                    .and_then(|res| res.ok())
+                    .flatten()
+                    .map(|guest| {
+                        let awards = my_awards_fetcher
+                            .get()
+                            .and_then(|res| res.ok())
+                            .unwrap_or_default();
+                        view! {
+                            <section class="home-section centered guest-points">
+                                <h3>"Your Points"</h3>
+                                <p class="personal-score">{guest.personal_score}</p>
+                                {if awards.is_empty() {
+                                    view! { <p>"No points yet. Go earn some for your house!"</p> }
+                                        .into_any()
+                                } else {
+                                    view! {
+                                        <ul>
+                                            {awards
+                                                .into_iter()
+                                                .map(|award| {
+                                                    view! {
+                                                        <li>
+                                                            <span class="award-amount">
+                                                                {format!("{:+}", award.amount)}
+                                                            </span>
+                                                            " "
+                                                            {award.reason}
+                                                        </li>
+                                                    }
+                                                })
+                                                .collect_view()}
+                                        </ul>
+                                    }
+                                        .into_any()
+                                }}
+                            </section>
+                        }
+                    })
+            }}
+        </Suspense>
     }
-
-    statuses
 }
 
-fn process_guess(
-    guess: String,
-    target: String,
-    guesses: RwSignal<Vec<String>>,
-    current_guess: RwSignal<String>,
-    keyboard_status: RwSignal<HashMap<char, LetterStatus>>,
-    game_over: RwSignal<bool>,
-    message: RwSignal<String>,
-) {
-    guesses.update(|gs| gs.push(guess.clone()));
-    current_guess.set(String::new());
+/// Admin dashboard section listing recent Sorting Hat deliveries, with a button to send any of them
+/// again.
+#[component]
+fn SortingHatDeliveries(
+    sorting_hat_fetcher: Resource<Result<SortingHatOverview, AppError>>,
+) -> impl IntoView {
+    let reflash_error = RwSignal::new(String::new());
 
-    // Update keyboard statuses.
-    let statuses = compute_statuses(&guess, &target);
-    keyboard_status.update(|ks| {
-        for (i, c) in guess.chars().enumerate() {
-            let new_status = statuses[i];
-            let current = ks.get(&c).cloned().unwrap_or(LetterStatus::Unused);
-            // Priority: Current > Present > Absent.
-            let updated = match (current, new_status) {
-                (_, LetterStatus::Correct) => LetterStatus::Correct,
-                (LetterStatus::Unused, LetterStatus::Present) => LetterStatus::Present,
-                (LetterStatus::Absent, LetterStatus::Present) => LetterStatus::Present,
-                (_, LetterStatus::Absent)
-                    if current != LetterStatus::Correct && current != LetterStatus::Present =>
-                {
-                    LetterStatus::Absent
+    let reflash = move |delivery_id: i32| {
+        spawn_local(async move {
+            match reflash_sorting_hat_handler(delivery_id).await {
+                Ok(()) => {
+                    reflash_error.set(String::new());
+                    sorting_hat_fetcher.refetch();
                 }
-                _ => current,
-            };
-            ks.insert(c, updated);
-        }
-    });
+                Err(e) => reflash_error.set(e.to_string()),
+            }
+        });
+    };
 
-    // Check win/loss.
-    if guess == target {
-        game_over.set(true);
-        message.set("You win!".to_string());
-    } else if guesses.get().len() >= 6 {
-        game_over.set(true);
-        message.set(format!("Game over! The word was {}", target));
+    view! {
+        <section class="admin-section centered">
+            <h2>"Sorting Hat"</h2>
+            <button class="btn-secondary" on:click=move |_| sorting_hat_fetcher.refetch()>
+                "Refresh"
+            </button>
+            {move || {
+                (!reflash_error.get().is_empty())
+                    .then(|| view! { <p class="error">{reflash_error.get()}</p> })
+            }}
+            <Suspense fallback=|| view! { <p>"Loading deliveries..."</p> }>
+                {move || {
+                    sorting_hat_fetcher
+                        .get()
+                        .map(|result| match result {
+                            Ok(overview) => {
+                                let status = overview
+                                    .endpoint
+                                    .unwrap_or_else(|| "not configured".to_string());
+                                view! {
+                                    <p class="sorting-hat-status">"Endpoint: " {status}</p>
+                                    <div class="table-responsive">
+                                        <table class="admin-table sorting-hat-log">
+                                            <thead>
+                                                <tr>
+                                                    <th>"Guest"</th>
+                                                    <th>"House"</th>
+                                                    <th>"Status"</th>
+                                                    <th>"Attempts"</th>
+                                                    <th>"Last Error"</th>
+                                                    <th>"Updated"</th>
+                                                    <th></th>
+                                                </tr>
+                                            </thead>
+                                            <tbody>
+                                                {overview
+                                                    .deliveries
+                                                    .into_iter()
+                                                    .map(|delivery| {
+                                                        let delivery_id = delivery.id;
+                                                        view! {
+                                                            <tr>
+                                                                <td>
+                                                                    {delivery.guest_name.unwrap_or_else(|| "-".to_string())}
+                                                                </td>
+                                                                <td>{delivery.house_name}</td>
+                                                                <td class=format!(
+                                                                    "delivery-{}",
+                                                                    delivery.status,
+                                                                )>{delivery.status.clone()}</td>
+                                                                <td>{delivery.attempts}</td>
+                                                                <td>{delivery.last_error.unwrap_or_default()}</td>
+                                                                <td>{delivery.updated_at.format("%H:%M:%S").to_string()}</td>
+                                                                <td>
+                                                                    <button
+                                                                        class="btn-secondary"
+                                                                        on:click=move |_| reflash(delivery_id)
+                                                                    >
+                                                                        "Re-flash"
+                                                                    </button>
+                                                                </td>
+                                                            </tr>
+                                                        }
+                                                    })
+                                                    .collect_view()}
+                                            </tbody>
+                                        </table>
+                                    </div>
+                                }
+                                    .into_any()
+                            }
+                            Err(e) => view! { <p class="error">{e.to_string()}</p> }.into_any(),
+                        })
+                }}
+            </Suspense>
+        </section>
     }
 }
 
+/// House names and scores. Scores that differ from `previous_scores` (keyed by house id) count up
+/// or down to their new value.
 #[component]
-fn HouseScores(houses: Vec<House>) -> impl IntoView {
+fn HouseScores(
+    houses: Vec<House>,
+    #[prop(optional)] previous_scores: HashMap<i32, i32>,
+) -> impl IntoView {
     view! {
         <section class="house-scores centered">
             <h2>"House Scores"</h2>
@@ -2288,31 +4837,28 @@ fn HouseScores(houses: Vec<House>) -> impl IntoView {
                 {houses
                     .iter()
                     .map(|house| {
-                        let bg_class = format!(
-                            "house-box {}",
-                            match house.id {
-                                1 => "gryffindor-bg",
-                                2 => "hufflepuff-bg",
-                                3 => "ravenclaw-bg",
-                                4 => "slytherin-bg",
-                                _ => "",
-                            },
-                        );
-                        let text_color_class = match house.id {
-                            1 => "gryffindor-text",
-                            2 => "hufflepuff-text",
-                            3 => "ravenclaw-text",
-                            4 => "slytherin-text",
-                            _ => "",
-                        };
                         let display_name = house.name.to_uppercase();
+                        let previous_score = previous_scores
+                            .get(&house.id)
+                            .copied()
+                            .filter(|&score| score != house.score);
                         view! {
-                            <div class="score-row">
-                                <div class=bg_class>
-                                    <span class=text_color_class>{display_name}</span>
+                            <div class="score-row" class:score-changed=previous_score.is_some()>
+                                <div
+                                    class=format!("house-box house-{}", house.slug)
+                                    style=house.color_style()
+                                >
+                                    {house
+                                        .crest
+                                        .clone()
+                                        .map(|crest| view! { <img class="house-crest" src=crest alt="" /> })}
+                                    <span class="house-text">{display_name}</span>
                                 </div>
                                 <div class="score-display">
-                                    <span class="score-number">{house.score}</span>
+                                    <AnimatedScore
+                                        from=previous_score.unwrap_or(house.score)
+                                        to=house.score
+                                    />
                                 </div>
                             </div>
                         }
@@ -2323,141 +4869,889 @@ fn HouseScores(houses: Vec<House>) -> impl IntoView {
     }
 }
 
+/// A score that counts from `from` to `to` in about a second once the page is interactive.
+#[component]
+fn AnimatedScore(from: i32, to: i32) -> impl IntoView {
+    let shown = RwSignal::new(from);
+    let interval = StoredValue::new(None::<IntervalHandle>);
+
+    // Effects only run in the browser, so the server renders the starting value.
+    Effect::new(move |_| {
+        if from == to {
+            return;
+        }
+        let step = ((to - from).abs() / 20).max(1);
+        let tick = move || {
+            let next = if to > from {
+                (shown.get_untracked() + step).min(to)
+            } else {
+                (shown.get_untracked() - step).max(to)
+            };
+            shown.set(next);
+            if next == to {
+                if let Some(handle) = interval.get_value() {
+                    handle.clear();
+                }
+            }
+        };
+        if let Ok(handle) = set_interval_with_handle(tick, Duration::from_millis(50)) {
+            interval.set_value(Some(handle));
+        }
+    });
+    on_cleanup(move || {
+        if let Some(handle) = interval.get_value() {
+            handle.clear();
+        }
+    });
+
+    view! { <span class="score-number">{shown}</span> }
+}
+
+/// Full-screen scoreboard for the projector: house scores, crossword progress, best-dressed voting
+/// turnout and a ticker of the latest point awards. Needs no login, follows live events and also
+/// refreshes every 30 seconds in case the event stream drops.
+#[component]
+fn ScoreboardPage() -> impl IntoView {
+    let scoreboard_fetcher = Resource::new(|| (), |_| get_scoreboard());
+    // Scores as last shown, so changed ones can be animated.
+    let last_scores = StoredValue::new(HashMap::<i32, i32>::new());
+
+    use_live_events(move |event| {
+        if !matches!(event, LiveEvent::GuestsChanged) {
+            scoreboard_fetcher.refetch();
+        }
+    });
+    let refresh = StoredValue::new(None::<IntervalHandle>);
+    Effect::new(move |_| {
+        if let Ok(handle) = set_interval_with_handle(
+            move || scoreboard_fetcher.refetch(),
+            Duration::from_secs(30),
+        ) {
+            refresh.set_value(Some(handle));
+        }
+    });
+    on_cleanup(move || {
+        if let Some(handle) = refresh.get_value() {
+            handle.clear();
+        }
+    });
+
+    view! {
+        <Title text="Scoreboard" />
+        <div class="scoreboard">
+            <Transition fallback=|| view! { <p>"Summoning the scores..."</p> }>
+                {move || {
+                    scoreboard_fetcher
+                        .get()
+                        .map(|result| match result {
+                            Ok(board) => {
+                                let previous_scores = last_scores.get_value();
+                                last_scores
+                                    .set_value(
+                                        board.houses.iter().map(|house| (house.id, house.score)).collect(),
+                                    );
+                                view! {
+                                    <HouseScores
+                                        houses=board.houses.clone()
+                                        previous_scores=previous_scores
+                                    />
+                                    <div class="scoreboard-panels">
+                                        <ScoreboardCrossword
+                                            houses=board.houses.clone()
+                                            progress=board.crossword_progress.clone()
+                                        />
+                                        <section class="scoreboard-voting centered">
+                                            <h2>"Best Dressed"</h2>
+                                            <p>
+                                                {if board.voting_open {
+                                                    "Voting is open!"
+                                                } else {
+                                                    "Voting is closed."
+                                                }}
+                                            </p>
+                                            <progress
+                                                max=board.eligible_voters.max(1)
+                                                value=board.votes_cast
+                                            ></progress>
+                                            <p class="turnout">
+                                                {board.votes_cast} " of " {board.eligible_voters}
+                                                " guests have voted"
+                                            </p>
+                                        </section>
+                                    </div>
+                                    <ScoreboardTicker awards=board.recent_awards />
+                                }
+                                    .into_any()
+                            }
+                            Err(e) => view! { <p class="error">{e.to_string()}</p> }.into_any(),
+                        })
+                }}
+            </Transition>
+        </div>
+    }
+}
+
+/// Number of crossword words each house has solved, one marker per word.
+#[component]
+fn ScoreboardCrossword(houses: Vec<House>, progress: Vec<Vec<bool>>) -> impl IntoView {
+    view! {
+        <section class="scoreboard-crossword centered">
+            <h2>"Horcrux Hunt"</h2>
+            {houses
+                .into_iter()
+                .zip(progress)
+                .map(|(house, words)| {
+                    let solved = words.iter().filter(|&&done| done).count();
+                    let total = words.len();
+                    let style = house.color_style();
+                    view! {
+                        <div class="crossword-row" style=style>
+                            <span class="crossword-house">{house.name}</span>
+                            <span class="crossword-words">
+                                {words
+                                    .into_iter()
+                                    .map(|done| view! { <span class="word-marker" class:solved=done></span> })
+                                    .collect_view()}
+                            </span>
+                            <span class="crossword-count">{solved} "/" {total}</span>
+                        </div>
+                    }
+                })
+                .collect_view()}
+        </section>
+    }
+}
+
+/// Latest point awards, scrolling past along the bottom of the screen.
+#[component]
+fn ScoreboardTicker(awards: Vec<PointAwardLog>) -> impl IntoView {
+    let items = awards
+        .into_iter()
+        .map(|award| {
+            let recipient = award
+                .guest_name
+                .or(award.house_name)
+                .unwrap_or_else(|| "Unknown".to_string());
+            format!("{:+} {} - {}", award.amount, recipient, award.reason)
+        })
+        .collect::<Vec<_>>();
+    // The track holds the items twice so the scroll can loop without a gap.
+    let track = items
+        .iter()
+        .chain(items.iter())
+        .cloned()
+        .collect::<Vec<_>>();
+
+    view! {
+        <div class="scoreboard-ticker">
+            {if items.is_empty() {
+                view! { <p class="ticker-empty">"No points awarded yet."</p> }.into_any()
+            } else {
+                view! {
+                    <div class="ticker-track">
+                        {track
+                            .into_iter()
+                            .map(|item| view! { <span class="ticker-item">{item}</span> })
+                            .collect_view()}
+                    </div>
+                }
+                    .into_any()
+            }}
+        </div>
+    }
+}
+
+// A solving time as minutes and seconds (or hours, minutes and seconds), e.g. "12:05".
+fn format_elapsed(seconds: Option<i32>) -> String {
+    let Some(seconds) = seconds else {
+        return "-".to_string();
+    };
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+/// Post-party leaderboard of each house's fastest crossword solvers.
+#[component]
+fn LeaderboardPage() -> impl IntoView {
+    let leaderboard_fetcher = Resource::new(|| (), |_| get_crossword_leaderboard_handler());
+
+    use_live_events(move |event| {
+        if event.changes_crossword() {
+            leaderboard_fetcher.refetch();
+        }
+    });
+
+    view! {
+        <Title text="Horcrux Hunt Leaderboard" />
+        <div class="leaderboard">
+            <a class="back-link" href="/">
+                "← Home"
+            </a>
+            <h1>"Horcrux Hunt Leaderboard"</h1>
+            <p class="leaderboard-note">
+                "Most words solved first, then the quickest from opening the crossword to the last word."
+            </p>
+            <Transition fallback=|| view! { <p>"Counting the words..."</p> }>
+                {move || {
+                    leaderboard_fetcher
+                        .get()
+                        .map(|result| match result {
+                            Ok(leaderboard) => {
+                                leaderboard
+                                    .into_iter()
+                                    .map(|entry| {
+                                        let style = entry.house.color_style();
+                                        view! {
+                                            <section class="leaderboard-house" style=style>
+                                                <h2>{entry.house.name}</h2>
+                                                {if entry.solvers.is_empty() {
+                                                    view! { <p class="leaderboard-empty">"No words solved."</p> }
+                                                        .into_any()
+                                                } else {
+                                                    view! {
+                                                        <ol>
+                                                            {entry
+                                                                .solvers
+                                                                .into_iter()
+                                                                .map(|solver| {
+                                                                    let words = if solver.words_solved == 1 {
+                                                                        "word"
+                                                                    } else {
+                                                                        "words"
+                                                                    };
+                                                                    view! {
+                                                                        <li>
+                                                                            <span class="solver-name">{solver.guest_name}</span>
+                                                                            <span class="solver-words">
+                                                                                {format!(
+                                                                                    "{} {} ({} first)",
+                                                                                    solver.words_solved,
+                                                                                    words,
+                                                                                    solver.first_solves,
+                                                                                )}
+                                                                            </span>
+                                                                            <span class="solver-time">
+                                                                                {format_elapsed(solver.elapsed_seconds)}
+                                                                            </span>
+                                                                        </li>
+                                                                    }
+                                                                })
+                                                                .collect_view()}
+                                                        </ol>
+                                                    }
+                                                        .into_any()
+                                                }}
+                                            </section>
+                                        }
+                                    })
+                                    .collect_view()
+                                    .into_any()
+                            }
+                            Err(e) => {
+                                view! { <p class="error">{format!("Error: {}", e)}</p> }.into_any()
+                            }
+                        })
+                }}
+            </Transition>
+        </div>
+    }
+}
+
+// How long typing on the crossword must pause before the letters are saved.
+const CROSSWORD_SAVE_DELAY: Duration = Duration::from_millis(400);
+
+// Id of a crossword cell's input, for moving the keyboard focus to it.
+fn crossword_cell_id(row: usize, col: usize) -> String {
+    format!("crossword-cell-{}-{}", row, col)
+}
+
+// Focuses a crossword cell once the grid has rendered, selecting its letter so typing replaces it.
+fn focus_crossword_cell(row: usize, col: usize) {
+    #[cfg(feature = "hydrate")]
+    request_animation_frame(move || {
+        if let Some(input) = document()
+            .get_element_by_id(&crossword_cell_id(row, col))
+            .and_then(|element| element.dyn_into::<web_sys::HtmlInputElement>().ok())
+        {
+            let _ = input.focus();
+            input.select();
+        }
+    });
+    #[cfg(not(feature = "hydrate"))]
+    let _ = (row, col);
+}
+
 #[component]
 fn Crossword() -> impl IntoView {
+    let layout_fetcher = Resource::new(|| (), |_| get_crossword_layout());
     let state_fetcher = Resource::new(|| (), |_| get_crossword_state());
-    let grid = RwSignal::new(vec![vec![None::<char>; 12]; 15]);
-    let completions = RwSignal::new([false; 7]);
-    let horcrux_clues: RwSignal<Vec<String>> = RwSignal::new(vec![]);
+    let puzzle = RwSignal::new(None::<PuzzleLayout>);
+    let grid = RwSignal::new(Vec::<Vec<Option<char>>>::new());
+    let completions = RwSignal::new(Vec::<bool>::new());
+    let horcrux_clues: RwSignal<Vec<Option<String>>> = RwSignal::new(vec![]);
     let show_rules_modal = RwSignal::new(false);
+    // Set in house board mode: the house and who last wrote each cell.
+    let board = RwSignal::new(None::<HouseBoard>);
+    // Cells whose last edit lost to a teammate's on a house board, or to another device's.
+    let conflicts = RwSignal::new(Vec::<(usize, usize)>::new());
+    // Letters typed but not yet saved, and those being saved, as (row, col, letter).
+    let unsaved = StoredValue::new(Vec::<(usize, usize, Option<char>)>::new());
+    let saving = StoredValue::new(Vec::<(usize, usize, Option<char>)>::new());
+    let save_timer = StoredValue::new(None::<TimeoutHandle>);
+    // Version of our own crossword as the server last sent it.
+    let version = StoredValue::new(0);
+    let hint_status_fetcher = Resource::new(|| (), |_| get_hint_status());
+    // The house's horcrux claims, by word.
+    let claims_fetcher = Resource::new(|| (), |_| get_horcrux_claims_handler());
+    let horcrux_claims = RwSignal::new(Vec::<Option<ClaimStatus>>::new());
+    let chosen_hint_word = RwSignal::new(None::<usize>);
+    let hint_error = RwSignal::new(String::new());
+    // The cell being typed in, and the way typing advances.
+    let active_cell = RwSignal::new(None::<(usize, usize)>);
+    let typing_direction = RwSignal::new(Direction::Across);
+
+    // The server decides which words are solved, and only then tells us what they reveal. Its
+    // letters (a house board's include the teammates') replace ours, apart from those still being
+    // saved.
+    let apply_crossword = move |crossword: GuestCrossword| {
+        grid.update(|grid| {
+            grid.iter_mut().for_each(|row| row.fill(None));
+            let typed = saving.get_value().into_iter().chain(unsaved.get_value());
+            let letters = crossword
+                .filled
+                .iter()
+                .map(|&(r, c, ch)| (r, c, Some(ch)))
+                .chain(typed);
+            for (r, c, letter) in letters {
+                if let Some(cell) = grid.get_mut(r).and_then(|row| row.get_mut(c)) {
+                    *cell = letter;
+                }
+            }
+        });
+        completions.set(crossword.completions);
+        horcrux_clues.set(crossword.reveals);
+        board.set(crossword.board);
+        version.set_value(crossword.version);
+    };
+
+    // Teammates' letters on a house board, and their hints.
+    use_live_events(move |event| {
+        let house_id = board.with_untracked(|board| board.as_ref().map(|board| board.house_id));
+        if house_id.is_some_and(|house_id| event.changes_house_board(house_id)) {
+            state_fetcher.refetch();
+        }
+        if event.changes_scores() {
+            hint_status_fetcher.refetch();
+        }
+        if event.changes_horcrux_claims() {
+            claims_fetcher.refetch();
+        }
+    });
 
-    // On mount/load, sync state to signals.
     Effect::new(move |_| {
-        if let Some(Ok(state)) = state_fetcher.get() {
-            grid.set(state.grid);
-            completions.set(state.completions);
-            horcrux_clues.set(
-                CROSSWORD_DEFS
-                    .iter()
-                    .map(|w| w.reveal_text.to_string())
-                    .collect(),
-            );
+        if let Some(Ok(claims)) = claims_fetcher.get() {
+            horcrux_claims.set(claims);
         }
     });
 
-    // Handler for cell input: update grid, check affected words reactively.
-    let on_cell_change = move |row: usize, col: usize, new_char: Option<char>| {
+    // The word a hint is for: the chosen one while it is unsolved, else the first unsolved word.
+    let hint_word = move || {
+        completions.with(|completions| {
+            let unsolved = |i: &usize| !completions.get(*i).copied().unwrap_or(false);
+            let word_count = puzzle.with(|p| p.as_ref().map_or(0, |p| p.words.len()));
+            chosen_hint_word
+                .get()
+                .filter(unsolved)
+                .or_else(|| (0..word_count).find(unsolved))
+        })
+    };
+
+    let take_hint = move |kind: HintKind, cost: i32| {
+        let Some(word_index) = hint_word() else {
+            return;
+        };
+        let what = match kind {
+            HintKind::Letter => "a letter",
+            HintKind::Word => "the whole word",
+        };
+        let label = puzzle.with_untracked(|p| {
+            p.as_ref()
+                .map(|p| p.clue_label(word_index))
+                .unwrap_or_default()
+        });
+        if !leptos::leptos_dom::helpers::window()
+            .confirm_with_message(&format!(
+                "Reveal {} of {} for {} house points?",
+                what, label, cost
+            ))
+            .unwrap_or(false)
+        {
+            return;
+        }
         spawn_local(async move {
-            grid.update(|grid| {
-                grid[row][col] = new_char;
-            });
+            match take_crossword_hint_handler(word_index, kind).await {
+                Ok(crossword) => {
+                    hint_error.set(String::new());
+                    apply_crossword(crossword);
+                }
+                Err(e) => hint_error.set(e.to_string()),
+            }
+            hint_status_fetcher.refetch();
+        });
+    };
+
+    // On mount/load, sync layout and state to signals.
+    Effect::new(move |_| {
+        if let (Some(Ok(layout)), Some(Ok(crossword))) = (layout_fetcher.get(), state_fetcher.get())
+        {
+            let mut full_grid = vec![vec![None; layout.cols]; layout.rows];
+            for &(r, c, ch) in &crossword.filled {
+                if r < layout.rows && c < layout.cols {
+                    full_grid[r][c] = Some(ch);
+                }
+            }
+            grid.set(full_grid);
+            apply_crossword(crossword);
+            puzzle.set(Some(layout));
+        }
+    });
+
+    // Sends typed letters to the server: on a house board with the version of each cell we last
+    // saw, else with the version of our own crossword we last saw.
+    let send_edits = move |edits: Vec<(usize, usize, Option<char>)>| {
+        let board_edits = board.with_untracked(|board| {
+            board.as_ref().map(|board| {
+                edits
+                    .iter()
+                    .map(|&(row, col, letter)| BoardEdit {
+                        row,
+                        col,
+                        letter,
+                        version: board
+                            .cells
+                            .iter()
+                            .find(|cell| (cell.row, cell.col) == (row, col))
+                            .map_or(0, |cell| cell.version),
+                    })
+                    .collect::<Vec<_>>()
+            })
+        });
+        let cell_edits = edits
+            .into_iter()
+            .map(|(row, col, letter)| CellEdit { row, col, letter })
+            .collect::<Vec<_>>();
+        let version = version.get_value();
+        async move {
+            match board_edits {
+                Some(board_edits) => edit_house_board_handler(board_edits).await,
+                None => edit_crossword_handler(version, cell_edits).await,
+            }
+        }
+    };
 
-            let mut new_completions = completions.get_untracked();
-            let current_grid = grid.get_untracked();
-            for (word_idx, word_def) in CROSSWORD_DEFS.iter().enumerate() {
-                if !new_completions[word_idx] && cell_is_in_word(word_def, row, col) {
-                    if word_is_complete(&grid.get_untracked(), word_def) {
-                        new_completions[word_idx] = true;
+    // Saves the unsaved letters, one batch at a time; letters typed while a batch is on its way go
+    // in the next.
+    let save = move || {
+        if !saving.with_value(Vec::is_empty) {
+            return;
+        }
+        spawn_local(async move {
+            loop {
+                let edits = unsaved.get_value();
+                if edits.is_empty() {
+                    break;
+                }
+                unsaved.set_value(Vec::new());
+                saving.set_value(edits.clone());
+                let result = send_edits(edits).await;
+                saving.set_value(Vec::new());
+                match result {
+                    Ok(result) => {
+                        apply_crossword(result.crossword);
+                        conflicts.set(result.rejected);
                     }
+                    Err(e) => log!("Failed to save crossword: {}", e),
                 }
             }
-            completions.set(new_completions);
-
-            // Create a CrosswordState containing the full grid, then sparsify it and send it to
-            // the server function.
-            //
-            // The reason for doing this is because sending the full grid with
-            // Vec<Vec<Option<char>> seems to drop all None values, collapsing the grid and causing
-            // loss of positional information.
-            let full_state = CrosswordState::new_full_grid(current_grid.clone(), new_completions);
-            let sparse_state = SparseState {
-                filled: full_state.sparse.filled,
-                completions: new_completions,
+        });
+    };
+
+    // Handler for cell input: show the letter at once, and save it once typing pauses.
+    let on_cell_change = move |row: usize, col: usize, new_char: Option<char>| {
+        grid.update(|grid| {
+            grid[row][col] = new_char;
+        });
+        unsaved.update_value(|unsaved| {
+            unsaved.retain(|&(r, c, _)| (r, c) != (row, col));
+            unsaved.push((row, col, new_char));
+        });
+        if let Some(timer) = save_timer.get_value() {
+            timer.clear();
+        }
+        save_timer.set_value(set_timeout_with_handle(save, CROSSWORD_SAVE_DELAY).ok());
+    };
+
+    // Letters typed just before leaving the page are sent off without waiting.
+    on_cleanup(move || {
+        if let Some(timer) = save_timer.get_value() {
+            timer.clear();
+        }
+        let edits = unsaved.get_value();
+        if !edits.is_empty() {
+            let request = send_edits(edits);
+            spawn_local(async move {
+                if let Err(e) = request.await {
+                    log!("Failed to save crossword: {}", e);
+                }
+            });
+        }
+    });
+
+    // Which cells belong to solved words; those show their letter and can't be edited.
+    let frozen = Memo::new(move |_| {
+        puzzle.with(|p| {
+            let Some(p) = p.as_ref() else {
+                return vec![];
             };
+            completions.with(|completions| {
+                (0..p.rows)
+                    .map(|row| {
+                        (0..p.cols)
+                            .map(|col| {
+                                completions
+                                    .iter()
+                                    .zip(&p.words)
+                                    .any(|(&c, word)| c && word.contains(row, col))
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .collect::<Vec<_>>()
+            })
+        })
+    });
+    let is_open =
+        move |(row, col): (usize, usize)| !frozen.with_untracked(|frozen| frozen[row][col]);
+
+    // The word being typed: the one through the active cell, preferring the typing direction.
+    let active_word = move || {
+        let (row, col) = active_cell.get()?;
+        let direction = typing_direction.get();
+        puzzle.with(|p| p.as_ref()?.word_at(row, col, direction))
+    };
+
+    let move_to = move |(row, col): (usize, usize)| {
+        active_cell.set(Some((row, col)));
+        focus_crossword_cell(row, col);
+    };
+
+    // Arrow keys go to the nearest open cell that way, and turn typing that way.
+    let arrow = move |row: usize, col: usize, direction: Direction, forward: bool| {
+        typing_direction.set(direction);
+        let next = puzzle.with_untracked(|p| {
+            let p = p.as_ref()?;
+            std::iter::successors(p.next_word_cell(row, col, direction, forward), |&(r, c)| {
+                p.next_word_cell(r, c, direction, forward)
+            })
+            .find(|&cell| is_open(cell))
+        });
+        if let Some(cell) = next {
+            move_to(cell);
+        }
+    };
+
+    // The next (or previous) open cell of the active word, for typing and backspace.
+    let step_in_word = move |row: usize, col: usize, forward: bool| {
+        puzzle.with_untracked(|p| {
+            let p = p.as_ref()?;
+            let word = &p.words[p.word_at(row, col, typing_direction.get_untracked())?];
+            let cells: Vec<(usize, usize)> = word.cells().collect();
+            let pos = cells.iter().position(|&cell| cell == (row, col))?;
+            if forward {
+                cells[pos + 1..].iter().copied().find(|&cell| is_open(cell))
+            } else {
+                cells[..pos]
+                    .iter()
+                    .rev()
+                    .copied()
+                    .find(|&cell| is_open(cell))
+            }
+        })
+    };
 
-            let _ = update_crossword_state_handler(sparse_state).await;
+    // Starts typing a word from its first empty cell (or its first open cell).
+    let select_word = move |word_index: usize| {
+        let start = puzzle.with_untracked(|p| {
+            let word = p.as_ref()?.words.get(word_index)?;
+            typing_direction.set(word.direction);
+            let open: Vec<(usize, usize)> = word.cells().filter(|&cell| is_open(cell)).collect();
+            grid.with_untracked(|grid| {
+                open.iter()
+                    .copied()
+                    .find(|&(r, c)| grid[r][c].is_none())
+                    .or(open.first().copied())
+            })
         });
+        if let Some(cell) = start {
+            move_to(cell);
+        }
     };
 
-    // Render grid: 15 rows, 12 cols; show input only for word cells, else blend to background.
+    // Solving a word re-renders the grid, which loses the focus.
+    Effect::new(move |_| {
+        frozen.track();
+        if let Some((row, col)) = active_cell.get_untracked() {
+            focus_crossword_cell(row, col);
+        }
+    });
+
+    // Render the puzzle's grid; show input only for word cells, else blend to background. Cells
+    // where words start carry the clue number.
     let grid_view = move || {
-        (0..15).map(|row| view! {
-            <div class="crossword-row">
-                {(0..12)
-                    .map(move |col| {
-                        let cell_content = grid.get()[row][col];
-                        let is_input_cell = CROSSWORD_DEFS
-                            .iter()
-                            .any(|w| cell_is_in_word(w, row, col));
-                        let is_frozen = completions
-                            .get()
-                            .iter()
-                            .enumerate()
-                            .any(|(i, &c)| c && cell_is_in_word(&CROSSWORD_DEFS[i], row, col));
-                        let class = if is_input_cell {
-                            "crossword-cell"
-                        } else {
-                            "crossword-blank"
+        let Some(layout) = puzzle.get() else {
+            return vec![];
+        };
+        let numbers: HashMap<(usize, usize), usize> = layout
+            .words
+            .iter()
+            .zip(layout.clue_numbers())
+            .map(|(word, number)| ((word.row, word.col), number))
+            .collect();
+        (0..layout.rows)
+            .map(|row| {
+                let cells = (0..layout.cols)
+                    .map(|col| {
+                        let is_input_cell = layout.is_word_cell(row, col);
+                        let is_frozen = frozen.with(|frozen| frozen[row][col]);
+                        let number = numbers.get(&(row, col)).copied();
+                        let letter = move || {
+                            grid.with(|grid| {
+                                grid.get(row)
+                                    .and_then(|cells| cells.get(col))
+                                    .copied()
+                                    .flatten()
+                                    .map(String::from)
+                                    .unwrap_or_default()
+                            })
+                        };
+                        let class = move || {
+                            let mut class = String::from(if is_input_cell {
+                                "crossword-cell"
+                            } else {
+                                "crossword-blank"
+                            });
+                            if is_frozen {
+                                class.push_str(" frozen");
+                            }
+                            if active_cell.get() == Some((row, col)) {
+                                class.push_str(" active");
+                            } else if active_word().is_some_and(|i| {
+                                puzzle.with(|p| p.as_ref().is_some_and(|p| p.words[i].contains(row, col)))
+                            }) {
+                                class.push_str(" highlighted");
+                            }
+                            if conflicts.with(|conflicts| conflicts.contains(&(row, col))) {
+                                class.push_str(" conflict");
+                            }
+                            class
+                        };
+                        // On a house board, hovering a letter shows who wrote it.
+                        let title = move || {
+                            let filled = grid.with(|grid| grid[row][col].is_some());
+                            board.with(|board| {
+                                board.as_ref().filter(|_| filled).and_then(|board| {
+                                    board
+                                        .cells
+                                        .iter()
+                                        .find(|cell| (cell.row, cell.col) == (row, col))
+                                        .and_then(|cell| cell.guest_name.clone())
+                                        .map(|name| format!("Written by {}", name))
+                                })
+                            })
                         };
-                        let extra_class = if is_frozen { "frozen" } else { "" };
-                        let class_str = format!("{} {}", class, extra_class);
-                        if is_input_cell && !is_frozen {
-                            let cell_value = cell_content.map_or("".to_string(), |c| c.to_string());
+                        let cell = if is_input_cell && !is_frozen {
                             view! {
                                 <input
-                                    class=class_str
+                                    id=crossword_cell_id(row, col)
+                                    class=class
+                                    title=title
                                     type="text"
-                                    maxlength=1
-                                    value=cell_value.as_str()
+                                    autocomplete="off"
+                                    prop:value=letter
+                                    on:focus=move |ev| {
+                                        active_cell.set(Some((row, col)));
+                                        // Type along the word through the cell if there's only one.
+                                        let direction = puzzle.with_untracked(|p| {
+                                            p.as_ref()
+                                                .and_then(|p| {
+                                                    p.word_at(row, col, typing_direction.get_untracked())
+                                                        .map(|i| p.words[i].direction)
+                                                })
+                                        });
+                                        if let Some(direction) = direction {
+                                            typing_direction.set(direction);
+                                        }
+                                        #[cfg(feature = "hydrate")]
+                                        event_target::<web_sys::HtmlInputElement>(&ev).select();
+                                        #[cfg(not(feature = "hydrate"))]
+                                        let _ = ev;
+                                    }
+                                    on:mousedown=move |_| {
+                                        // Clicking the active cell again turns typing the other way.
+                                        if active_cell.get_untracked() == Some((row, col)) {
+                                            let other = match typing_direction.get_untracked() {
+                                                Direction::Across => Direction::Down,
+                                                Direction::Down => Direction::Across,
+                                            };
+                                            let crossed = puzzle.with_untracked(|p| {
+                                                p.as_ref().is_some_and(|p| {
+                                                    p.words
+                                                        .iter()
+                                                        .any(|w| w.direction == other && w.contains(row, col))
+                                                })
+                                            });
+                                            if crossed {
+                                                typing_direction.set(other);
+                                            }
+                                        }
+                                    }
+                                    on:keydown=move |ev| {
+                                        let arrow_key = match ev.key().as_str() {
+                                            "ArrowRight" => Some((Direction::Across, true)),
+                                            "ArrowLeft" => Some((Direction::Across, false)),
+                                            "ArrowDown" => Some((Direction::Down, true)),
+                                            "ArrowUp" => Some((Direction::Down, false)),
+                                            _ => None,
+                                        };
+                                        if let Some((direction, forward)) = arrow_key {
+                                            ev.prevent_default();
+                                            arrow(row, col, direction, forward);
+                                        } else if ev.key() == "Backspace"
+                                            && grid.with_untracked(|grid| grid[row][col].is_none())
+                                        {
+                                            // Backspace in an empty cell erases the previous one.
+                                            ev.prevent_default();
+                                            if let Some((r, c)) = step_in_word(row, col, false) {
+                                                on_cell_change(r, c, None);
+                                                move_to((r, c));
+                                            }
+                                        }
+                                    }
                                     on:input=move |ev| {
-                                        let val = event_target_value(&ev)
+                                        let value = event_target_value(&ev);
+                                        let letter = value
                                             .chars()
-                                            .next()
-                                            .and_then(|c| c.to_uppercase().next());
-                                        on_cell_change(row, col, val);
+                                            .rev()
+                                            .find(|c| c.is_ascii_alphabetic())
+                                            .map(|c| c.to_ascii_uppercase());
+                                        if letter.is_none() && !value.is_empty() {
+                                            // Not a letter: put back what was there.
+                                            grid.update(|_| {});
+                                            return;
+                                        }
+                                        on_cell_change(row, col, letter);
+                                        if letter.is_some() {
+                                            if let Some(next) = step_in_word(row, col, true) {
+                                                move_to(next);
+                                            }
+                                        }
                                     }
                                     style:display="block"
                                 />
                             }
                                 .into_any()
                         } else {
-                            if let Some(c) = cell_content {
-                                view! { <div class=class_str>{format!("{}", c)}</div> }.into_any()
-                            } else {
-                                view! { <div class=class_str /> }.into_any()
-                            }
+                            view! { <div class=class title=title>{letter}</div> }.into_any()
+                        };
+                        view! {
+                            <div class="crossword-square">
+                                {number.map(|n| view! { <span class="cell-number">{n}</span> })}
+                                {cell}
+                            </div>
                         }
                     })
-                    .collect_view()}
+                    .collect_view();
+                view! { <div class="crossword-row">{cells}</div> }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // The across and down clues, in clue number order. Clicking one starts typing that word.
+    let clues_view = move || {
+        let layout = puzzle.get()?;
+        let numbers = layout.clue_numbers();
+        let mut order: Vec<usize> = (0..layout.words.len()).collect();
+        order.sort_by_key(|&i| numbers[i]);
+        let clue_list = |direction: Direction, heading: &'static str| {
+            let entries = order
+                .iter()
+                .copied()
+                .filter(|&i| layout.words[i].direction == direction)
+                .map(|i| {
+                    let word = &layout.words[i];
+                    let class = move || {
+                        let mut class = String::from("clue-entry");
+                        if completions.with(|c| c.get(i).copied().unwrap_or(false)) {
+                            class.push_str(" solved");
+                        }
+                        if active_word() == Some(i) {
+                            class.push_str(" active");
+                        }
+                        class
+                    };
+                    view! {
+                        <li class=class on:click=move |_| select_word(i)>
+                            <span class="clue-number">{numbers[i]}</span>
+                            {format!(" {} ({})", word.clue, word.len)}
+                        </li>
+                    }
+                })
+                .collect_view();
+            view! {
+                <div class="clue-list">
+                    <h3>{heading}</h3>
+                    <ul>{entries}</ul>
+                </div>
+            }
+        };
+        Some(view! {
+            <div class="crossword-clues">
+                {clue_list(Direction::Across, "Across")} {clue_list(Direction::Down, "Down")}
             </div>
-        }).collect_view()
+        })
     };
 
-    // Render the 7 horcrux clues below; reveal the ones whose corresponding crossword answers are
-    // correct.
+    // Render the horcrux clues below; the server only reveals the ones whose corresponding
+    // crossword answers are correct. Each can then be claimed once the horcrux is found.
     let horcrux_clues_view = move || {
-        let clues = horcrux_clues.get();
-        let completions = completions.get();
-        clues
-            .iter()
-            .zip(completions.iter())
-            .map(|(clue, &completed)| {
-                let clue_view = if !completed {
-                    view! { <div class="clue">{clue.clone()}</div> }.into_any()
-                } else {
-                    let lines: Vec<_> = clue.split('\n').collect();
-                    view! {
-                        <div class="clues reveal">
-                            {lines
-                                .iter()
-                                .map(|line| {
-                                    view! {
-                                        <p style="font-style: italic; margin: 0.25em 0;">{*line}</p>
-                                    }
-                                })
-                                .collect_view()}
-                        </div>
-                    }.into_any()
+        horcrux_clues
+            .get()
+            .into_iter()
+            .enumerate()
+            .map(|(word_index, clue)| {
+                let clue_view = match clue {
+                    None => view! { <div class="clue"></div> }.into_any(),
+                    Some(clue) => {
+                        view! {
+                            <div class="clues reveal">
+                                {clue
+                                    .split('\n')
+                                    .map(|line| {
+                                        view! {
+                                            <p style="font-style: italic; margin: 0.25em 0;">
+                                                {line.to_string()}
+                                            </p>
+                                        }
+                                    })
+                                    .collect_view()}
+                                <HorcruxClaimForm word_index claims=horcrux_claims />
+                            </div>
+                        }
+                            .into_any()
+                    }
                 };
                 view! {
                     {clue_view}
@@ -2467,17 +5761,96 @@ fn Crossword() -> impl IntoView {
             .collect_view()
     };
 
+    // Hint prices and the house's remaining hints, with a word picker; hidden once all is solved.
+    let hints_view = move || {
+        let status = hint_status_fetcher.get().and_then(|result| result.ok())?;
+        hint_word()?;
+        let layout = puzzle.get()?;
+        let solved = completions.get();
+        let left = (status.limit - status.used).max(0);
+        Some(view! {
+            <div class="crossword-hints">
+                <h2>"Hints"</h2>
+                <p>{format!("Your house has {} of {} hints left.", left, status.limit)}</p>
+                <select
+                    class="form-select"
+                    prop:value=move || hint_word().map(|i| i.to_string()).unwrap_or_default()
+                    on:change=move |ev| chosen_hint_word.set(event_target_value(&ev).parse().ok())
+                >
+                    {layout
+                        .words
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, _)| !solved.get(*i).copied().unwrap_or(false))
+                        .map(|(i, word)| {
+                            view! {
+                                <option value=i
+                                    .to_string()>
+                                    {format!("{} ({} letters)", layout.clue_label(i), word.len)}
+                                </option>
+                            }
+                        })
+                        .collect_view()}
+                </select>
+                <button
+                    class="btn-secondary"
+                    disabled=left == 0
+                    on:click=move |_| take_hint(HintKind::Letter, status.letter_cost)
+                >
+                    {format!("Reveal a letter (-{})", status.letter_cost)}
+                </button>
+                <button
+                    class="btn-secondary"
+                    disabled=left == 0
+                    on:click=move |_| take_hint(HintKind::Word, status.word_cost)
+                >
+                    {format!("Reveal the word (-{})", status.word_cost)}
+                </button>
+                {move || {
+                    (!hint_error.get().is_empty())
+                        .then(|| view! { <p class="error">{hint_error.get()}</p> })
+                }}
+            </div>
+        })
+    };
+
     view! {
         <div class="crossword">
             <a class="back-link" href="/">
                 "← Home"
             </a>
             <h1>"Horcrux Hunt"</h1>
+            {move || {
+                board
+                    .with(|board| board.is_some())
+                    .then(|| {
+                        view! {
+                            <p class="house-board-note">
+                                "Your whole house shares this board: teammates' letters appear as they type them."
+                            </p>
+                        }
+                    })
+            }}
+            {move || {
+                (board.with(|board| board.is_none()) && !conflicts.with(Vec::is_empty))
+                    .then(|| {
+                        view! {
+                            <p class="conflict-note">
+                                "Your crossword was changed on another device, so the marked letters were not saved."
+                            </p>
+                        }
+                    })
+            }}
             <button class="btn-rules" on:click=move |_| show_rules_modal.set(true)>
                 "Rules"
             </button>
             <div class="crossword-grid">{grid_view}</div>
+            {clues_view}
             <div class="horcrux-clues">{horcrux_clues_view}</div>
+            {hints_view}
+            <a class="leaderboard-link" href="/leaderboard">
+                "Leaderboard"
+            </a>
         </div>
         {move || {
             show_rules_modal
@@ -2495,13 +5868,16 @@ fn Crossword() -> impl IntoView {
                                         "Lord Voldemort has hidden his Horcruxes all over Hogwarts! We need your help in finding them."
                                     </p>
                                     <p>
-                                        "Explore Hogwarts to find clues for the crossword below. As a house, you earn 5 points for each correct word. Complete all 7 words for a bonus!"
+                                        "Explore Hogwarts to find clues for the crossword below. As a house, you earn 5 points for each correct word. Complete every word for a bonus!"
                                     </p>
                                     <p>
                                         "Each completed word also guides you to one of the hidden horcruxes."
                                     </p>
                                     <p>
-                                        "Hunt down the horcruxes. These are dangerous objects full of dark magic, so when you find one, DO NOT TOUCH IT! Send a photo of it by WhatsOwl to Hagrid so she can safely retrieve it for the Order to destroy."
+                                        "Stuck? Your house can buy a few hints, paid for with house points."
+                                    </p>
+                                    <p>
+                                        "Hunt down the horcruxes. These are dangerous objects full of dark magic, so when you find one, DO NOT TOUCH IT! Tell Hagrid where it is with the "We found it!" button under its clue, with a photo if you can, so she can safely retrieve it for the Order to destroy. Your house earns points for every horcrux she recovers."
                                     </p>
                                     <p>
                                         <em>"Good luck, witches and wizards!"</em>
@@ -2521,38 +5897,16 @@ fn Crossword() -> impl IntoView {
     }
 }
 
-fn cell_is_in_word(def: &WordDef, row: usize, col: usize) -> bool {
-    match def.dir {
-        Direction::Across => {
-            row == def.start_row && col >= def.start_col && col < def.start_col + def.len
-        }
-        Direction::Down => {
-            col == def.start_col && row >= def.start_row && row < def.start_row + def.len
-        }
-    }
-}
+#[component]
+fn CatchTheKeys() -> impl IntoView {
+    let keys_fetcher = Resource::new(|| (), |_| get_house_keys_handler());
 
-fn word_is_complete(grid: &Vec<Vec<Option<char>>>, def: &WordDef) -> bool {
-    for i in 0..def.len {
-        let r = match def.dir {
-            Direction::Across => def.start_row,
-            Direction::Down => def.start_row + i,
-        };
-        let c = match def.dir {
-            Direction::Across => def.start_col + i,
-            Direction::Down => def.start_col,
-        };
-        let cell = grid[r][c];
-        let expected = def.answer.as_bytes()[i] as char;
-        if cell != Some(expected) {
-            return false;
+    use_live_events(move |event| {
+        if event.changes_keys() {
+            keys_fetcher.refetch();
         }
-    }
-    true
-}
+    });
 
-#[component]
-fn CatchTheKeys() -> impl IntoView {
     view! {
         <div class="catch-the-keys">
             <a class="back-link" href="/">
@@ -2582,10 +5936,368 @@ fn CatchTheKeys() -> impl IntoView {
                     <em>"Speed, team work, and honesty are key (literally)!"</em>
                 </p>
             </section>
+            <section class="keys-progress">
+                <h2>"Progress"</h2>
+                <p>
+                    "Found a key? Scan its QR code while logged in to turn it in for your house. Careful: a letter that isn't in your house's name costs you points!"
+                </p>
+                <Suspense fallback=|| {
+                    view! { <p>"Loading..."</p> }
+                }>
+                    {move || {
+                        keys_fetcher
+                            .get()
+                            .map(|result| match result {
+                                Ok(houses) => {
+                                    houses
+                                        .into_iter()
+                                        .map(|keys| view! { <HouseKeyRing keys=keys /> })
+                                        .collect_view()
+                                        .into_any()
+                                }
+                                Err(e) => view! { <p class="error">{e.to_string()}</p> }.into_any(),
+                            })
+                    }}
+                </Suspense>
+            </section>
+        </div>
+    }
+}
+
+/// A house's Catch the Keys progress: a tile per letter of its name, lit once the key is turned in
+/// (or a wildcard stands in for it), the wildcards used, and the place it finished in.
+#[component]
+fn HouseKeyRing(keys: HouseKeys) -> impl IntoView {
+    let wildcards = keys
+        .turned_in
+        .iter()
+        .filter(|&&key| key == WILDCARD)
+        .count();
+    let status = match (keys.place, keys.points) {
+        (Some(place), Some(points)) => format!("{}, {} points", ordinal(place), points),
+        (Some(place), None) => ordinal(place),
+        (None, _) => format!("{} to go", keys.missing.len()),
+    };
+    view! {
+        <div class="house-keys" class:finished=keys.place.is_some()>
+            <h3>{keys.house_name.clone()} <span class="house-keys-status">{status}</span></h3>
+            <div class="key-tiles">
+                {key_tiles(&keys.house_name, &keys.missing)
+                    .into_iter()
+                    .map(|(key, found)| {
+                        view! { <span class="key-tile" class:found=found>{key}</span> }
+                    })
+                    .collect_view()}
+                {(wildcards > 0)
+                    .then(|| {
+                        view! {
+                            <span class="key-tile found wildcard">
+                                {format!("{} ×{}", WILDCARD, wildcards)}
+                            </span>
+                        }
+                    })}
+            </div>
+        </div>
+    }
+}
+
+/// Admin dashboard Catch the Keys desk: Hagrid and Harry record the keys each of their houses turns
+/// in, and can take back one recorded by mistake until the house has finished.
+#[component]
+fn CatchTheKeysAdmin() -> impl IntoView {
+    let keys_fetcher = Resource::new(|| (), |_| get_house_keys_handler());
+    let keys_error = RwSignal::new(String::new());
+
+    use_live_events(move |event| {
+        if event.changes_keys() {
+            keys_fetcher.refetch();
+        }
+    });
+
+    let turn_in = move |house_id: i32, key: char| {
+        spawn_local(async move {
+            match turn_in_key_handler(house_id, key.to_string()).await {
+                Ok(_) => keys_error.set(String::new()),
+                Err(e) => keys_error.set(e.to_string()),
+            }
+            keys_fetcher.refetch();
+        });
+    };
+
+    let take_back = move |house_id: i32, key: char| {
+        spawn_local(async move {
+            match take_back_key_handler(house_id, key.to_string()).await {
+                Ok(_) => keys_error.set(String::new()),
+                Err(e) => keys_error.set(e.to_string()),
+            }
+            keys_fetcher.refetch();
+        });
+    };
+
+    view! {
+        <section class="admin-section centered">
+            <h2>"Catch the Keys"</h2>
+            <Suspense fallback=|| {
+                view! { <p>"Loading..."</p> }
+            }>
+                {move || {
+                    keys_fetcher
+                        .get()
+                        .and_then(|result| result.ok())
+                        .map(|houses| {
+                            ["Hagrid", "Harry"]
+                                .into_iter()
+                                .map(|keeper| {
+                                    let desk: Vec<HouseKeys> = houses
+                                        .iter()
+                                        .filter(|keys| keys.keeper == keeper)
+                                        .cloned()
+                                        .collect();
+                                    view! {
+                                        <div class="keys-desk">
+                                            <h3>{format!("{}'s desk", keeper)}</h3>
+                                            {desk
+                                                .into_iter()
+                                                .map(|keys| {
+                                                    let house_id = keys.house_id;
+                                                    let finished = keys.place.is_some();
+                                                    let mut needed = keys.missing.clone();
+                                                    needed.sort_unstable();
+                                                    needed.dedup();
+                                                    if !finished {
+                                                        needed.push(WILDCARD);
+                                                    }
+                                                    let turned_in = keys.turned_in.clone();
+                                                    view! {
+                                                        <HouseKeyRing keys=keys />
+                                                        <div class="key-buttons">
+                                                            {needed
+                                                                .into_iter()
+                                                                .map(|key| {
+                                                                    view! {
+                                                                        <button
+                                                                            class="btn-primary"
+                                                                            title="Turned in"
+                                                                            on:click=move |_| turn_in(house_id, key)
+                                                                        >
+                                                                            {key}
+                                                                        </button>
+                                                                    }
+                                                                })
+                                                                .collect_view()}
+                                                        </div>
+                                                        {(!finished && !turned_in.is_empty())
+                                                            .then(|| {
+                                                                view! {
+                                                                    <div class="key-buttons">
+                                                                        "Take back: "
+                                                                        {turned_in
+                                                                            .into_iter()
+                                                                            .map(|key| {
+                                                                                view! {
+                                                                                    <button
+                                                                                        class="btn-secondary"
+                                                                                        on:click=move |_| take_back(house_id, key)
+                                                                                    >
+                                                                                        {key}
+                                                                                    </button>
+                                                                                }
+                                                                            })
+                                                                            .collect_view()}
+                                                                    </div>
+                                                                }
+                                                            })}
+                                                    }
+                                                })
+                                                .collect_view()}
+                                        </div>
+                                    }
+                                })
+                                .collect_view()
+                        })
+                }}
+            </Suspense>
+            {move || {
+                (!keys_error.get().is_empty())
+                    .then(|| view! { <p class="error">{keys_error.get()}</p> })
+            }}
+            <p>
+                <a href="/admin/keys">"Print the key QR codes"</a>
+            </p>
+        </section>
+    }
+}
+
+/// Printable sheet of every physical key's QR code, to cut out and stick on the keys before they
+/// are hidden. Printing it again gives the same codes.
+#[component]
+fn KeySheet() -> impl IntoView {
+    let is_admin_fetcher = Resource::new(|| (), |_| is_admin());
+    let sheet_fetcher = Resource::new(|| (), |_| get_key_sheet());
+
+    // Like the admin dashboard, only for admins.
+    let navigate = use_navigate();
+    Effect::new(move || {
+        is_admin_fetcher.with(|maybe_result| {
+            if let Some(Ok(false)) = maybe_result {
+                navigate("/", NavigateOptions::default());
+            }
+        });
+    });
+
+    let print = move |_| {
+        let _ = leptos::leptos_dom::helpers::window().print();
+    };
+
+    view! {
+        <div class="key-sheet">
+            <div class="key-sheet-controls">
+                <a class="back-link" href="/admin">
+                    "← Admin"
+                </a>
+                <button class="btn-primary" on:click=print>
+                    "Print"
+                </button>
+            </div>
+            <Suspense fallback=|| {
+                view! { <p>"Loading..."</p> }
+            }>
+                {move || {
+                    sheet_fetcher
+                        .get()
+                        .map(|result| match result {
+                            Ok(keys) => {
+                                view! {
+                                    <div class="key-cards">
+                                        {keys
+                                            .into_iter()
+                                            .map(|key| {
+                                                view! {
+                                                    <div class="key-card">
+                                                        <span class="key-card-letter">{key.key}</span>
+                                                        <div inner_html=key.qr_svg />
+                                                        {key
+                                                            .claimed_by
+                                                            .map(|house| {
+                                                                view! {
+                                                                    <span class="key-card-claimed">
+                                                                        {format!("Turned in for {}", house)}
+                                                                    </span>
+                                                                }
+                                                            })}
+                                                    </div>
+                                                }
+                                            })
+                                            .collect_view()}
+                                    </div>
+                                }
+                                    .into_any()
+                            }
+                            Err(e) => view! { <p class="error">{e.to_string()}</p> }.into_any(),
+                        })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+/// Where a key's QR code leads: turns the key in for the logged in guest's house, and says how
+/// that went.
+#[component]
+fn ClaimKey() -> impl IntoView {
+    let current_user_fetcher = Resource::new(|| (), |_| get_current_user());
+    let query_map = use_query_map();
+    let claim = RwSignal::new(None::<Result<KeyClaim, AppError>>);
+
+    // Claims the key once we know who is scanning it, and only once.
+    let claimed = StoredValue::new(false);
+    Effect::new(move |_| {
+        let logged_in = matches!(current_user_fetcher.get(), Some(Ok(Some(_))));
+        let params = query_map.read();
+        let key_id = params.get("key").and_then(|key| key.parse::<i32>().ok());
+        let token = params.get("token");
+        if let (true, Some(key_id), Some(token)) = (logged_in, key_id, token) {
+            if !claimed.get_value() {
+                claimed.set_value(true);
+                spawn_local(async move {
+                    claim.set(Some(claim_key_handler(key_id, token).await));
+                });
+            }
+        }
+    });
+
+    view! {
+        <div class="catch-the-keys">
+            <a class="back-link" href="/games/catch_the_keys">
+                "← Catch the Keys"
+            </a>
+            <h1>"Catch the Keys"</h1>
+            <Suspense fallback=|| {
+                view! { <p>"Loading..."</p> }
+            }>
+                {move || {
+                    current_user_fetcher
+                        .get()
+                        .map(|user| match user {
+                            Ok(Some(_)) => {
+                                match claim.get() {
+                                    None => view! { <p>"Turning in your key..."</p> }.into_any(),
+                                    Some(Ok(claim)) => {
+                                        view! { <KeyClaimResult claim=claim /> }.into_any()
+                                    }
+                                    // E.g. "That R has already been turned in for Ravenclaw".
+                                    Some(Err(AppError::DbError(reason))) => {
+                                        view! { <p class="error">{reason}</p> }.into_any()
+                                    }
+                                    Some(Err(e)) => {
+                                        view! { <p class="error">{e.to_string()}</p> }.into_any()
+                                    }
+                                }
+                            }
+                            Ok(None) => {
+                                view! {
+                                    <p>
+                                        "Log in with your own QR code first, then scan this key again."
+                                    </p>
+                                }
+                                    .into_any()
+                            }
+                            Err(e) => view! { <p class="error">{e.to_string()}</p> }.into_any(),
+                        })
+                }}
+            </Suspense>
         </div>
     }
 }
 
+#[component]
+fn KeyClaimResult(claim: KeyClaim) -> impl IntoView {
+    let house_name = claim.house.house_name.clone();
+    let message = match (claim.penalty, claim.house.place) {
+        (Some(penalty), _) if claim.repeat => format!(
+            "{} isn't a letter of {}: you already lost {} points for it. Leave it for another house!",
+            claim.key, house_name, penalty
+        ),
+        (Some(penalty), _) => format!(
+            "{} isn't a letter of {}: that cost you {} points. Leave it for another house!",
+            claim.key, house_name, penalty
+        ),
+        (None, Some(place)) => format!(
+            "{} turned in, and {} has all its keys: {} place!",
+            claim.key,
+            house_name,
+            ordinal(place)
+        ),
+        (None, None) => format!("{} turned in for {}!", claim.key, house_name),
+    };
+    view! {
+        <p class="key-claim" class:error=claim.penalty.is_some()>
+            {message}
+        </p>
+        <HouseKeyRing keys=claim.house />
+    }
+}
+
 #[component]
 fn BestDressed() -> impl IntoView {
     let current_user_fetcher = Resource::new(|| (), |_| get_current_user());
@@ -2595,6 +6307,14 @@ fn BestDressed() -> impl IntoView {
     let rcv_result_fetcher = Resource::new(|| (), |_| get_rcv_result_handler());
     let user_vote_fetcher = Resource::new(|| (), |_| get_user_vote_handler());
 
+    // Shows the results as soon as the admin closes voting.
+    use_live_events(move |event| {
+        if matches!(event, LiveEvent::Resync | LiveEvent::VotingChanged) {
+            voting_open_fetcher.refetch();
+            rcv_result_fetcher.refetch();
+        }
+    });
+
     let first_choice = RwSignal::new(0i32);
     let second_choice = RwSignal::new(0i32);
     let third_choice = RwSignal::new(0i32);
@@ -2942,80 +6662,74 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_compute_statuses() {
-        // Exact match.
-        assert_eq!(
-            compute_statuses("APPLE", "APPLE"),
-            vec![LetterStatus::Correct; 5]
-        );
-
-        // All absent.
+    fn test_wordle_key_names() {
+        assert_eq!(WordleKey::from_key_name("g"), Some(WordleKey::Letter('G')));
+        assert_eq!(WordleKey::from_key_name("Q"), Some(WordleKey::Letter('Q')));
+        assert_eq!(WordleKey::from_key_name("Enter"), Some(WordleKey::Enter));
         assert_eq!(
-            compute_statuses("APPLE", "CROWD"),
-            vec![LetterStatus::Absent; 5]
-        );
-
-        // All present but jumbled with some correct.
-        assert_eq!(
-            compute_statuses("STARE", "TEARS"),
-            vec![
-                LetterStatus::Present,
-                LetterStatus::Present,
-                LetterStatus::Correct,
-                LetterStatus::Correct,
-                LetterStatus::Present,
-            ]
-        );
-
-        // Partial match with duplicates.
-        assert_eq!(
-            compute_statuses("PAPER", "APPLE"),
-            vec![
-                LetterStatus::Present,
-                LetterStatus::Present,
-                LetterStatus::Correct,
-                LetterStatus::Present,
-                LetterStatus::Absent
-            ]
+            WordleKey::from_key_name("Backspace"),
+            Some(WordleKey::Backspace)
         );
+        assert_eq!(WordleKey::from_key_name("Shift"), None);
+        assert_eq!(WordleKey::from_key_name("1"), None);
+        assert_eq!(WordleKey::from_key_name("é"), None);
+        assert_eq!(WordleKey::from_key_name(" "), None);
+    }
 
-        // Duplicates exceeding target count.
+    #[test]
+    fn test_describe_guess() {
+        let guess = WordleGuess {
+            word: "GHOST".to_string(),
+            statuses: crate::wordle::compute_statuses("GHOST", "HOGST"),
+        };
         assert_eq!(
-            compute_statuses("AAABB", "AACDD"),
-            vec![
-                LetterStatus::Correct,
-                LetterStatus::Correct,
-                LetterStatus::Absent,
-                LetterStatus::Absent,
-                LetterStatus::Absent
-            ]
+            describe_guess(&guess),
+            "GHOST: G in the word, wrong place, H in the word, wrong place, \
+             O in the word, wrong place, S correct, T correct"
         );
     }
 
     #[test]
     #[ignore]
     fn test_process_guess() {
-        let target = "APPLE".to_string();
+        use crate::wordle::compute_statuses;
+
+        let guess = |word: &str| WordleGuess {
+            word: word.to_string(),
+            statuses: compute_statuses(word, "APPLE"),
+        };
+        let game = |words: &[&str], status: WordleStatus| WordleGame {
+            guesses: words.iter().map(|word| guess(word)).collect(),
+            status,
+            answer: (status != WordleStatus::Playing).then(|| "APPLE".to_string()),
+            points: (status == WordleStatus::Won).then_some(8),
+        };
         let guesses = RwSignal::new(vec![]);
         let current_guess = RwSignal::new("BREAD".to_string());
         let keyboard_status = RwSignal::new(HashMap::new());
         let game_over = RwSignal::new(false);
         let message = RwSignal::new(String::new());
+        let announcement = RwSignal::new(String::new());
 
         process_guess(
-            "BREAD".to_string(),
-            target.clone(),
+            game(&["BREAD"], WordleStatus::Playing),
             guesses,
             current_guess,
             keyboard_status,
             game_over,
             message,
+            announcement,
         );
 
-        assert_eq!(guesses.get(), vec!["BREAD".to_string()]);
+        assert_eq!(guesses.get(), vec![guess("BREAD")]);
         assert_eq!(current_guess.get(), String::new());
         assert!(!game_over.get());
         assert_eq!(message.get(), String::new());
+        assert_eq!(
+            announcement.get(),
+            "BREAD: B not in the word, R not in the word, E in the word, wrong place, \
+             A in the word, wrong place, D not in the word"
+        );
 
         // Check keyboard updates.
         let ks = keyboard_status.get();
@@ -3026,18 +6740,23 @@ mod tests {
         assert_eq!(ks.get(&'R'), Some(&LetterStatus::Absent));
 
         // Win case.
-        let current_guess = RwSignal::new("APPLE".to_string());
         process_guess(
-            "APPLE".to_string(),
-            target.clone(),
+            game(&["BREAD", "APPLE"], WordleStatus::Won),
             guesses,
             current_guess,
             keyboard_status,
             game_over,
             message,
+            announcement,
         );
         assert!(game_over.get());
-        assert_eq!(message.get(), "You win!");
+        assert_eq!(message.get(), "You win! 8 points for your house.");
+        // Only the new guess is read out.
+        assert_eq!(
+            announcement.get(),
+            "APPLE: A correct, P correct, P correct, L correct, E correct. \
+             You win! 8 points for your house."
+        );
 
         // Check keyboard updates.
         let ks = keyboard_status.get();
@@ -3049,20 +6768,18 @@ mod tests {
         assert_eq!(ks.get(&'P'), Some(&LetterStatus::Correct));
         assert_eq!(ks.get(&'R'), Some(&LetterStatus::Absent));
 
-        // Loss case (simulate 6 guesses).
-        let guesses = RwSignal::new(vec!["WRONG".to_string(); 5]);
-        let current_guess = RwSignal::new("WRONG".to_string());
+        // Loss case (6 guesses).
         process_guess(
-            "WRONG".to_string(),
-            target,
+            game(&["WRONG"; 6], WordleStatus::Lost),
             guesses,
             current_guess,
             keyboard_status,
             game_over,
             message,
+            announcement,
         );
         assert_eq!(guesses.get().len(), 6);
         assert!(game_over.get());
-        assert!(message.get().contains("Game over!"));
+        assert_eq!(message.get(), "Game over! The word was APPLE");
     }
 }
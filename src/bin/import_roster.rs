@@ -0,0 +1,48 @@
+// Imports a guest roster (CSV or JSON) as unregistered guests.
+//
+// Usage: import_roster <roster file> [--replace]
+//
+// With --replace, guests who have never been registered are removed before importing, so the file
+// becomes the new party's guest list.
+#[cfg(feature = "ssr")]
+use hp_halloween_25::{establish_connection, import_guest_roster, parse_roster};
+
+#[cfg(feature = "ssr")]
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let replace = args.iter().any(|arg| arg == "--replace");
+    let Some(path) = args.iter().find(|arg| !arg.starts_with("--")) else {
+        eprintln!("Usage: import_roster <roster.csv|roster.json> [--replace]");
+        std::process::exit(2);
+    };
+
+    let contents = std::fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("Failed to read roster {}: {}", path, e));
+    let names = parse_roster(&contents).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    });
+
+    let mut conn = establish_connection();
+    let report = import_guest_roster(&mut conn, &names, replace).expect("Failed to import roster");
+
+    if !report.invalid.is_empty() {
+        eprintln!("Roster rejected, nothing was imported:");
+        for problem in &report.invalid {
+            eprintln!("  {}", problem);
+        }
+        std::process::exit(1);
+    }
+    if replace {
+        println!("Removed {} unregistered guests.", report.removed);
+    }
+    for name in &report.duplicates {
+        println!("Skipped duplicate: {}", name);
+    }
+    println!("Imported {} guests.", report.imported.len());
+}
+
+#[cfg(not(feature = "ssr"))]
+fn main() {
+    println!("This binary requires the 'ssr' feature to be enabled.");
+}
@@ -0,0 +1,71 @@
+// A stand-in for the Sorting Hat device, so the whole registration flow can be tried on one
+// machine. It logs every house it is asked to announce.
+//
+// Usage: mock_sorting_hat [address]   (default 127.0.0.1:3100)
+//
+// Point the app at it with SORTING_HAT_URL=http://127.0.0.1:3100/flash. Set MOCK_HAT_FAILURE_RATE
+// to a number between 0 and 1 to make that share of requests fail, to exercise the retries.
+#[cfg(feature = "ssr")]
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    routing::get,
+    Router,
+};
+#[cfg(feature = "ssr")]
+use std::collections::HashMap;
+
+#[cfg(feature = "ssr")]
+async fn flash(
+    State(failure_rate): State<f64>,
+    Query(params): Query<HashMap<String, String>>,
+) -> (StatusCode, String) {
+    let Some(house) = params.get("house") else {
+        return (
+            StatusCode::BAD_REQUEST,
+            "Missing house parameter".to_string(),
+        );
+    };
+    let slug = params.get("slug").map(String::as_str).unwrap_or("?");
+    if rand::random::<f64>() < failure_rate {
+        println!("Dropped request for house {} ({})", house, slug);
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "The hat is thinking".to_string(),
+        );
+    }
+    println!("Announcing house {} ({})!", house, slug);
+    (StatusCode::OK, format!("Sorted into {}", slug))
+}
+
+#[cfg(feature = "ssr")]
+#[tokio::main]
+async fn main() {
+    let addr = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "127.0.0.1:3100".to_string());
+    let failure_rate: f64 = std::env::var("MOCK_HAT_FAILURE_RATE")
+        .map(|rate| {
+            rate.parse()
+                .expect("MOCK_HAT_FAILURE_RATE must be a number")
+        })
+        .unwrap_or(0.0);
+
+    let app = Router::new()
+        .route("/flash", get(flash))
+        .with_state(failure_rate);
+
+    println!(
+        "Mock Sorting Hat listening on http://{}/flash (failure rate {})",
+        addr, failure_rate
+    );
+    let listener = tokio::net::TcpListener::bind(&addr)
+        .await
+        .unwrap_or_else(|e| panic!("Failed to bind {}: {}", addr, e));
+    axum::serve(listener, app).await.unwrap();
+}
+
+#[cfg(not(feature = "ssr"))]
+fn main() {
+    println!("This binary requires the 'ssr' feature to be enabled.");
+}
@@ -0,0 +1,51 @@
+// Checks the cached house and guest scores against the point award ledger.
+//
+// Usage: reconcile_scores [--fix]
+//
+// Without --fix, mismatches are only reported and the exit status is 1 if there are any. With
+// --fix, every mismatched score is overwritten with its ledger total.
+#[cfg(feature = "ssr")]
+use hp_halloween_25::{establish_connection, reconcile_scores, verify_scores};
+
+#[cfg(feature = "ssr")]
+fn main() {
+    let fix = std::env::args().skip(1).any(|arg| arg == "--fix");
+
+    let mut conn = establish_connection();
+    let mismatches = if fix {
+        reconcile_scores(&mut conn).expect("Failed to reconcile scores")
+    } else {
+        verify_scores(&mut conn).expect("Failed to verify scores")
+    };
+
+    if mismatches.is_empty() {
+        println!("All scores match the point award ledger.");
+        return;
+    }
+    for mismatch in &mismatches {
+        let kind = if mismatch.house_id.is_some() {
+            "House"
+        } else {
+            "Guest"
+        };
+        println!(
+            "{} {}: cached {}, ledger {} ({:+})",
+            kind,
+            mismatch.name,
+            mismatch.cached,
+            mismatch.ledger,
+            mismatch.ledger - mismatch.cached
+        );
+    }
+    if fix {
+        println!("Fixed {} scores.", mismatches.len());
+    } else {
+        println!("Run with --fix to overwrite them with the ledger totals.");
+        std::process::exit(1);
+    }
+}
+
+#[cfg(not(feature = "ssr"))]
+fn main() {
+    println!("This binary requires the 'ssr' feature to be enabled.");
+}
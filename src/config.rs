@@ -0,0 +1,394 @@
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ssr")]
+use std::collections::HashMap;
+#[cfg(feature = "ssr")]
+use std::env;
+
+#[cfg(feature = "ssr")]
+use std::time::Duration;
+
+#[cfg(feature = "ssr")]
+use crate::{model::House, sorting_hat::RetryPolicy, SortingPlan};
+
+// Which games are offered at this party. Games switched off here are hidden from guests entirely;
+// the admin "Games Toggle" still controls whether the enabled ones are open yet.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameToggles {
+    pub crossword: bool,
+    pub catch_the_keys: bool,
+    pub best_dressed: bool,
+    pub wordle: bool,
+}
+
+impl Default for GameToggles {
+    fn default() -> Self {
+        Self {
+            crossword: true,
+            catch_the_keys: true,
+            best_dressed: true,
+            wordle: false,
+        }
+    }
+}
+
+// The part of the configuration the browser is allowed to see (served by `get_client_config`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClientConfig {
+    pub public_base_url: String,
+    pub games: GameToggles,
+    pub crossword_house_board: bool,
+    pub wordle_hard_mode: bool,
+}
+
+// Sorting Hat balancing settings; see `SortingPlan`.
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SortingConfig {
+    pub reveal_stages: Vec<f64>,
+    pub house_caps: HashMap<String, usize>, // keyed by house slug
+}
+
+#[cfg(feature = "ssr")]
+impl Default for SortingConfig {
+    fn default() -> Self {
+        let plan = SortingPlan::default();
+        Self {
+            reveal_stages: plan.reveal_stages,
+            house_caps: HashMap::new(),
+        }
+    }
+}
+
+// Crossword hint prices, in house points, and how many hints a house may take unless the admin
+// sets its limit.
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct HintConfig {
+    pub letter_cost: i32,
+    pub word_cost: i32,
+    pub default_limit: i32,
+}
+
+#[cfg(feature = "ssr")]
+impl Default for HintConfig {
+    fn default() -> Self {
+        Self {
+            letter_cost: 2,
+            word_cost: 10,
+            default_limit: 3,
+        }
+    }
+}
+
+/// Server configuration, loaded once at startup and provided to server functions via context.
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct AppConfig {
+    /// Base URL guests' phones use to reach the app, e.g. for login QR codes.
+    pub public_base_url: String,
+    /// Sorting Hat endpoint; the sorted house is passed as the `house` query parameter.
+    pub sorting_hat_url: Option<String>,
+    /// Attempts per Sorting Hat delivery, including the first, before it is marked failed.
+    pub sorting_hat_attempts: u32,
+    /// Delay before retrying a failed Sorting Hat delivery; doubles after each further failure.
+    pub sorting_hat_retry_ms: u64,
+    /// Lifetime of the guest login cookie.
+    pub guest_session_hours: u32,
+    /// Lifetime of the admin login cookie.
+    pub admin_session_hours: u32,
+    /// Crossword puzzle file, see `crate::crossword`.
+    pub crossword_puzzle: String,
+    /// Each house solves the crossword together on one shared board instead of every guest
+    /// having their own grid.
+    pub crossword_house_board: bool,
+    pub crossword_hints: HintConfig,
+    /// Directory the photos sent with horcrux claims are saved in.
+    pub horcrux_photo_dir: String,
+    /// House points for a horcrux claim the admin approves.
+    pub horcrux_points: i32,
+    /// Wordle answer list, one word per line; see `crate::wordle`.
+    pub wordle_answers: String,
+    /// Words accepted as Wordle guesses besides the answers.
+    pub wordle_guesses: String,
+    /// Wordle guesses must use every letter revealed so far.
+    pub wordle_hard_mode: bool,
+    /// House points for solving the daily Wordle in one guess, two guesses, and so on; any guess
+    /// past the end of the list scores its last entry.
+    pub wordle_points: Vec<i32>,
+    /// How many of a house's guests have to solve a Wordle race for the house to win it.
+    pub wordle_race_solvers: i32,
+    /// House points for winning a Wordle race.
+    pub wordle_race_points: i32,
+    /// House points for turning in all of a house's keys in Catch the Keys first, second, and so
+    /// on; houses finishing past the end of the list score nothing.
+    pub catch_the_keys_points: Vec<i32>,
+    /// Wildcard keys made for Catch the Keys, besides one key per letter of every house's name.
+    pub catch_the_keys_wildcards: usize,
+    /// Points a guest (and so their house) loses for scanning a key whose letter isn't in their
+    /// house's name.
+    pub catch_the_keys_wrong_house_penalty: i32,
+    pub games: GameToggles,
+    pub sorting: SortingConfig,
+}
+
+#[cfg(feature = "ssr")]
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            public_base_url: "http://localhost:3000".to_string(),
+            sorting_hat_url: None,
+            sorting_hat_attempts: 5,
+            sorting_hat_retry_ms: 1000,
+            guest_session_hours: 24,
+            admin_session_hours: 24,
+            crossword_puzzle: "puzzles/horcrux_hunt.json".to_string(),
+            crossword_house_board: false,
+            crossword_hints: HintConfig::default(),
+            horcrux_photo_dir: "uploads/horcrux".to_string(),
+            horcrux_points: 10,
+            wordle_answers: "words/hp_answers.txt".to_string(),
+            wordle_guesses: "words/guesses.txt".to_string(),
+            wordle_hard_mode: false,
+            wordle_points: vec![10, 8, 6, 4, 3, 2],
+            wordle_race_solvers: 3,
+            wordle_race_points: 30,
+            catch_the_keys_points: vec![40, 30, 20, 10],
+            catch_the_keys_wildcards: 4,
+            catch_the_keys_wrong_house_penalty: 5,
+            games: GameToggles::default(),
+            sorting: SortingConfig::default(),
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl AppConfig {
+    /// Loads the configuration from the TOML file named by `APP_CONFIG` (default `config.toml`,
+    /// which may be absent), then applies overrides from `PUBLIC_BASE_URL`, `SORTING_HAT_URL`,
+    /// `GUEST_SESSION_HOURS` and `ADMIN_SESSION_HOURS`.
+    pub fn load() -> Result<Self, String> {
+        let mut config = match env::var("APP_CONFIG") {
+            Ok(path) => Self::from_file(&path)?,
+            Err(_) if std::path::Path::new("config.toml").exists() => {
+                Self::from_file("config.toml")?
+            }
+            Err(_) => Self::default(),
+        };
+        config.apply_env(|key| env::var(key).ok())?;
+        Ok(config)
+    }
+
+    /// Parses a TOML configuration file. Missing keys take their default values.
+    pub fn from_file(path: &str) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read config {}: {}", path, e))?;
+        Self::from_toml(&contents).map_err(|e| format!("Invalid config {}: {}", path, e))
+    }
+
+    pub fn from_toml(contents: &str) -> Result<Self, String> {
+        toml::from_str(contents).map_err(|e| e.to_string())
+    }
+
+    /// Applies environment overrides, looking each variable up with `lookup`.
+    pub fn apply_env(&mut self, lookup: impl Fn(&str) -> Option<String>) -> Result<(), String> {
+        if let Some(url) = lookup("PUBLIC_BASE_URL") {
+            self.public_base_url = url;
+        }
+        if let Some(url) = lookup("SORTING_HAT_URL") {
+            // An empty value switches the Sorting Hat off.
+            self.sorting_hat_url = Some(url).filter(|url| !url.is_empty());
+        }
+        if let Some(hours) = lookup("GUEST_SESSION_HOURS") {
+            self.guest_session_hours = hours
+                .parse()
+                .map_err(|_| format!("GUEST_SESSION_HOURS must be a number, got {}", hours))?;
+        }
+        if let Some(hours) = lookup("ADMIN_SESSION_HOURS") {
+            self.admin_session_hours = hours
+                .parse()
+                .map_err(|_| format!("ADMIN_SESSION_HOURS must be a number, got {}", hours))?;
+        }
+        self.public_base_url = self.public_base_url.trim_end_matches('/').to_string();
+        Ok(())
+    }
+
+    pub fn client_config(&self) -> ClientConfig {
+        ClientConfig {
+            public_base_url: self.public_base_url.clone(),
+            games: self.games.clone(),
+            crossword_house_board: self.crossword_house_board,
+            wordle_hard_mode: self.wordle_hard_mode,
+        }
+    }
+
+    /// Builds the Sorting Hat plan, resolving the configured house caps (by slug) to house ids.
+    /// Unknown slugs are ignored.
+    pub fn sorting_plan(&self, houses: &[House]) -> SortingPlan {
+        SortingPlan {
+            reveal_stages: self.sorting.reveal_stages.clone(),
+            house_caps: houses
+                .iter()
+                .filter_map(|house| {
+                    self.sorting
+                        .house_caps
+                        .get(&house.slug)
+                        .map(|&cap| (house.id, cap))
+                })
+                .collect(),
+        }
+    }
+
+    pub fn sorting_hat_retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: self.sorting_hat_attempts.max(1),
+            initial_delay: Duration::from_millis(self.sorting_hat_retry_ms),
+        }
+    }
+
+    /// URL a guest scans to log in.
+    pub fn login_url(&self, guest_id: i32, token: &str) -> String {
+        format!(
+            "{}/login?guest_id={}&token={}",
+            self.public_base_url, guest_id, token
+        )
+    }
+
+    /// URL a guest scans to claim a Catch the Keys key for their house.
+    pub fn key_claim_url(&self, key_id: i32, token: &str) -> String {
+        format!(
+            "{}/games/catch_the_keys/claim?key={}&token={}",
+            self.public_base_url, key_id, token
+        )
+    }
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_from_toml() {
+        let config = AppConfig::from_toml(
+            r#"
+            public_base_url = "http://10.0.0.5:3000/"
+            sorting_hat_url = "http://10.0.0.6/flash"
+            sorting_hat_attempts = 3
+            guest_session_hours = 12
+            crossword_puzzle = "puzzles/halloween.json"
+            crossword_house_board = true
+            wordle_hard_mode = true
+            wordle_points = [5, 3]
+            wordle_race_solvers = 2
+            catch_the_keys_points = [25, 5]
+            catch_the_keys_wildcards = 2
+
+            [crossword_hints]
+            word_cost = 12
+
+            [games]
+            wordle = true
+            catch_the_keys = false
+
+            [sorting]
+            reveal_stages = [0.25, 0.5]
+            house_caps = { gryffindor = 10 }
+            "#,
+        )
+        .expect("Config should parse");
+        assert_eq!(config.public_base_url, "http://10.0.0.5:3000/");
+        assert_eq!(
+            config.sorting_hat_url.as_deref(),
+            Some("http://10.0.0.6/flash")
+        );
+        assert_eq!(config.guest_session_hours, 12);
+        assert_eq!(
+            config.sorting_hat_retry_policy(),
+            RetryPolicy {
+                max_attempts: 3,
+                initial_delay: Duration::from_secs(1), // default
+            }
+        );
+        assert_eq!(config.admin_session_hours, 24); // default
+        assert_eq!(config.crossword_puzzle, "puzzles/halloween.json");
+        assert!(config.crossword_house_board);
+        assert_eq!(config.crossword_hints.word_cost, 12);
+        assert_eq!(config.crossword_hints.letter_cost, 2); // default
+        assert!(config.wordle_hard_mode);
+        assert_eq!(config.wordle_answers, "words/hp_answers.txt"); // default
+        assert_eq!(config.wordle_points, vec![5, 3]);
+        assert_eq!(config.wordle_race_solvers, 2);
+        assert_eq!(config.wordle_race_points, 30); // default
+        assert_eq!(config.catch_the_keys_points, vec![25, 5]);
+        assert_eq!(config.catch_the_keys_wildcards, 2);
+        assert_eq!(config.catch_the_keys_wrong_house_penalty, 5); // default
+        assert!(config.games.wordle);
+        assert!(!config.games.catch_the_keys);
+        assert!(config.games.crossword); // default
+        assert_eq!(config.sorting.reveal_stages, vec![0.25, 0.5]);
+
+        assert!(AppConfig::from_toml("guest_session_hours = \"forever\"").is_err());
+    }
+
+    #[test]
+    fn test_config_env_overrides() {
+        let mut config = AppConfig::default();
+        config
+            .apply_env(|key| match key {
+                "PUBLIC_BASE_URL" => Some("https://party.example/".to_string()),
+                "SORTING_HAT_URL" => Some(String::new()),
+                "ADMIN_SESSION_HOURS" => Some("2".to_string()),
+                _ => None,
+            })
+            .expect("Overrides should apply");
+        assert_eq!(config.public_base_url, "https://party.example");
+        assert_eq!(config.sorting_hat_url, None);
+        assert_eq!(config.admin_session_hours, 2);
+        assert_eq!(
+            config.login_url(3, "abc"),
+            "https://party.example/login?guest_id=3&token=abc"
+        );
+        assert_eq!(
+            config.key_claim_url(7, "xyz"),
+            "https://party.example/games/catch_the_keys/claim?key=7&token=xyz"
+        );
+
+        let err = config
+            .apply_env(|key| (key == "GUEST_SESSION_HOURS").then(|| "soon".to_string()))
+            .expect_err("Should reject a non-numeric lifetime");
+        assert!(err.contains("GUEST_SESSION_HOURS"));
+    }
+
+    #[test]
+    fn test_config_sorting_plan() {
+        let mut config = AppConfig::default();
+        config.sorting.house_caps =
+            HashMap::from([("ravenclaw".to_string(), 3), ("durmstrang".to_string(), 5)]);
+        let houses = vec![
+            House {
+                id: 1,
+                name: "Gryffindor".to_string(),
+                score: 0,
+                slug: "gryffindor".to_string(),
+                primary_color: "#d32f2f".to_string(),
+                secondary_color: "#ffd700".to_string(),
+                crest: None,
+            },
+            House {
+                id: 3,
+                name: "Ravenclaw".to_string(),
+                score: 0,
+                slug: "ravenclaw".to_string(),
+                primary_color: "#1976d2".to_string(),
+                secondary_color: "#432818".to_string(),
+                crest: None,
+            },
+        ];
+        let plan = config.sorting_plan(&houses);
+        assert_eq!(plan.reveal_stages, vec![0.5]);
+        assert_eq!(plan.house_caps, HashMap::from([(3, 3)]));
+    }
+}
@@ -0,0 +1,435 @@
+// Crossword puzzles.
+//
+// A puzzle (grid size and words, with their clues and the horcrux each one leads to) is defined in
+// a JSON file under `puzzles/`, named by `crossword_puzzle` in the configuration. The server loads
+// and validates it at startup and provides it to server functions via context; the crossword page
+// only ever gets its `PuzzleLayout`, without the answers: the server checks the guests' letters.
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    Across,
+    Down,
+}
+
+/// One word of a puzzle.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WordDef {
+    /// Cell of the first letter.
+    pub row: usize,
+    pub col: usize,
+    pub direction: Direction,
+    /// Upper-case letters only.
+    pub answer: String,
+    #[serde(default)]
+    pub clue: String,
+    /// Riddle revealed once the word is solved, leading to the horcrux.
+    pub reveal_text: String,
+    /// Where in the house the clue for this word is hidden.
+    pub location: String,
+    pub horcrux: String,
+}
+
+impl WordDef {
+    pub fn len(&self) -> usize {
+        self.answer.chars().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.answer.is_empty()
+    }
+
+    /// The cells of the word, first letter first.
+    pub fn cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.len()).map(move |i| match self.direction {
+            Direction::Across => (self.row, self.col + i),
+            Direction::Down => (self.row + i, self.col),
+        })
+    }
+
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        covers(self.row, self.col, self.direction, self.len(), row, col)
+    }
+
+    /// Whether every cell of the word holds the right letter.
+    pub fn is_complete(&self, grid: &[Vec<Option<char>>]) -> bool {
+        self.cells()
+            .zip(self.answer.chars())
+            .all(|((row, col), expected)| {
+                grid.get(row)
+                    .and_then(|cells| cells.get(col))
+                    .copied()
+                    .flatten()
+                    == Some(expected)
+            })
+    }
+}
+
+/// What the browser is told about a word: where it goes and its clue, but not the answer.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WordSlot {
+    pub row: usize,
+    pub col: usize,
+    pub direction: Direction,
+    pub len: usize,
+    pub clue: String,
+}
+
+impl WordSlot {
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        covers(self.row, self.col, self.direction, self.len, row, col)
+    }
+
+    /// The cells of the word, first letter first.
+    pub fn cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.len).map(move |i| match self.direction {
+            Direction::Across => (self.row, self.col + i),
+            Direction::Down => (self.row + i, self.col),
+        })
+    }
+}
+
+// Whether a word of `len` letters starting at (`start_row`, `start_col`) covers (`row`, `col`).
+fn covers(
+    start_row: usize,
+    start_col: usize,
+    direction: Direction,
+    len: usize,
+    row: usize,
+    col: usize,
+) -> bool {
+    match direction {
+        Direction::Across => row == start_row && col >= start_col && col < start_col + len,
+        Direction::Down => col == start_col && row >= start_row && row < start_row + len,
+    }
+}
+
+/// A puzzle as the crossword page sees it, without answers or reveal texts.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PuzzleLayout {
+    pub title: String,
+    pub rows: usize,
+    pub cols: usize,
+    pub words: Vec<WordSlot>,
+}
+
+impl PuzzleLayout {
+    /// Whether the cell belongs to any word (and so takes a letter).
+    pub fn is_word_cell(&self, row: usize, col: usize) -> bool {
+        self.words.iter().any(|word| word.contains(row, col))
+    }
+
+    /// The clue number of each word, in puzzle order. As in printed crosswords, the cells where
+    /// words start are numbered left to right, top to bottom, so an across word and a down word
+    /// starting on the same cell share a number.
+    pub fn clue_numbers(&self) -> Vec<usize> {
+        let mut starts: Vec<(usize, usize)> =
+            self.words.iter().map(|word| (word.row, word.col)).collect();
+        starts.sort_unstable();
+        starts.dedup();
+        self.words
+            .iter()
+            .map(|word| starts.binary_search(&(word.row, word.col)).unwrap_or(0) + 1)
+            .collect()
+    }
+
+    /// How the clue of a word is referred to, e.g. "3 Down".
+    pub fn clue_label(&self, word_index: usize) -> String {
+        let number = self.clue_numbers().get(word_index).copied().unwrap_or(0);
+        match self.words.get(word_index).map(|word| word.direction) {
+            Some(Direction::Down) => format!("{} Down", number),
+            _ => format!("{} Across", number),
+        }
+    }
+
+    /// The word through the cell, preferring one running in `direction`.
+    pub fn word_at(&self, row: usize, col: usize, direction: Direction) -> Option<usize> {
+        let mut through = (0..self.words.len()).filter(|&i| self.words[i].contains(row, col));
+        let first = through.next()?;
+        Some(
+            std::iter::once(first)
+                .chain(through)
+                .find(|&i| self.words[i].direction == direction)
+                .unwrap_or(first),
+        )
+    }
+
+    /// The nearest word cell from (`row`, `col`) in `direction`, going forward (right or down) or
+    /// back, skipping blank cells. None at the edge of the grid.
+    pub fn next_word_cell(
+        &self,
+        row: usize,
+        col: usize,
+        direction: Direction,
+        forward: bool,
+    ) -> Option<(usize, usize)> {
+        let step = |(row, col): (usize, usize)| -> Option<(usize, usize)> {
+            let (row, col) = match (direction, forward) {
+                (Direction::Across, true) => (row, col + 1),
+                (Direction::Across, false) => (row, col.checked_sub(1)?),
+                (Direction::Down, true) => (row + 1, col),
+                (Direction::Down, false) => (row.checked_sub(1)?, col),
+            };
+            (row < self.rows && col < self.cols).then_some((row, col))
+        };
+        std::iter::successors(step((row, col)), |&cell| step(cell))
+            .find(|&(row, col)| self.is_word_cell(row, col))
+    }
+}
+
+/// A crossword puzzle. Build it with `Puzzle::from_json` (or `Puzzle::load`), which validates it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Puzzle {
+    pub title: String,
+    pub rows: usize,
+    pub cols: usize,
+    pub words: Vec<WordDef>,
+}
+
+impl Puzzle {
+    /// Parses and validates a puzzle definition.
+    pub fn from_json(contents: &str) -> Result<Self, String> {
+        let puzzle: Puzzle = serde_json::from_str(contents).map_err(|e| e.to_string())?;
+        puzzle.validate()?;
+        Ok(puzzle)
+    }
+
+    /// Reads, parses and validates a puzzle file.
+    #[cfg(feature = "ssr")]
+    pub fn load(path: &str) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read puzzle {}: {}", path, e))?;
+        Self::from_json(&contents).map_err(|e| format!("Invalid puzzle {}: {}", path, e))
+    }
+
+    /// Checks that the puzzle has words, that every word is upper-case letters and fits in the
+    /// grid, and that words crossing each other agree on the shared letter.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.rows == 0 || self.cols == 0 {
+            return Err("Grid must have at least one row and one column".to_string());
+        }
+        if self.words.is_empty() {
+            return Err("Puzzle has no words".to_string());
+        }
+
+        let mut letters: Vec<Vec<Option<(char, usize)>>> = vec![vec![None; self.cols]; self.rows];
+        for (index, word) in self.words.iter().enumerate() {
+            if word.is_empty() || !word.answer.chars().all(|c| c.is_ascii_uppercase()) {
+                return Err(format!(
+                    "Word {} ({:?}) must be upper-case letters A-Z",
+                    index, word.answer
+                ));
+            }
+            for ((row, col), letter) in word.cells().zip(word.answer.chars()) {
+                if row >= self.rows || col >= self.cols {
+                    return Err(format!(
+                        "Word {} ({}) runs off the {}x{} grid at row {}, column {}",
+                        index, word.answer, self.rows, self.cols, row, col
+                    ));
+                }
+                match letters[row][col] {
+                    Some((existing, other)) if existing != letter => {
+                        return Err(format!(
+                            "Words {} ({}) and {} ({}) disagree at row {}, column {}: {} vs {}",
+                            other,
+                            self.words[other].answer,
+                            index,
+                            word.answer,
+                            row,
+                            col,
+                            existing,
+                            letter
+                        ));
+                    }
+                    _ => letters[row][col] = Some((letter, index)),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn empty_grid(&self) -> Vec<Vec<Option<char>>> {
+        vec![vec![None; self.cols]; self.rows]
+    }
+
+    /// Whether the cell belongs to any word (and so takes a letter).
+    pub fn is_word_cell(&self, row: usize, col: usize) -> bool {
+        self.words.iter().any(|word| word.contains(row, col))
+    }
+
+    pub fn layout(&self) -> PuzzleLayout {
+        PuzzleLayout {
+            title: self.title.clone(),
+            rows: self.rows,
+            cols: self.cols,
+            words: self
+                .words
+                .iter()
+                .map(|word| WordSlot {
+                    row: word.row,
+                    col: word.col,
+                    direction: word.direction,
+                    len: word.len(),
+                    clue: word.clue.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// The reveal text of each solved word, in puzzle order.
+    pub fn reveals(&self, completions: &[bool]) -> Vec<Option<String>> {
+        self.words
+            .iter()
+            .zip(completions.iter().chain(std::iter::repeat(&false)))
+            .map(|(word, &solved)| solved.then(|| word.reveal_text.clone()))
+            .collect()
+    }
+
+    /// Which words are correctly filled in on `grid`, in puzzle order.
+    pub fn completions(&self, grid: &[Vec<Option<char>>]) -> Vec<bool> {
+        self.words
+            .iter()
+            .map(|word| word.is_complete(grid))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(row: usize, col: usize, direction: Direction, answer: &str) -> WordDef {
+        WordDef {
+            row,
+            col,
+            direction,
+            answer: answer.to_string(),
+            clue: String::new(),
+            reveal_text: String::new(),
+            location: String::new(),
+            horcrux: String::new(),
+        }
+    }
+
+    fn puzzle(words: Vec<WordDef>) -> Puzzle {
+        Puzzle {
+            title: "Test".to_string(),
+            rows: 5,
+            cols: 5,
+            words,
+        }
+    }
+
+    #[test]
+    fn test_horcrux_hunt_puzzle_is_valid() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/puzzles/horcrux_hunt.json");
+        let contents = std::fs::read_to_string(path).unwrap();
+        let puzzle = Puzzle::from_json(&contents).expect("Puzzle should be valid");
+        assert_eq!((puzzle.rows, puzzle.cols), (15, 12));
+        assert_eq!(puzzle.words.len(), 7);
+        assert_eq!(puzzle.words[1].answer, "EXPELLIARMUS");
+    }
+
+    #[test]
+    fn test_puzzle_validation() {
+        // Crossing words sharing a letter are fine.
+        let crossing = puzzle(vec![
+            word(0, 0, Direction::Across, "CAT"),
+            word(0, 1, Direction::Down, "ARK"),
+        ]);
+        assert!(crossing.validate().is_ok());
+
+        let conflict = puzzle(vec![
+            word(0, 0, Direction::Across, "CAT"),
+            word(0, 1, Direction::Down, "OWL"),
+        ]);
+        let err = conflict.validate().unwrap_err();
+        assert!(err.contains("disagree at row 0, column 1"), "{}", err);
+
+        let too_long = puzzle(vec![word(4, 2, Direction::Across, "TOAD")]);
+        assert!(too_long.validate().unwrap_err().contains("runs off"));
+
+        let lower_case = puzzle(vec![word(0, 0, Direction::Across, "owl")]);
+        assert!(lower_case.validate().is_err());
+        assert!(puzzle(vec![]).validate().is_err());
+        assert!(Puzzle::from_json("{\"title\": \"No grid\"}").is_err());
+    }
+
+    #[test]
+    fn test_puzzle_completions() {
+        let puzzle = puzzle(vec![
+            word(0, 0, Direction::Across, "CAT"),
+            word(0, 1, Direction::Down, "ARK"),
+        ]);
+        let mut grid = puzzle.empty_grid();
+        for (col, letter) in "CAT".chars().enumerate() {
+            grid[0][col] = Some(letter);
+        }
+        assert_eq!(puzzle.completions(&grid), vec![true, false]);
+        assert!(puzzle.is_word_cell(2, 1));
+        assert!(!puzzle.is_word_cell(2, 2));
+
+        grid[1][1] = Some('R');
+        grid[2][1] = Some('K');
+        assert_eq!(puzzle.completions(&grid), vec![true, true]);
+    }
+
+    #[test]
+    fn test_puzzle_layout_hides_answers() {
+        let mut crossing = puzzle(vec![
+            word(0, 0, Direction::Across, "CAT"),
+            word(0, 1, Direction::Down, "ARK"),
+        ]);
+        crossing.words[1].reveal_text = "Look under the boat".to_string();
+        let layout = crossing.layout();
+        assert_eq!(layout.words[1].len, 3);
+        assert!(layout.is_word_cell(2, 1));
+        assert!(!layout.is_word_cell(1, 0));
+        let json = serde_json::to_string(&layout).unwrap();
+        assert!(!json.contains("ARK") && !json.contains("boat"));
+
+        assert_eq!(
+            crossing.reveals(&[false, true]),
+            vec![None, Some("Look under the boat".to_string())]
+        );
+        assert_eq!(crossing.reveals(&[]), vec![None, None]);
+    }
+
+    #[test]
+    fn test_layout_clue_numbers_and_navigation() {
+        let crossing = puzzle(vec![
+            word(2, 0, Direction::Across, "AIL"),
+            word(0, 1, Direction::Down, "TWIG"),
+            word(0, 1, Direction::Across, "TOAD"),
+            word(0, 4, Direction::Down, "DRAKE"),
+        ]);
+        assert!(crossing.validate().is_ok());
+        let layout = crossing.layout();
+        // Starts in reading order: (0, 1), (0, 4), (2, 0).
+        assert_eq!(layout.clue_numbers(), vec![3, 1, 1, 2]);
+        assert_eq!(layout.clue_label(1), "1 Down");
+        assert_eq!(layout.clue_label(2), "1 Across");
+
+        assert_eq!(layout.word_at(0, 1, Direction::Across), Some(2));
+        assert_eq!(layout.word_at(0, 1, Direction::Down), Some(1));
+        assert_eq!(layout.word_at(2, 0, Direction::Down), Some(0));
+        assert_eq!(layout.word_at(4, 0, Direction::Across), None);
+
+        // Arrow keys skip blank cells and stop at the edge.
+        assert_eq!(
+            layout.next_word_cell(2, 2, Direction::Across, true),
+            Some((2, 4))
+        );
+        assert_eq!(layout.next_word_cell(0, 1, Direction::Across, false), None);
+        assert_eq!(
+            layout.next_word_cell(0, 1, Direction::Down, true),
+            Some((1, 1))
+        );
+        assert_eq!(layout.next_word_cell(4, 4, Direction::Down, true), None);
+        assert_eq!(
+            layout.words[1].cells().collect::<Vec<_>>(),
+            vec![(0, 1), (1, 1), (2, 1), (3, 1)]
+        );
+    }
+}
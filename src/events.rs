@@ -0,0 +1,273 @@
+// Live updates pushed from the server to open pages over Server-Sent Events.
+//
+// Server functions publish a `LiveEvent` on the `EventBus` after a change is committed, and every
+// page subscribed through `use_live_events` refetches whatever the event affects.
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ssr")]
+use axum::{
+    response::sse::{Event, KeepAlive, Sse},
+    Extension,
+};
+#[cfg(feature = "ssr")]
+use futures::stream::Stream;
+#[cfg(feature = "ssr")]
+use leptos::prelude::use_context;
+#[cfg(feature = "ssr")]
+use std::convert::Infallible;
+#[cfg(feature = "ssr")]
+use tokio::sync::broadcast::{self, error::RecvError};
+
+/// Route serving the event stream.
+pub const EVENTS_PATH: &str = "/api/events";
+
+/// Something that changed on the server.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LiveEvent {
+    /// Sent when a page connects, and when it fell too far behind to be told what it missed.
+    /// Subscribers should refetch everything they show.
+    Resync,
+    /// Points were awarded to or taken from a house, directly or through one of its guests.
+    PointsAwarded { house_id: i32, amount: i32 },
+    /// A guest was registered, unregistered or re-sorted, or the roster was imported.
+    GuestsChanged,
+    /// A house solved a crossword word for the first time (which also scores points).
+    CrosswordWordCompleted { house_id: i32, word_index: usize },
+    /// Letters were written on a house's shared crossword board (house board mode). Only the house
+    /// is named, so that other houses' pages never see its letters.
+    HouseBoardChanged { house_id: i32 },
+    /// A guest claimed to have found a horcrux, or a claim was approved or rejected.
+    HorcruxClaimsChanged { house_id: i32 },
+    /// Best-dressed voting was opened, closed or reset.
+    VotingChanged,
+    /// A guest submitted a best-dressed vote.
+    VoteSubmitted,
+    /// The games were opened or closed.
+    GamesToggled { enabled: bool },
+    /// A Wordle race was started, stopped or won, or a racer guessed.
+    WordleRaceChanged,
+    /// A key a house turned in for Catch the Keys was recorded or taken back.
+    KeysTurnedIn { house_id: i32 },
+}
+
+impl LiveEvent {
+    /// Whether house scores (and the point award log) may have changed.
+    pub fn changes_scores(&self) -> bool {
+        matches!(
+            self,
+            LiveEvent::Resync
+                | LiveEvent::PointsAwarded { .. }
+                | LiveEvent::CrosswordWordCompleted { .. }
+        )
+    }
+
+    /// Whether the guest lists may have changed.
+    pub fn changes_guests(&self) -> bool {
+        matches!(self, LiveEvent::Resync | LiveEvent::GuestsChanged)
+    }
+
+    /// Whether the voting status, turnout or results may have changed.
+    pub fn changes_voting(&self) -> bool {
+        matches!(
+            self,
+            LiveEvent::Resync | LiveEvent::VotingChanged | LiveEvent::VoteSubmitted
+        )
+    }
+
+    /// Whether crossword progress may have changed.
+    pub fn changes_crossword(&self) -> bool {
+        matches!(
+            self,
+            LiveEvent::Resync | LiveEvent::CrosswordWordCompleted { .. }
+        )
+    }
+
+    /// Whether the shared crossword board of `house_id` may have changed.
+    pub fn changes_house_board(&self, house_id: i32) -> bool {
+        match self {
+            LiveEvent::Resync => true,
+            LiveEvent::HouseBoardChanged { house_id: changed } => *changed == house_id,
+            _ => false,
+        }
+    }
+
+    /// Whether a horcrux claim may have been made or reviewed.
+    pub fn changes_horcrux_claims(&self) -> bool {
+        matches!(
+            self,
+            LiveEvent::Resync | LiveEvent::HorcruxClaimsChanged { .. }
+        )
+    }
+
+    /// Whether a Wordle race or its standings may have changed.
+    pub fn changes_wordle_race(&self) -> bool {
+        matches!(self, LiveEvent::Resync | LiveEvent::WordleRaceChanged)
+    }
+
+    /// Whether any house's Catch the Keys progress may have changed.
+    pub fn changes_keys(&self) -> bool {
+        matches!(self, LiveEvent::Resync | LiveEvent::KeysTurnedIn { .. })
+    }
+
+    /// Whether the games may have been opened or closed.
+    pub fn changes_games(&self) -> bool {
+        matches!(self, LiveEvent::Resync | LiveEvent::GamesToggled { .. })
+    }
+}
+
+/// Fans live events out to every connected page. Provided to server functions via context.
+#[cfg(feature = "ssr")]
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<LiveEvent>,
+}
+
+#[cfg(feature = "ssr")]
+impl EventBus {
+    /// `capacity` is how many events a slow page may fall behind before it is told to resync.
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+
+    pub fn publish(&self, event: LiveEvent) {
+        // Sending only fails when nobody is listening, which is fine.
+        let _ = self.sender.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<LiveEvent> {
+        self.sender.subscribe()
+    }
+}
+
+/// Publishes an event on the bus provided to the current server function, if there is one.
+#[cfg(feature = "ssr")]
+pub fn publish(event: LiveEvent) {
+    if let Some(bus) = use_context::<EventBus>() {
+        bus.publish(event);
+    }
+}
+
+/// Axum handler for `EVENTS_PATH`. Each event is sent as a JSON-encoded `LiveEvent`, starting with
+/// `LiveEvent::Resync`.
+#[cfg(feature = "ssr")]
+pub async fn live_events_handler(
+    Extension(bus): Extension<EventBus>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let stream = futures::stream::unfold(
+        (bus.subscribe(), true),
+        |(mut receiver, first)| async move {
+            let event = if first {
+                LiveEvent::Resync
+            } else {
+                match receiver.recv().await {
+                    Ok(event) => event,
+                    Err(RecvError::Lagged(_)) => LiveEvent::Resync,
+                    Err(RecvError::Closed) => return None,
+                }
+            };
+            let data = serde_json::to_string(&event).expect("Failed to serialize live event");
+            Some((Ok(Event::default().data(data)), (receiver, false)))
+        },
+    );
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// Calls `on_event` for every live event while the current component is mounted. Does nothing
+/// outside the browser. The browser reconnects on its own if the connection drops, and the server
+/// then sends `LiveEvent::Resync`.
+pub fn use_live_events(on_event: impl Fn(LiveEvent) + 'static) {
+    #[cfg(feature = "hydrate")]
+    {
+        use leptos::logging::log;
+        use leptos::prelude::on_cleanup;
+        use send_wrapper::SendWrapper;
+        use wasm_bindgen::{closure::Closure, JsCast};
+
+        let source = match web_sys::EventSource::new(EVENTS_PATH) {
+            Ok(source) => source,
+            Err(e) => {
+                log!("Failed to subscribe to live events: {:?}", e);
+                return;
+            }
+        };
+        // The page fetched everything while loading, so the resync that opens the first
+        // connection is skipped.
+        let connected = std::cell::Cell::new(false);
+        let on_message =
+            Closure::<dyn Fn(web_sys::MessageEvent)>::new(move |message: web_sys::MessageEvent| {
+                let Some(data) = message.data().as_string() else {
+                    return;
+                };
+                match serde_json::from_str::<LiveEvent>(&data) {
+                    Ok(LiveEvent::Resync) if !connected.replace(true) => {}
+                    Ok(event) => on_event(event),
+                    Err(e) => log!("Ignoring unknown live event {}: {}", data, e),
+                }
+            });
+        source.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+
+        // The closure must live as long as the connection; both are dropped on unmount.
+        let subscription = SendWrapper::new((source, on_message));
+        on_cleanup(move || {
+            let (source, _on_message) = subscription.take();
+            source.close();
+        });
+    }
+    #[cfg(not(feature = "hydrate"))]
+    let _ = on_event;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_live_event_json() {
+        let event = LiveEvent::PointsAwarded {
+            house_id: 2,
+            amount: -5,
+        };
+        let json = serde_json::to_string(&event).unwrap();
+        assert_eq!(
+            json,
+            r#"{"kind":"points_awarded","house_id":2,"amount":-5}"#
+        );
+        assert_eq!(serde_json::from_str::<LiveEvent>(&json).unwrap(), event);
+        assert_eq!(
+            serde_json::from_str::<LiveEvent>(r#"{"kind":"resync"}"#).unwrap(),
+            LiveEvent::Resync
+        );
+    }
+
+    #[test]
+    fn test_live_event_scope() {
+        let completed = LiveEvent::CrosswordWordCompleted {
+            house_id: 1,
+            word_index: 3,
+        };
+        assert!(completed.changes_scores());
+        assert!(completed.changes_crossword());
+        assert!(!completed.changes_voting());
+        assert!(!LiveEvent::VoteSubmitted.changes_scores());
+        assert!(LiveEvent::GuestsChanged.changes_guests());
+        assert!(LiveEvent::Resync.changes_games());
+
+        let board = LiveEvent::HouseBoardChanged { house_id: 2 };
+        assert!(board.changes_house_board(2));
+        assert!(!board.changes_house_board(3));
+        assert!(!board.changes_crossword());
+        assert!(LiveEvent::Resync.changes_house_board(3));
+
+        let claims = LiveEvent::HorcruxClaimsChanged { house_id: 1 };
+        assert!(claims.changes_horcrux_claims());
+        assert!(!claims.changes_scores());
+        assert!(!completed.changes_horcrux_claims());
+
+        assert!(LiveEvent::WordleRaceChanged.changes_wordle_race());
+        assert!(!LiveEvent::WordleRaceChanged.changes_scores());
+        assert!(LiveEvent::KeysTurnedIn { house_id: 1 }.changes_keys());
+        assert!(!LiveEvent::WordleRaceChanged.changes_keys());
+    }
+}
@@ -0,0 +1,201 @@
+// Catch the Keys.
+//
+// Every house has to round up one key for each letter of its name (a letter appearing twice needs
+// two keys), and any of them can be stood in for by a wildcard key marked `WILDCARD`. Hagrid and
+// Harry record the keys houses turn in on the admin dashboard; the server keeps them in
+// `turned_in_keys`, and the houses that have all their keys score in the order they finished, with
+// the points configured by `catch_the_keys_points`.
+//
+// Each physical key also carries a QR code (printed from the key sheet on the admin dashboard), and
+// a guest scanning one turns it in for their house. A key whose letter isn't in the house's name
+// costs the guest `catch_the_keys_wrong_house_penalty` points instead.
+use std::collections::HashMap;
+
+/// The mark on a wildcard key.
+pub const WILDCARD: char = '✷';
+
+/// The keys a house has to turn in: the letters of its name, upper case, in order.
+pub fn house_keys(house_name: &str) -> Vec<char> {
+    house_name
+        .chars()
+        .filter(char::is_ascii_alphabetic)
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// Who the keys of a house are turned in to: Hagrid for Gryffindor and Hufflepuff, Harry for the
+/// others.
+pub fn keeper(house_slug: &str) -> &'static str {
+    match house_slug {
+        "gryffindor" | "hufflepuff" => "Hagrid",
+        _ => "Harry",
+    }
+}
+
+/// The letters of `house_name` still to be turned in, in order, given the keys `turned_in` so far.
+/// Wildcards stand in for the last missing letters.
+pub fn missing_keys(house_name: &str, turned_in: &[char]) -> Vec<char> {
+    let mut have: HashMap<char, usize> = HashMap::new();
+    for &key in turned_in {
+        *have.entry(key).or_insert(0) += 1;
+    }
+    let mut missing: Vec<char> = house_keys(house_name)
+        .into_iter()
+        .filter(|c| match have.get_mut(c) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                false
+            }
+            _ => true,
+        })
+        .collect();
+    let wildcards = have.get(&WILDCARD).copied().unwrap_or(0);
+    missing.truncate(missing.len().saturating_sub(wildcards));
+    missing
+}
+
+/// Whether `key` (upper case, or `WILDCARD`) is one the house named `house_name` can use at all.
+pub fn is_house_key(house_name: &str, key: char) -> bool {
+    key == WILDCARD || house_keys(house_name).contains(&key)
+}
+
+/// The physical keys to make for houses named `house_names`: a set of letters for each house, then
+/// `wildcards` wildcards.
+pub fn key_set(house_names: &[&str], wildcards: usize) -> Vec<char> {
+    house_names
+        .iter()
+        .flat_map(|name| house_keys(name))
+        .chain(std::iter::repeat_n(WILDCARD, wildcards))
+        .collect()
+}
+
+/// Why `key` (upper case, or `WILDCARD`) can't be turned in by the house named `house_name`, which
+/// has already turned in `turned_in`: it isn't one of the house's letters, or the house has enough
+/// of it, or all its keys already.
+pub fn turn_in_error(house_name: &str, turned_in: &[char], key: char) -> Option<String> {
+    if missing_keys(house_name, turned_in).is_empty() {
+        return Some(format!("{} has all its keys already", house_name));
+    }
+    if key == WILDCARD {
+        return None;
+    }
+    let needed = house_keys(house_name)
+        .into_iter()
+        .filter(|&c| c == key)
+        .count();
+    if needed == 0 {
+        return Some(format!("{} is not a letter of {}", key, house_name));
+    }
+    if turned_in.iter().filter(|&&c| c == key).count() >= needed {
+        return Some(format!("{} has every {} already", house_name, key));
+    }
+    None
+}
+
+/// The keys of `house_name` in order, each with whether it has been turned in (or stood in for by a
+/// wildcard), given the keys still `missing`.
+pub fn key_tiles(house_name: &str, missing: &[char]) -> Vec<(char, bool)> {
+    let mut still_missing: HashMap<char, usize> = HashMap::new();
+    for &c in missing {
+        *still_missing.entry(c).or_insert(0) += 1;
+    }
+    house_keys(house_name)
+        .into_iter()
+        .map(|c| match still_missing.get_mut(&c) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                (c, false)
+            }
+            _ => (c, true),
+        })
+        .collect()
+}
+
+/// 1st, 2nd, 3rd and so on.
+pub fn ordinal(place: i32) -> String {
+    let suffix = match (place % 10, place % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", place, suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_house_keys() {
+        assert_eq!(
+            house_keys("Gryffindor"),
+            vec!['G', 'R', 'Y', 'F', 'F', 'I', 'N', 'D', 'O', 'R']
+        );
+        assert_eq!(house_keys("Hufflepuff").len(), 10);
+        assert_eq!(keeper("hufflepuff"), "Hagrid");
+        assert_eq!(keeper("slytherin"), "Harry");
+        assert!(is_house_key("Slytherin", 'Y'));
+        assert!(is_house_key("Slytherin", WILDCARD));
+        assert!(!is_house_key("Slytherin", 'A'));
+        assert_eq!(key_set(&["Ravenclaw", "Slytherin"], 2).len(), 9 + 9 + 2);
+        assert_eq!(key_set(&["Gryffindor"], 1).last(), Some(&WILDCARD));
+        assert_eq!(
+            key_tiles("Slytherin", &['I', 'N']),
+            vec![
+                ('S', true),
+                ('L', true),
+                ('Y', true),
+                ('T', true),
+                ('H', true),
+                ('E', true),
+                ('R', true),
+                ('I', false),
+                ('N', false),
+            ]
+        );
+        assert_eq!(
+            [1, 2, 3, 4, 11, 12, 21, 102].map(ordinal),
+            ["1st", "2nd", "3rd", "4th", "11th", "12th", "21st", "102nd"]
+        );
+    }
+
+    #[test]
+    fn test_missing_keys() {
+        assert_eq!(missing_keys("Ravenclaw", &[]), house_keys("Ravenclaw"));
+        assert_eq!(
+            missing_keys("Ravenclaw", &['A', 'W', 'R']),
+            vec!['V', 'E', 'N', 'C', 'L', 'A']
+        );
+        // Wildcards stand in for the last letters still missing.
+        assert_eq!(
+            missing_keys("Ravenclaw", &['A', 'W', 'R', WILDCARD, WILDCARD]),
+            vec!['V', 'E', 'N', 'C']
+        );
+        let all: Vec<char> = "SLYTHE".chars().chain([WILDCARD; 3]).collect();
+        assert!(missing_keys("Slytherin", &all).is_empty());
+    }
+
+    #[test]
+    fn test_turn_in_error() {
+        assert_eq!(turn_in_error("Gryffindor", &['F'], 'F'), None);
+        assert_eq!(
+            turn_in_error("Gryffindor", &['F', 'F'], 'F'),
+            Some("Gryffindor has every F already".to_string())
+        );
+        assert_eq!(
+            turn_in_error("Gryffindor", &[], 'W'),
+            Some("W is not a letter of Gryffindor".to_string())
+        );
+        assert_eq!(turn_in_error("Gryffindor", &['F', 'F'], WILDCARD), None);
+
+        // A letter a wildcard stood in for can still be turned in, until the house is done.
+        assert_eq!(turn_in_error("Ravenclaw", &[WILDCARD], 'W'), None);
+        let all = [WILDCARD; 9];
+        assert_eq!(
+            turn_in_error("Ravenclaw", &all, 'W'),
+            Some("Ravenclaw has all its keys already".to_string())
+        );
+    }
+}
@@ -1,15 +1,24 @@
 pub mod app;
+pub mod config;
+pub mod crossword;
+pub mod events;
+pub mod keys;
 pub mod model;
 #[cfg(feature = "ssr")]
 pub mod schema;
+#[cfg(feature = "ssr")]
+pub mod sorting_hat;
+pub mod wordle;
 
 #[cfg(feature = "ssr")]
-use chrono::Utc;
+use chrono::{NaiveDate, Utc};
 #[cfg(feature = "ssr")]
-use diesel::connection::SimpleConnection;
+use diesel::connection::{AnsiTransactionManager, SimpleConnection, TransactionManager};
 #[cfg(feature = "ssr")]
 use diesel::prelude::*;
 #[cfg(feature = "ssr")]
+use diesel::r2d2::CustomizeConnection;
+#[cfg(feature = "ssr")]
 use diesel::SqliteConnection;
 #[cfg(feature = "ssr")]
 use dotenvy::dotenv;
@@ -25,17 +34,38 @@ use std::io::{Error as IoError, ErrorKind};
 #[cfg(feature = "ssr")]
 use uuid::Uuid;
 
+#[cfg(feature = "ssr")]
+use crate::config::HintConfig;
+#[cfg(feature = "ssr")]
+use crate::crossword::Puzzle;
+#[cfg(feature = "ssr")]
+use crate::keys::{
+    house_keys, is_house_key, keeper, key_set, missing_keys, turn_in_error, WILDCARD,
+};
 #[cfg(feature = "ssr")]
 use crate::model::{
-    CrosswordState, DbCrosswordState, Guest, House, HouseCrosswordCompletion, NewAdminSession,
-    NewDbCrosswordState, NewHouseCrosswordCompletion, NewPointAward, NewSession, NewVote,
-    NewVotingStatus, PointAward, PointAwardLog, RcvResult, RcvRound, Vote, VotingStatus,
+    BoardCell, BoardEdit, CellEdit, ClaimStatus, CrosswordHintLog, CrosswordSolveLog,
+    CrosswordState, CrosswordStateUpdate, DbCrosswordState, DbPhysicalKey, DbWordleGame,
+    DbWordleRound, DbWordleRoundGame, Guest, GuestCrossword, HintKind, HintTaken, HorcruxClaim,
+    HorcruxClaimLog, House, HouseBoard, HouseBoardCell, HouseBoardUpdate, HouseCrosswordCompletion,
+    HouseHintUsage, HouseKeys, HouseLeaderboard, KeyClaim, NewAdminSession, NewCrosswordHint,
+    NewCrosswordSolve, NewDbCrosswordState, NewGuest, NewHorcruxClaim, NewHouseBoardCell,
+    NewHouseCrosswordCompletion, NewKeyCompletion, NewPhysicalKey, NewPointAward, NewSession,
+    NewSortingHatDelivery, NewTurnedInKey, NewVote, NewVotingStatus, NewWordleGame, NewWordleRound,
+    NewWordleRoundGame, NewWrongKeyClaim, PhotoUpload, PointAward, PointAwardLog, RcvResult,
+    RcvRound, RosterImportReport, ScoreMismatch, SolverStats, SortingHatDelivery,
+    SortingHatDeliveryLog, SparseState, Vote, VotingStatus, WordleGame, WordleGuess, WordleRace,
+    WordleRaceHouse, WordleRacer, WordleStatus,
 };
 #[cfg(feature = "ssr")]
 use crate::schema::{
-    admin_sessions, crossword_states, guests, house_crossword_completions, houses, point_awards,
-    sessions, votes, voting_status,
+    admin_sessions, crossword_hint_limits, crossword_hints, crossword_solves, crossword_states,
+    guests, horcrux_claims, house_board_cells, house_crossword_completions, houses,
+    key_completions, physical_keys, point_awards, sessions, sorting_hat_deliveries, turned_in_keys,
+    votes, voting_status, wordle_games, wordle_round_games, wordle_rounds, wrong_key_claims,
 };
+#[cfg(feature = "ssr")]
+use crate::wordle::{compute_statuses, hard_mode_error, WordList, MAX_GUESSES, WORD_LENGTH};
 
 #[cfg(feature = "hydrate")]
 #[wasm_bindgen::prelude::wasm_bindgen]
@@ -45,24 +75,131 @@ pub fn hydrate() {
     leptos::mount::hydrate_body(App);
 }
 
+// Enable WAL mode to allow concurrent reads during writes, and a timeout to retry locked operations.
+#[cfg(feature = "ssr")]
+const SQLITE_PRAGMAS: &str = "PRAGMA foreign_keys = ON; \
+    PRAGMA journal_mode = WAL; \
+    PRAGMA synchronous = NORMAL; \
+    PRAGMA busy_timeout = 10000;";
+
 #[cfg(feature = "ssr")]
 pub fn establish_connection() -> SqliteConnection {
     dotenv().ok();
     let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set in .env");
-    let mut conn = SqliteConnection::establish(&database_url)
+    establish_connection_to(&database_url)
+}
+
+/// Connects to the SQLite database at `database_url` and applies `SQLITE_PRAGMAS`.
+#[cfg(feature = "ssr")]
+pub fn establish_connection_to(database_url: &str) -> SqliteConnection {
+    let mut conn = SqliteConnection::establish(database_url)
         .expect(&format!("Error connecting to {}", database_url));
+    conn.batch_execute(SQLITE_PRAGMAS)
+        .expect("Failed to set SQLite PRAGMAs");
+    conn
+}
 
-    // Enable WAL mode to allow concurrent reads during writes, and a timeout to retry locked
-    // operations.
-    conn.batch_execute(
-        "PRAGMA foreign_keys = ON; \
-        PRAGMA journal_mode = WAL; \
-        PRAGMA synchronous = NORMAL; \
-        PRAGMA busy_timeout = 10000;",
-    )
-    .expect("Failed to set SQLite PRAGMAs");
+/// Applies `SQLITE_PRAGMAS` to every connection the pool opens, like `establish_connection` does.
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone, Copy)]
+pub struct SqlitePragmas;
 
-    conn
+#[cfg(feature = "ssr")]
+impl CustomizeConnection<SqliteConnection, diesel::r2d2::Error> for SqlitePragmas {
+    fn on_acquire(&self, conn: &mut SqliteConnection) -> Result<(), diesel::r2d2::Error> {
+        conn.batch_execute(SQLITE_PRAGMAS)
+            .map_err(diesel::r2d2::Error::QueryError)
+    }
+}
+
+/// Runs `f` in a transaction that takes SQLite's write lock up front (`BEGIN IMMEDIATE`), so
+/// concurrent writers queue up behind the busy timeout instead of failing when they try to upgrade
+/// a read lock. Inside an open transaction, `f` runs in a savepoint of that transaction instead.
+#[cfg(feature = "ssr")]
+pub fn write_transaction<T, F>(
+    conn: &mut SqliteConnection,
+    f: F,
+) -> Result<T, diesel::result::Error>
+where
+    F: FnOnce(&mut SqliteConnection) -> Result<T, diesel::result::Error>,
+{
+    let in_transaction = AnsiTransactionManager::transaction_manager_status_mut(conn)
+        .transaction_depth()?
+        .is_some();
+    if in_transaction {
+        conn.transaction(f)
+    } else {
+        conn.immediate_transaction(f)
+    }
+}
+
+/// How the Sorting Hat spreads guests across houses when no house is chosen for them.
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SortingPlan {
+    /// Fractions of the guest list (between 0 and 1) at which a reveal stage ends. While a stage is
+    /// in progress, houses are balanced against that stage's share of the guests rather than the
+    /// whole party, so early arrivals don't pile into one house.
+    pub reveal_stages: Vec<f64>,
+    /// Maximum number of sorted guests per house, keyed by house id. Houses without an entry are
+    /// uncapped. Caps only constrain the Sorting Hat; an admin can still pick a full house.
+    pub house_caps: HashMap<i32, usize>,
+}
+
+#[cfg(feature = "ssr")]
+impl Default for SortingPlan {
+    /// Balances the first half of the party, then the whole party.
+    fn default() -> Self {
+        Self {
+            reveal_stages: vec![0.5],
+            house_caps: HashMap::new(),
+        }
+    }
+}
+
+/// Computes how many guests each house should hold at the current reveal stage.
+///
+/// `current_counts` holds the number of guests already sorted into each house, and `caps` the
+/// optional per-house maximum at the same index (missing entries are uncapped). The stage size is
+/// the first reveal threshold (`reveal_stages` as fractions of `total_guests`) above the number of
+/// guests sorted so far, or `total_guests` once every threshold has passed. That many places are
+/// then dealt out one at a time to the house with the fewest places that still has room, ties going
+/// to the house with fewer guests right now and then to the lower index, so targets never differ by
+/// more than one between uncapped houses.
+/// If every house hits its cap, the targets sum to less than the stage size.
+#[cfg(feature = "ssr")]
+pub fn compute_house_targets(
+    total_guests: usize,
+    current_counts: &[usize],
+    reveal_stages: &[f64],
+    caps: &[Option<usize>],
+) -> Vec<usize> {
+    let sorted_so_far: usize = current_counts.iter().sum();
+    let mut thresholds: Vec<usize> = reveal_stages
+        .iter()
+        .map(|fraction| (fraction * total_guests as f64).floor() as usize)
+        .filter(|&threshold| threshold > 0 && threshold < total_guests)
+        .collect();
+    thresholds.sort_unstable();
+    let stage_size = thresholds
+        .into_iter()
+        .find(|&threshold| threshold > sorted_so_far)
+        .unwrap_or(total_guests);
+
+    let mut targets = vec![0usize; current_counts.len()];
+    for _ in 0..stage_size {
+        let next_house = (0..targets.len())
+            .filter(|&idx| match caps.get(idx).copied().flatten() {
+                Some(cap) => targets[idx] < cap,
+                None => true,
+            })
+            .min_by_key(|&idx| (targets[idx], current_counts[idx], idx));
+        match next_house {
+            Some(idx) => targets[idx] += 1,
+            None => break,
+        }
+    }
+    targets
 }
 
 /// Registers a guest by ID (prepopulated unregistered guest), assigns them to a house, sets their
@@ -75,6 +212,19 @@ pub fn register_guest(
     guest_id: i32,
     house_id: Option<i32>,
     character: &str,
+) -> Result<(Guest, String), diesel::result::Error> {
+    register_guest_with_plan(conn, guest_id, house_id, character, &SortingPlan::default())
+}
+
+/// Same as `register_guest`, but lets the caller control how the Sorting Hat balances houses when
+/// `house_id` is `None`.
+#[cfg(feature = "ssr")]
+pub fn register_guest_with_plan(
+    conn: &mut SqliteConnection,
+    guest_id: i32,
+    house_id: Option<i32>,
+    character: &str,
+    plan: &SortingPlan,
 ) -> Result<(Guest, String), diesel::result::Error> {
     conn.transaction(|conn| {
         // Fetch the existing guest and ensure it's inactive.
@@ -98,66 +248,54 @@ pub fn register_guest(
             }
             provided_house_id
         } else {
-            // Assert that we're working with 37 guests, for simplicity.
-            let total_guests: i64 = guests::table.count().get_result(conn)?;
-            if total_guests != 37 {
-                return Err(diesel::result::Error::QueryBuilderError(Box::new(
-                    IoError::new(
-                        ErrorKind::Other,
-                        "Expected exactly 37 guests in the database",
-                    ),
-                )));
-            }
-
             // Based on how many have been sorted, determine how many we're targeting in each
             // house.
-            let sorted_so_far: i64 = guests::table
-                .filter(guests::is_active.eq(1i32))
-                .count()
-                .get_result(conn)?;
-            let targets: Vec<i64> = if sorted_so_far < 18 {
-                vec![4, 5, 5, 4]
-            } else {
-                vec![10, 9, 9, 9]
-            };
-
-            // Load the house ids in order.
+            let total_guests: i64 = guests::table.count().get_result(conn)?;
             let house_ids: Vec<i32> = houses::table
                 .select(houses::id)
                 .order(houses::id.asc())
                 .load(conn)?;
-            if house_ids.len() != 4 {
+            if house_ids.is_empty() {
                 return Err(diesel::result::Error::QueryBuilderError(Box::new(
-                    IoError::new(ErrorKind::Other, "Expected exactly 4 houses"),
+                    IoError::new(ErrorKind::Other, "No houses to sort into"),
                 )));
             }
 
             // Compute current counts for each house, and subsequently, the remaining spots in each
             // house.
-            let mut current_counts: Vec<i64> = Vec::new();
+            let mut current_counts: Vec<usize> = Vec::new();
             for &house_id in &house_ids {
                 let count: i64 = guests::table
                     .filter(guests::is_active.eq(1i32))
                     .filter(guests::house_id.eq(Some(house_id)))
                     .count()
                     .get_result(conn)?;
-                current_counts.push(count);
+                current_counts.push(count as usize);
             }
-            let remainings: Vec<i64> = targets
+            let caps: Vec<Option<usize>> = house_ids
+                .iter()
+                .map(|house_id| plan.house_caps.get(house_id).copied())
+                .collect();
+            let targets = compute_house_targets(
+                total_guests as usize,
+                &current_counts,
+                &plan.reveal_stages,
+                &caps,
+            );
+            let remainings: Vec<usize> = targets
                 .iter()
                 .zip(current_counts.iter())
-                .map(|(&target, &current)| (target - current).max(0))
+                .map(|(&target, &current)| target.saturating_sub(current))
                 .collect();
+            if remainings.iter().all(|&remaining| remaining == 0) {
+                return Err(diesel::result::Error::QueryBuilderError(Box::new(
+                    IoError::new(ErrorKind::Other, "All houses are full"),
+                )));
+            }
 
             // Create a distribution of the houses weighted by the number of spots left in each
             // house.
-            let dist = WeightedIndex::new(
-                remainings
-                    .iter()
-                    .map(|&w| w as usize)
-                    .collect::<Vec<usize>>(),
-            )
-            .map_err(|e| {
+            let dist = WeightedIndex::new(&remainings).map_err(|e| {
                 diesel::result::Error::QueryBuilderError(Box::new(IoError::new(
                     ErrorKind::Other,
                     format!("WeightedIndex error: {}", e),
@@ -237,6 +375,167 @@ pub fn get_all_unregistered_guests(
         .load(conn)
 }
 
+/// Longest guest name accepted when importing a roster.
+#[cfg(feature = "ssr")]
+pub const MAX_GUEST_NAME_LEN: usize = 64;
+
+/// Parses a guest roster file into a list of raw names.
+///
+/// Two formats are accepted:
+/// - JSON: an array of names (`["Leila S", "Gautam A"]`) or of objects with a `name` field
+///   (`[{"name": "Leila S"}]`).
+/// - CSV: one guest per line, with the name in the first column. An optional `name` header row,
+///   blank lines and lines starting with `#` are skipped. Fields may be double-quoted.
+///
+/// Names are returned as-is (validation happens in `import_guest_roster`).
+#[cfg(feature = "ssr")]
+pub fn parse_roster(contents: &str) -> Result<Vec<String>, String> {
+    let trimmed = contents.trim_start_matches('\u{feff}').trim();
+    if trimmed.starts_with('[') {
+        let entries: Vec<serde_json::Value> =
+            serde_json::from_str(trimmed).map_err(|e| format!("Invalid JSON roster: {}", e))?;
+        return entries
+            .into_iter()
+            .enumerate()
+            .map(|(idx, entry)| match entry {
+                serde_json::Value::String(name) => Ok(name),
+                serde_json::Value::Object(mut fields) => match fields.remove("name") {
+                    Some(serde_json::Value::String(name)) => Ok(name),
+                    _ => Err(format!("Entry {} has no string \"name\" field", idx + 1)),
+                },
+                _ => Err(format!(
+                    "Entry {} must be a string or an object with a \"name\" field",
+                    idx + 1
+                )),
+            })
+            .collect();
+    }
+
+    let mut names = Vec::new();
+    for (line_idx, line) in trimmed.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        // Take the first column, honouring double quotes so names may contain commas.
+        let name = if let Some(rest) = line.strip_prefix('"') {
+            let mut name = String::new();
+            let mut chars = rest.chars().peekable();
+            let mut closed = false;
+            while let Some(c) = chars.next() {
+                if c == '"' {
+                    if chars.peek() == Some(&'"') {
+                        name.push('"');
+                        chars.next();
+                    } else {
+                        closed = true;
+                        break;
+                    }
+                } else {
+                    name.push(c);
+                }
+            }
+            if !closed {
+                return Err(format!("Unterminated quote on line {}", line_idx + 1));
+            }
+            name
+        } else {
+            line.split(',').next().unwrap_or_default().to_string()
+        };
+
+        // Skip a header row.
+        if names.is_empty() && name.trim().eq_ignore_ascii_case("name") {
+            continue;
+        }
+        names.push(name);
+    }
+
+    Ok(names)
+}
+
+/// Normalizes a guest name for storage: trims it and collapses internal runs of whitespace.
+#[cfg(feature = "ssr")]
+fn normalize_guest_name(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Imports a roster of guest names as unregistered guests.
+///
+/// Names are normalized (trimmed, whitespace collapsed) and compared case-insensitively against
+/// each other and against every guest already in the database; duplicates are skipped and reported.
+/// If any entry is invalid (empty or longer than `MAX_GUEST_NAME_LEN`), nothing is imported and the
+/// offending entries are reported instead.
+/// When `replace` is set, guests that have never been registered are deleted first, so the roster
+/// replaces the previous party's list without touching anyone who has already been sorted.
+#[cfg(feature = "ssr")]
+pub fn import_guest_roster(
+    conn: &mut SqliteConnection,
+    names: &[String],
+    replace: bool,
+) -> Result<RosterImportReport, diesel::result::Error> {
+    conn.transaction(|conn| {
+        let mut report = RosterImportReport::default();
+
+        // Validate every entry up front so a bad file doesn't leave a partial import behind.
+        let mut candidates = Vec::new();
+        for (idx, raw_name) in names.iter().enumerate() {
+            let name = normalize_guest_name(raw_name);
+            if name.is_empty() {
+                report
+                    .invalid
+                    .push(format!("Entry {}: name is empty", idx + 1));
+            } else if name.chars().count() > MAX_GUEST_NAME_LEN {
+                report.invalid.push(format!(
+                    "Entry {}: \"{}\" is longer than {} characters",
+                    idx + 1,
+                    name,
+                    MAX_GUEST_NAME_LEN
+                ));
+            } else {
+                candidates.push(name);
+            }
+        }
+        if !report.invalid.is_empty() {
+            return Ok(report);
+        }
+
+        if replace {
+            report.removed = diesel::delete(
+                guests::table
+                    .filter(guests::is_active.eq(0i32))
+                    .filter(guests::registered_at.is_null()),
+            )
+            .execute(conn)?;
+        }
+
+        let mut seen: HashSet<String> = guests::table
+            .select(guests::name)
+            .load::<String>(conn)?
+            .iter()
+            .map(|name| normalize_guest_name(name).to_lowercase())
+            .collect();
+
+        for name in candidates {
+            if !seen.insert(name.to_lowercase()) {
+                report.duplicates.push(name);
+                continue;
+            }
+            diesel::insert_into(guests::table)
+                .values(&NewGuest {
+                    name: &name,
+                    house_id: None,
+                    character: None,
+                    registered_at: None,
+                })
+                .execute(conn)?;
+            report.imported.push(name);
+        }
+
+        Ok(report)
+    })
+}
+
 /// Unregisters a guest, deletes sessions associated with that guest.
 /// Returns number of affected rows.
 #[cfg(feature = "ssr")]
@@ -330,8 +629,8 @@ pub fn reregister_guest(
     })
 }
 
-/// Awards or deducts points to a guest. Updates both the guest's personal score and the house
-/// score, and logs the award.
+/// Awards or deducts points to a guest. Updates both the guest's personal score and the score of
+/// the guest's current house, and logs the award with both.
 #[cfg(feature = "ssr")]
 pub fn award_points_to_guest(
     conn: &mut SqliteConnection,
@@ -339,7 +638,7 @@ pub fn award_points_to_guest(
     amount: i32,
     reason: &str,
 ) -> Result<PointAward, diesel::result::Error> {
-    conn.transaction(|conn| {
+    write_transaction(conn, |conn| {
         // Fetch the active guest first.
         let guest: Guest = guests::table
             .filter(guests::id.eq(guest_id))
@@ -350,29 +649,22 @@ pub fn award_points_to_guest(
         // Ensure the guest has a house assigned (for active guests).
         let house_id = guest.house_id.ok_or(diesel::result::Error::NotFound)?;
 
-        // Fetch the house.
-        let house: House = houses::table
-            .filter(houses::id.eq(house_id))
-            .select(House::as_select())
-            .first(conn)?;
-
-        // Update the guest's personal score.
+        // Update the guest's personal score and the house score. Both are incremented in SQL so
+        // concurrent awards can't overwrite each other.
         diesel::update(guests::table.filter(guests::id.eq(guest_id)))
-            .set(guests::personal_score.eq(guest.personal_score + amount))
-            .execute(conn)?;
-
-        // Update the house score.
-        diesel::update(houses::table.filter(houses::id.eq(house.id)))
-            .set(houses::score.eq(house.score + amount))
+            .set(guests::personal_score.eq(guests::personal_score + amount))
             .execute(conn)?;
+        add_to_house_score(conn, house_id, amount)?;
 
-        // Log the award.
+        // Log the award against the guest's current house, so it keeps counting for that house if
+        // the guest is re-sorted later.
         let new_award = NewPointAward {
             guest_id: Some(guest_id),
-            house_id: None,
+            house_id: Some(house_id),
             amount,
             reason: reason.to_string(),
             awarded_at: Utc::now().naive_utc(),
+            reverts_award_id: None,
         };
         diesel::insert_into(point_awards::table)
             .values(&new_award)
@@ -388,15 +680,8 @@ pub fn award_points_to_house(
     amount: i32,
     reason: &str,
 ) -> Result<PointAward, diesel::result::Error> {
-    conn.transaction(|conn| {
-        let house: House = houses::table
-            .filter(houses::id.eq(house_id))
-            .select(House::as_select())
-            .first(conn)?;
-
-        diesel::update(houses::table.filter(houses::id.eq(house_id)))
-            .set(houses::score.eq(house.score + amount))
-            .execute(conn)?;
+    write_transaction(conn, |conn| {
+        add_to_house_score(conn, house_id, amount)?;
 
         let new_award = NewPointAward {
             guest_id: None,
@@ -404,6 +689,7 @@ pub fn award_points_to_house(
             amount,
             reason: reason.to_string(),
             awarded_at: Utc::now().naive_utc(),
+            reverts_award_id: None,
         };
         diesel::insert_into(point_awards::table)
             .values(&new_award)
@@ -411,6 +697,168 @@ pub fn award_points_to_house(
     })
 }
 
+/// Adds `amount` to a house's score in a single `UPDATE`. Fails with `NotFound` if there is no
+/// such house.
+#[cfg(feature = "ssr")]
+fn add_to_house_score(
+    conn: &mut SqliteConnection,
+    house_id: i32,
+    amount: i32,
+) -> Result<(), diesel::result::Error> {
+    let updated = diesel::update(houses::table.filter(houses::id.eq(house_id)))
+        .set(houses::score.eq(houses::score + amount))
+        .execute(conn)?;
+    if updated == 0 {
+        return Err(diesel::result::Error::NotFound);
+    }
+    Ok(())
+}
+
+/// Reverts a point award by logging a linked entry for the opposite amount and taking the points
+/// back from the same guest and house. Guest awards logged without a house (before awards recorded
+/// one) are taken from the guest's current house instead. Reversals themselves can't be reverted (award the
+/// points again instead), and each award can only be reverted once. Returns the reversing entry.
+#[cfg(feature = "ssr")]
+pub fn revert_point_award(
+    conn: &mut SqliteConnection,
+    award_id: i32,
+) -> Result<PointAward, diesel::result::Error> {
+    write_transaction(conn, |conn| {
+        let award: PointAward = point_awards::table
+            .filter(point_awards::id.eq(award_id))
+            .select(PointAward::as_select())
+            .first(conn)?;
+        if award.reverts_award_id.is_some() {
+            return Err(diesel::result::Error::QueryBuilderError(Box::new(
+                IoError::other("A reversal can't be reverted"),
+            )));
+        }
+        let already_reverted: i64 = point_awards::table
+            .filter(point_awards::reverts_award_id.eq(award_id))
+            .count()
+            .get_result(conn)?;
+        if already_reverted > 0 {
+            return Err(diesel::result::Error::QueryBuilderError(Box::new(
+                IoError::other("Award has already been reverted"),
+            )));
+        }
+
+        // Take the points back from the guest, and from the house the award counted for.
+        let mut house_id = award.house_id;
+        if let Some(guest_id) = award.guest_id {
+            let guest_house_id: Option<i32> =
+                diesel::update(guests::table.filter(guests::id.eq(guest_id)))
+                    .set(guests::personal_score.eq(guests::personal_score - award.amount))
+                    .returning(guests::house_id)
+                    .get_result(conn)?;
+            house_id = house_id.or(guest_house_id);
+        }
+        if let Some(house_id) = house_id {
+            add_to_house_score(conn, house_id, -award.amount)?;
+        }
+
+        let reversal = NewPointAward {
+            guest_id: award.guest_id,
+            house_id,
+            amount: -award.amount,
+            reason: format!("Reverted: {}", award.reason),
+            awarded_at: Utc::now().naive_utc(),
+            reverts_award_id: Some(award.id),
+        };
+        diesel::insert_into(point_awards::table)
+            .values(&reversal)
+            .get_result(conn)
+    })
+}
+
+/// Recomputes every house and guest score from the point award ledger and returns the cached
+/// scores that disagree with it, houses first. Awards count for the house they were logged with;
+/// guest awards logged without one count for the guest's current house. Doesn't change anything;
+/// see `reconcile_scores`.
+#[cfg(feature = "ssr")]
+pub fn verify_scores(
+    conn: &mut SqliteConnection,
+) -> Result<Vec<ScoreMismatch>, diesel::result::Error> {
+    conn.transaction(|conn| {
+        let awards: Vec<(Option<i32>, Option<i32>, i32)> = point_awards::table
+            .select((
+                point_awards::guest_id,
+                point_awards::house_id,
+                point_awards::amount,
+            ))
+            .load(conn)?;
+        let all_guests: Vec<Guest> = guests::table.select(Guest::as_select()).load(conn)?;
+        let guest_houses: HashMap<i32, Option<i32>> = all_guests
+            .iter()
+            .map(|guest| (guest.id, guest.house_id))
+            .collect();
+
+        let mut house_totals: HashMap<i32, i32> = HashMap::new();
+        let mut guest_totals: HashMap<i32, i32> = HashMap::new();
+        for (guest_id, house_id, amount) in awards {
+            if let Some(guest_id) = guest_id {
+                *guest_totals.entry(guest_id).or_insert(0) += amount;
+            }
+            let house_id = house_id
+                .or_else(|| guest_id.and_then(|id| guest_houses.get(&id).copied().flatten()));
+            if let Some(house_id) = house_id {
+                *house_totals.entry(house_id).or_insert(0) += amount;
+            }
+        }
+
+        let mut mismatches = Vec::new();
+        for house in get_all_houses(conn)? {
+            let ledger = house_totals.get(&house.id).copied().unwrap_or(0);
+            if house.score != ledger {
+                mismatches.push(ScoreMismatch {
+                    house_id: Some(house.id),
+                    guest_id: None,
+                    name: house.name,
+                    cached: house.score,
+                    ledger,
+                });
+            }
+        }
+        for guest in all_guests {
+            let ledger = guest_totals.get(&guest.id).copied().unwrap_or(0);
+            if guest.personal_score != ledger {
+                mismatches.push(ScoreMismatch {
+                    house_id: None,
+                    guest_id: Some(guest.id),
+                    name: guest.name,
+                    cached: guest.personal_score,
+                    ledger,
+                });
+            }
+        }
+        Ok(mismatches)
+    })
+}
+
+/// Overwrites every cached score that disagrees with the point award ledger with the ledger total.
+/// Returns the mismatches that were fixed, as `verify_scores` reported them.
+#[cfg(feature = "ssr")]
+pub fn reconcile_scores(
+    conn: &mut SqliteConnection,
+) -> Result<Vec<ScoreMismatch>, diesel::result::Error> {
+    write_transaction(conn, |conn| {
+        let mismatches = verify_scores(conn)?;
+        for mismatch in &mismatches {
+            if let Some(house_id) = mismatch.house_id {
+                diesel::update(houses::table.filter(houses::id.eq(house_id)))
+                    .set(houses::score.eq(mismatch.ledger))
+                    .execute(conn)?;
+            }
+            if let Some(guest_id) = mismatch.guest_id {
+                diesel::update(guests::table.filter(guests::id.eq(guest_id)))
+                    .set(guests::personal_score.eq(mismatch.ledger))
+                    .execute(conn)?;
+            }
+        }
+        Ok(mismatches)
+    })
+}
+
 /// Creates an admin session and returns the token.
 #[cfg(feature = "ssr")]
 pub fn create_admin_session(conn: &mut SqliteConnection) -> Result<String, diesel::result::Error> {
@@ -460,9 +908,42 @@ pub fn get_guest_token(
 pub fn get_all_point_awards(
     conn: &mut SqliteConnection,
 ) -> Result<Vec<PointAwardLog>, diesel::result::Error> {
-    point_awards::table
+    load_point_award_logs(conn, None, None)
+}
+
+/// Returns the `limit` most recent point awards, like `get_all_point_awards`.
+#[cfg(feature = "ssr")]
+pub fn get_recent_point_awards(
+    conn: &mut SqliteConnection,
+    limit: i64,
+) -> Result<Vec<PointAwardLog>, diesel::result::Error> {
+    load_point_award_logs(conn, None, Some(limit))
+}
+
+/// Returns the point awards given to one guest, newest first.
+#[cfg(feature = "ssr")]
+pub fn get_guest_point_awards(
+    conn: &mut SqliteConnection,
+    guest_id: i32,
+) -> Result<Vec<PointAwardLog>, diesel::result::Error> {
+    load_point_award_logs(conn, Some(guest_id), None)
+}
+
+#[cfg(feature = "ssr")]
+fn load_point_award_logs(
+    conn: &mut SqliteConnection,
+    guest_id: Option<i32>,
+    limit: Option<i64>,
+) -> Result<Vec<PointAwardLog>, diesel::result::Error> {
+    let reversals = diesel::alias!(point_awards as reversals);
+    let mut query = point_awards::table
         .left_join(guests::table.on(point_awards::guest_id.eq(guests::id.nullable())))
         .left_join(houses::table.on(point_awards::house_id.eq(houses::id.nullable())))
+        .left_join(
+            reversals.on(reversals
+                .field(point_awards::reverts_award_id)
+                .eq(point_awards::id.nullable())),
+        )
         .select((
             point_awards::id,
             guests::name.nullable(),
@@ -470,35 +951,45 @@ pub fn get_all_point_awards(
             point_awards::amount,
             point_awards::reason,
             point_awards::awarded_at,
+            point_awards::reverts_award_id,
+            reversals.field(point_awards::id).nullable(),
         ))
-        .order(point_awards::awarded_at.desc())
-        .load(conn)
+        .order((point_awards::awarded_at.desc(), point_awards::id.desc()))
+        .into_boxed();
+    if let Some(guest_id) = guest_id {
+        query = query.filter(point_awards::guest_id.eq(guest_id));
+    }
+    if let Some(limit) = limit {
+        query = query.limit(limit);
+    }
+    query.load(conn)
 }
 
 /// Fetches the crossword completion progress for all houses.
-/// Returns a 4x7 boolean matrix: rows = houses (0=Gryffindor/id1, 1=Hufflepuff/id2, 2=Ravenclaw/id3, 3=Slytherin/id4),
-/// columns = words (0-6). true if house has completed that word.
+/// Returns a boolean matrix with one row per house, in the same order as `get_all_houses`, and one
+/// column per word of a puzzle with `word_count` words. true if house has completed that word.
 #[cfg(feature = "ssr")]
 pub fn get_house_crossword_progress(
     conn: &mut SqliteConnection,
+    word_count: usize,
 ) -> Result<Vec<Vec<bool>>, diesel::result::Error> {
+    let house_ids: Vec<i32> = houses::table
+        .order(houses::name)
+        .select(houses::id)
+        .load(conn)?;
     let completions: Vec<HouseCrosswordCompletion> = house_crossword_completions::table
         .inner_join(houses::table.on(house_crossword_completions::house_id.eq(houses::id)))
         .select(HouseCrosswordCompletion::as_select())
         .load(conn)?;
 
-    let mut matrix: Vec<Vec<bool>> = vec![vec![false; 7]; 4];
+    let mut matrix: Vec<Vec<bool>> = vec![vec![false; word_count]; house_ids.len()];
 
     for completion in completions {
-        let house_idx = match completion.house_id {
-            1 => 0,
-            2 => 1,
-            3 => 2,
-            4 => 3,
-            _ => continue,
+        let Some(house_idx) = house_ids.iter().position(|&id| id == completion.house_id) else {
+            continue;
         };
         let word_idx = completion.word_index as usize;
-        if word_idx < 7 {
+        if word_idx < word_count {
             matrix[house_idx][word_idx] = true;
         }
     }
@@ -552,6 +1043,117 @@ pub fn get_all_active_guests(
         .load(conn)
 }
 
+/// Fetches a house by id.
+#[cfg(feature = "ssr")]
+pub fn get_house(
+    conn: &mut SqliteConnection,
+    house_id: i32,
+) -> Result<House, diesel::result::Error> {
+    houses::table
+        .filter(houses::id.eq(house_id))
+        .select(House::as_select())
+        .first(conn)
+}
+
+/// Sorting Hat delivery states, stored in `sorting_hat_deliveries.status`.
+#[cfg(feature = "ssr")]
+pub const DELIVERY_PENDING: &str = "pending";
+#[cfg(feature = "ssr")]
+pub const DELIVERY_DELIVERED: &str = "delivered";
+#[cfg(feature = "ssr")]
+pub const DELIVERY_FAILED: &str = "failed";
+
+/// Logs a new pending request for the Sorting Hat to announce a house. `guest_id` is the guest who
+/// was sorted, if any.
+#[cfg(feature = "ssr")]
+pub fn create_sorting_hat_delivery(
+    conn: &mut SqliteConnection,
+    guest_id: Option<i32>,
+    house_id: i32,
+) -> Result<SortingHatDelivery, diesel::result::Error> {
+    diesel::insert_into(sorting_hat_deliveries::table)
+        .values(&NewSortingHatDelivery { guest_id, house_id })
+        .returning(SortingHatDelivery::as_returning())
+        .get_result(conn)
+}
+
+/// Fetches a Sorting Hat delivery by id.
+#[cfg(feature = "ssr")]
+pub fn get_sorting_hat_delivery(
+    conn: &mut SqliteConnection,
+    delivery_id: i32,
+) -> Result<SortingHatDelivery, diesel::result::Error> {
+    sorting_hat_deliveries::table
+        .filter(sorting_hat_deliveries::id.eq(delivery_id))
+        .select(SortingHatDelivery::as_select())
+        .first(conn)
+}
+
+/// Records one attempt at a pending delivery. `error` is `None` if the hat acknowledged the request.
+/// A failed attempt leaves the delivery pending unless `give_up` is set, in which case it is marked
+/// failed. Returns the updated delivery.
+#[cfg(feature = "ssr")]
+pub fn record_sorting_hat_attempt(
+    conn: &mut SqliteConnection,
+    delivery_id: i32,
+    error: Option<&str>,
+    give_up: bool,
+) -> Result<SortingHatDelivery, diesel::result::Error> {
+    let status = match (error, give_up) {
+        (None, _) => DELIVERY_DELIVERED,
+        (Some(_), false) => DELIVERY_PENDING,
+        (Some(_), true) => DELIVERY_FAILED,
+    };
+    diesel::update(
+        sorting_hat_deliveries::table
+            .filter(sorting_hat_deliveries::id.eq(delivery_id))
+            .filter(sorting_hat_deliveries::status.eq(DELIVERY_PENDING)),
+    )
+    .set((
+        sorting_hat_deliveries::status.eq(status),
+        sorting_hat_deliveries::attempts.eq(sorting_hat_deliveries::attempts + 1),
+        sorting_hat_deliveries::last_error.eq(error),
+        sorting_hat_deliveries::updated_at.eq(Utc::now().naive_utc()),
+    ))
+    .returning(SortingHatDelivery::as_returning())
+    .get_result(conn)
+}
+
+/// Fetches all pending deliveries, oldest first.
+#[cfg(feature = "ssr")]
+pub fn get_pending_sorting_hat_deliveries(
+    conn: &mut SqliteConnection,
+) -> Result<Vec<SortingHatDelivery>, diesel::result::Error> {
+    sorting_hat_deliveries::table
+        .filter(sorting_hat_deliveries::status.eq(DELIVERY_PENDING))
+        .order(sorting_hat_deliveries::id)
+        .select(SortingHatDelivery::as_select())
+        .load(conn)
+}
+
+/// Returns the most recent `limit` deliveries with guest and house names, newest first.
+#[cfg(feature = "ssr")]
+pub fn get_sorting_hat_deliveries(
+    conn: &mut SqliteConnection,
+    limit: i64,
+) -> Result<Vec<SortingHatDeliveryLog>, diesel::result::Error> {
+    sorting_hat_deliveries::table
+        .inner_join(houses::table)
+        .left_join(guests::table)
+        .select((
+            sorting_hat_deliveries::id,
+            guests::name.nullable(),
+            houses::name,
+            sorting_hat_deliveries::status,
+            sorting_hat_deliveries::attempts,
+            sorting_hat_deliveries::last_error,
+            sorting_hat_deliveries::updated_at,
+        ))
+        .order(sorting_hat_deliveries::id.desc())
+        .limit(limit)
+        .load(conn)
+}
+
 /// Resets the entire database to its initial state.
 #[cfg(feature = "ssr")]
 pub fn reset_database(conn: &mut SqliteConnection) -> Result<(), diesel::result::Error> {
@@ -560,18 +1162,33 @@ pub fn reset_database(conn: &mut SqliteConnection) -> Result<(), diesel::result:
         diesel::delete(sessions::table).execute(conn)?;
         diesel::delete(admin_sessions::table).execute(conn)?;
 
-        // Delete all point awards.
+        // Delete all crossword hints, horcrux claims, Wordle games and races, Catch the Keys
+        // finishes, and point awards.
+        diesel::delete(crossword_hints::table).execute(conn)?;
+        diesel::delete(horcrux_claims::table).execute(conn)?;
+        diesel::delete(wordle_games::table).execute(conn)?;
+        diesel::delete(wordle_round_games::table).execute(conn)?;
+        diesel::delete(wordle_rounds::table).execute(conn)?;
+        diesel::delete(key_completions::table).execute(conn)?;
         diesel::delete(point_awards::table).execute(conn)?;
 
-        // Delete all guest crossword states.
+        // Delete all guest crossword states and solves.
+        diesel::delete(crossword_solves::table).execute(conn)?;
         diesel::delete(crossword_states::table).execute(conn)?;
 
-        // Delete all house crossword completion entries.
+        // Delete all house crossword completion entries and house boards.
         diesel::delete(house_crossword_completions::table).execute(conn)?;
+        diesel::delete(house_board_cells::table).execute(conn)?;
+
+        // Delete all turned-in keys, which frees the physical keys to be claimed again.
+        diesel::delete(turned_in_keys::table).execute(conn)?;
 
         // Delete all votes.
         diesel::delete(votes::table).execute(conn)?;
 
+        // Delete the Sorting Hat delivery log.
+        diesel::delete(sorting_hat_deliveries::table).execute(conn)?;
+
         // Reset voting status.
         diesel::update(voting_status::table)
             .set((
@@ -601,200 +1218,1727 @@ pub fn reset_database(conn: &mut SqliteConnection) -> Result<(), diesel::result:
     })
 }
 
-#[derive(Clone, Copy, Debug)]
-enum Direction {
-    Across,
-    Down,
-}
-
-#[derive(Clone, Debug)]
-struct WordDef {
-    start_row: usize,
-    start_col: usize,
-    len: usize,
-    dir: Direction,
-    answer: &'static str,
-    reveal_text: &'static str,
-    location: &'static str,
-    horcrux: &'static str,
-}
-
-const CROSSWORD_DEFS: &[WordDef] = &[
-    WordDef {
-        start_row: 1,
-        start_col: 1,
-        len: 5,
-        dir: Direction::Across,
-        answer: "WINKY",
-        reveal_text: "Behind a door where secrets sleep,\nI slither low, my watch I keep.\nNo voice, no spell, just breath and skin,\nThe darkness stirs, I wait within.",
-        location: "Newspaper",
-        horcrux: "Nagini",
-    },
-    WordDef {
-        start_row: 6,
-        start_col: 0,
-        len: 12,
-        dir: Direction::Across,
-        answer: "EXPELLIARMUS",
-        reveal_text: "Where portraits purr in rose-tinted frame,\nI nest in her china, igniting no flame.\nEmblem of lineage, cold and entwined,\nI whisper old venom, twisting the mind.",
-        location: "Room of Req",
-        horcrux: "Locket",
-    },
-    WordDef {
-        start_row: 2,
-        start_col: 0,
-        len: 10,
-        dir: Direction::Down,
-        answer: "DISSENDIUM",
-        reveal_text: "With lemon drops and half-moon gaze,\nI unravel riddles through misty haze.\nFrom elder's core, my power flows,\nShepherding souls where the wild wind blows.",
-        location: "Azkaban",
-        horcrux: "Ring",
-    },
-    WordDef {
-        start_row: 0,
-        start_col: 3,
-        len: 8,
-        dir: Direction::Down,
-        answer: "SNUFFLES",
-        reveal_text: "Among the sweets where riches gleam,\nA creature guards its golden dream.\nWhat glitters bright in sugared hue,\nConceals the pride of those loyal and true.",
-        location: "Bathroom",
-        horcrux: "Cup",
-    },
-    WordDef {
-        start_row: 5,
-        start_col: 6,
-        len: 10,
-        dir: Direction::Down,
-        answer: "SIRCADOGAN",
-        reveal_text: "You've shown your courage and wit so true,\nNow red and gold shall welcome you.\nStep through - but hush, for what you seek\nHolds words that whisper, though their writer can't speak.",
-        location: "Dept of Mysteries",
-        horcrux: "Diary",
-    },
-    WordDef {
-        start_row: 3,
-        start_col: 8,
-        len: 9,
-        dir: Direction::Down,
-        answer: "BOARHOUND",
-        reveal_text: "At the threshold where paths align,\nCloak, wand, and stone combine.\nThrough death I passed, through love restored,\nNow hang I here at fate's own door.",
-        location: "Photo frame",
-        horcrux: "Harry",
-    },
-    WordDef {
-        start_row: 1,
-        start_col: 10,
-        len: 7,
-        dir: Direction::Down,
-        answer: "IGNOTUS",
-        reveal_text: "\"Wit beyond measure\" once was prized,\nNow in your clutter, undisguised.\nAmong the things you cast aside,\nThe clever crown still tries to hide.",
-        location: "Mirror",
-        horcrux: "Diadem",
-    },
-];
-
 /// Fetches the crossword state for a guest, or inserts an empty one if it doesn't exist, and
 /// returns it.
 #[cfg(feature = "ssr")]
 pub fn get_or_init_crossword_state(
     conn: &mut SqliteConnection,
+    puzzle: &Puzzle,
     guest_id: i32,
 ) -> Result<CrosswordState, diesel::result::Error> {
-    let existing: Option<DbCrosswordState> = crossword_states::table
-        .filter(crossword_states::guest_id.eq(guest_id))
-        .first(conn)
+    let db_state = get_or_init_db_crossword_state(conn, puzzle, guest_id)?;
+    let mut state = CrosswordState::from_json(&db_state.state, puzzle);
+    state.version = db_state.version;
+    Ok(state)
+}
+
+#[cfg(feature = "ssr")]
+fn get_or_init_db_crossword_state(
+    conn: &mut SqliteConnection,
+    puzzle: &Puzzle,
+    guest_id: i32,
+) -> Result<DbCrosswordState, diesel::result::Error> {
+    let existing: Option<DbCrosswordState> = crossword_states::table
+        .filter(crossword_states::guest_id.eq(guest_id))
+        .first(conn)
+        .optional()?;
+    if let Some(db_state) = existing {
+        return Ok(db_state);
+    }
+
+    let initial_state =
+        CrosswordState::new_full_grid(puzzle.empty_grid(), vec![false; puzzle.words.len()]);
+    let now = chrono::Utc::now().naive_utc();
+    let new_db_state = NewDbCrosswordState {
+        guest_id,
+        state: initial_state.into(),
+        updated_at: now,
+        started_at: Some(now),
+    };
+    diesel::insert_into(crossword_states::table)
+        .values(&new_db_state)
+        .get_result(conn)
+}
+
+/// Fetches the house of an active guest. Guests who are inactive or unsorted are not found.
+#[cfg(feature = "ssr")]
+fn active_guest_house_id(
+    conn: &mut SqliteConnection,
+    guest_id: i32,
+) -> Result<i32, diesel::result::Error> {
+    let guest: Guest = guests::table
+        .filter(guests::id.eq(guest_id))
+        .filter(guests::is_active.eq(1i32))
+        .select(Guest::as_select())
+        .first(conn)?;
+    guest.house_id.ok_or(diesel::result::Error::NotFound)
+}
+
+/// Applies a guest's edits to their own crossword.
+///
+/// Words the edits complete score for the guest's house: points if it's the house's first
+/// completion of that word and, if that completes every word of the puzzle, an additional bonus.
+/// `new_house_words` holds the words the house completed for the first time. Words count as
+/// completed when the grid holds the puzzle's answer. Every word the guest completes is also
+/// recorded as their solve.
+///
+/// `version` is the version of the crossword the edits were typed on. If the crossword has been
+/// saved since (from another device, say), every edit is rejected and the state is returned as it
+/// is. Otherwise only edits to cells outside the puzzle's words or in words already solved, and
+/// letters other than A-Z, are rejected.
+#[cfg(feature = "ssr")]
+pub fn edit_crossword_state(
+    conn: &mut SqliteConnection,
+    puzzle: &Puzzle,
+    guest_id: i32,
+    version: i32,
+    edits: &[CellEdit],
+) -> Result<CrosswordStateUpdate, diesel::result::Error> {
+    write_transaction(conn, |conn| {
+        let house_id = active_guest_house_id(conn, guest_id)?;
+        let old_db_state = get_or_init_db_crossword_state(conn, puzzle, guest_id)?;
+        let mut state = CrosswordState::from_json(&old_db_state.state, puzzle);
+        state.version = old_db_state.version;
+        if version != old_db_state.version {
+            return Ok(CrosswordStateUpdate {
+                state,
+                rejected: edits.iter().map(|edit| (edit.row, edit.col)).collect(),
+                new_house_words: vec![],
+            });
+        }
+
+        let mut grid = state.grid;
+        let mut rejected = Vec::new();
+        for edit in edits {
+            let (row, col) = (edit.row, edit.col);
+            let letter = edit.letter.map(|c| c.to_ascii_uppercase());
+            let in_solved_word = puzzle
+                .words
+                .iter()
+                .zip(&state.completions)
+                .any(|(word, &done)| done && word.contains(row, col));
+            if !puzzle.is_word_cell(row, col)
+                || in_solved_word
+                || letter.is_some_and(|c| !c.is_ascii_uppercase())
+            {
+                rejected.push((row, col));
+                continue;
+            }
+            grid[row][col] = letter;
+        }
+
+        let completions = puzzle.completions(&grid);
+        let mut new_state = CrosswordState::new_full_grid(grid, completions);
+        let new_house_words =
+            save_crossword_state(conn, puzzle, house_id, &old_db_state, &new_state, true)?;
+        new_state.version = old_db_state.version + 1;
+        Ok(CrosswordStateUpdate {
+            state: new_state,
+            rejected,
+            new_house_words,
+        })
+    })
+}
+
+/// Saves a guest's new crossword state over `old_db_state`, bumping its version, and scores the
+/// words it newly solves (see `edit_crossword_state`). Those words are recorded as the guest's
+/// solves unless `record_solves` is unset (for words revealed by a hint). Returns the words the
+/// house completed for the first time.
+#[cfg(feature = "ssr")]
+fn save_crossword_state(
+    conn: &mut SqliteConnection,
+    puzzle: &Puzzle,
+    house_id: i32,
+    old_db_state: &DbCrosswordState,
+    new_state: &CrosswordState,
+    record_solves: bool,
+) -> Result<Vec<usize>, diesel::result::Error> {
+    // Award points for the words this guest just completed.
+    let old_completions = CrosswordState::from_json(&old_db_state.state, puzzle).completions;
+    let new_completions = puzzle.completions(&new_state.grid);
+    let newly_completed: Vec<usize> = (0..puzzle.words.len())
+        .filter(|&i| !old_completions[i] && new_completions[i])
+        .collect();
+    let new_house_words =
+        award_house_crossword_words(conn, puzzle, house_id, newly_completed.iter().copied())?;
+
+    let state: String = new_state.clone().into();
+    diesel::update(crossword_states::table.find(old_db_state.id))
+        .set((
+            crossword_states::state.eq(state),
+            crossword_states::updated_at.eq(Utc::now().naive_utc()),
+            crossword_states::version.eq(old_db_state.version + 1),
+        ))
+        .execute(conn)?;
+    if record_solves {
+        record_crossword_solves(conn, old_db_state.guest_id, house_id, &newly_completed)?;
+    }
+
+    Ok(new_house_words)
+}
+
+/// Awards house points for crossword words the house just solved (indices into the puzzle's
+/// words): 5 points for each word the house completes for the first time and, if that completes
+/// every word of the puzzle, a bonus of 15. Returns the words completed for the first time.
+#[cfg(feature = "ssr")]
+fn award_house_crossword_words(
+    conn: &mut SqliteConnection,
+    puzzle: &Puzzle,
+    house_id: i32,
+    words: impl IntoIterator<Item = usize>,
+) -> Result<Vec<usize>, diesel::result::Error> {
+    let word_count = puzzle.words.len();
+
+    // Query the house's initial completion count before any inserts.
+    let initial_count: i64 = house_crossword_completions::table
+        .filter(house_crossword_completions::house_id.eq(house_id))
+        .filter(house_crossword_completions::word_index.lt(word_count as i32))
+        .count()
+        .get_result(conn)?;
+
+    // Award points if first for the house. Track any new insertions.
+    let mut new_house_words = Vec::new();
+    for i in words {
+        if !house_has_completed_word(conn, house_id, i as i32)? {
+            // First time for for the house; award 5 points and mark completed.
+            award_points_to_house(
+                conn,
+                house_id,
+                5,
+                &format!("Crossword word {} completed by house", i),
+            )?;
+            insert_house_word_completion(conn, house_id, i as i32)?;
+            new_house_words.push(i);
+        }
+    }
+
+    // Check if this update caused the house to complete every word.
+    let effective_final_count = initial_count + new_house_words.len() as i64;
+    if !new_house_words.is_empty() && effective_final_count == word_count as i64 {
+        award_points_to_house(conn, house_id, 15, "Crossword completion bonus")?;
+    }
+
+    Ok(new_house_words)
+}
+
+/// Records that a guest solved crossword words, timed from when they opened the crossword (see
+/// `get_or_init_crossword_state`). A word solved again after being erased keeps its first solve.
+#[cfg(feature = "ssr")]
+fn record_crossword_solves(
+    conn: &mut SqliteConnection,
+    guest_id: i32,
+    house_id: i32,
+    words: &[usize],
+) -> Result<(), diesel::result::Error> {
+    if words.is_empty() {
+        return Ok(());
+    }
+    let started_at: Option<chrono::NaiveDateTime> = crossword_states::table
+        .filter(crossword_states::guest_id.eq(guest_id))
+        .select(crossword_states::started_at)
+        .first(conn)
+        .optional()?
+        .flatten();
+    let elapsed_seconds = started_at.map(|started_at| {
+        (Utc::now().naive_utc() - started_at)
+            .num_seconds()
+            .clamp(0, i32::MAX as i64) as i32
+    });
+    for &word_index in words {
+        diesel::insert_into(crossword_solves::table)
+            .values(&NewCrosswordSolve {
+                guest_id,
+                house_id,
+                word_index: word_index as i32,
+                elapsed_seconds,
+            })
+            .on_conflict((crossword_solves::guest_id, crossword_solves::word_index))
+            .do_nothing()
+            .execute(conn)?;
+    }
+    Ok(())
+}
+
+/// Fetches every crossword solve in the order they happened, with guest and house names, marking
+/// the first solve of each word within a house.
+#[cfg(feature = "ssr")]
+pub fn get_crossword_timeline(
+    conn: &mut SqliteConnection,
+) -> Result<Vec<CrosswordSolveLog>, diesel::result::Error> {
+    Ok(load_crossword_solves(conn)?
+        .into_iter()
+        .map(|solve| solve.log)
+        .collect())
+}
+
+/// Ranks each house's crossword solvers: most words solved first, then least time from opening
+/// the crossword to their latest solve. Houses are ordered by name; guests who solved nothing are
+/// left out.
+#[cfg(feature = "ssr")]
+pub fn get_crossword_leaderboard(
+    conn: &mut SqliteConnection,
+) -> Result<Vec<HouseLeaderboard>, diesel::result::Error> {
+    let mut leaderboard: Vec<HouseLeaderboard> = get_all_houses(conn)?
+        .into_iter()
+        .map(|house| HouseLeaderboard {
+            house,
+            solvers: Vec::new(),
+        })
+        .collect();
+
+    // Solves come in the order they happened, so a guest's latest solve is their last.
+    let mut solvers: HashMap<i32, (i32, SolverStats)> = HashMap::new();
+    for solve in load_crossword_solves(conn)? {
+        let (_, stats) = solvers.entry(solve.guest_id).or_insert_with(|| {
+            (
+                solve.house_id,
+                SolverStats {
+                    guest_name: solve.log.guest_name.clone(),
+                    words_solved: 0,
+                    first_solves: 0,
+                    elapsed_seconds: None,
+                },
+            )
+        });
+        stats.words_solved += 1;
+        stats.first_solves += usize::from(solve.log.first_for_house);
+        stats.elapsed_seconds = solve.log.elapsed_seconds;
+    }
+    for (house_id, stats) in solvers.into_values() {
+        if let Some(entry) = leaderboard
+            .iter_mut()
+            .find(|entry| entry.house.id == house_id)
+        {
+            entry.solvers.push(stats);
+        }
+    }
+    for entry in &mut leaderboard {
+        entry.solvers.sort_by(|a, b| {
+            b.words_solved
+                .cmp(&a.words_solved)
+                .then(
+                    a.elapsed_seconds
+                        .unwrap_or(i32::MAX)
+                        .cmp(&b.elapsed_seconds.unwrap_or(i32::MAX)),
+                )
+                .then_with(|| a.guest_name.cmp(&b.guest_name))
+        });
+    }
+    Ok(leaderboard)
+}
+
+/// A crossword solve, with the ids the leaderboard groups by.
+#[cfg(feature = "ssr")]
+struct CrosswordSolve {
+    guest_id: i32,
+    house_id: i32,
+    log: CrosswordSolveLog,
+}
+
+/// Loads every crossword solve, oldest first.
+#[cfg(feature = "ssr")]
+fn load_crossword_solves(
+    conn: &mut SqliteConnection,
+) -> Result<Vec<CrosswordSolve>, diesel::result::Error> {
+    type SolveRow = (
+        i32,
+        i32,
+        i32,
+        chrono::NaiveDateTime,
+        Option<i32>,
+        String,
+        String,
+    );
+    let rows: Vec<SolveRow> = crossword_solves::table
+        .inner_join(guests::table)
+        .inner_join(houses::table)
+        .order((crossword_solves::solved_at, crossword_solves::id))
+        .select((
+            crossword_solves::guest_id,
+            crossword_solves::house_id,
+            crossword_solves::word_index,
+            crossword_solves::solved_at,
+            crossword_solves::elapsed_seconds,
+            guests::name,
+            houses::name,
+        ))
+        .load(conn)?;
+
+    let mut solved = HashSet::new();
+    Ok(rows
+        .into_iter()
+        .map(
+            |(
+                guest_id,
+                house_id,
+                word_index,
+                solved_at,
+                elapsed_seconds,
+                guest_name,
+                house_name,
+            )| {
+                CrosswordSolve {
+                    guest_id,
+                    house_id,
+                    log: CrosswordSolveLog {
+                        guest_name,
+                        house_name,
+                        word_index: word_index as usize,
+                        solved_at,
+                        elapsed_seconds,
+                        first_for_house: solved.insert((house_id, word_index)),
+                    },
+                }
+            },
+        )
+        .collect())
+}
+
+/// Fetches a house's shared crossword board for `puzzle`: its letters, the words they solve, and
+/// who last wrote each cell. Cells outside the puzzle's words are ignored.
+#[cfg(feature = "ssr")]
+pub fn get_house_board(
+    conn: &mut SqliteConnection,
+    puzzle: &Puzzle,
+    house_id: i32,
+) -> Result<GuestCrossword, diesel::result::Error> {
+    let rows: Vec<(HouseBoardCell, Option<String>)> = house_board_cells::table
+        .left_join(guests::table)
+        .filter(house_board_cells::house_id.eq(house_id))
+        .order((house_board_cells::row_index, house_board_cells::col_index))
+        .select((HouseBoardCell::as_select(), guests::name.nullable()))
+        .load(conn)?;
+
+    let mut filled = Vec::new();
+    let mut cells = Vec::new();
+    for (cell, guest_name) in rows {
+        let (row, col) = (cell.row_index as usize, cell.col_index as usize);
+        if !puzzle.is_word_cell(row, col) {
+            continue;
+        }
+        if let Some(letter) = cell.letter.as_deref().and_then(|l| l.chars().next()) {
+            filled.push((row, col, letter));
+        }
+        cells.push(BoardCell {
+            row,
+            col,
+            guest_name,
+            version: cell.version,
+        });
+    }
+
+    let state = CrosswordState::from_sparse(SparseState { filled }, puzzle);
+    let mut crossword = GuestCrossword::new(state, puzzle);
+    crossword.board = Some(HouseBoard { house_id, cells });
+    Ok(crossword)
+}
+
+/// Applies a guest's edits to their house's shared crossword board, in order.
+///
+/// An edit is rejected when another guest has written the cell since the version the guest saw
+/// (the guest's own earlier writes never conflict), when the cell is not part of a word or belongs
+/// to a word the board already solves, or when the letter is not A-Z. Words the board solves
+/// afterwards score for the house as in `edit_crossword_state`, and count as solved by the guest.
+#[cfg(feature = "ssr")]
+pub fn edit_house_board(
+    conn: &mut SqliteConnection,
+    puzzle: &Puzzle,
+    guest_id: i32,
+    edits: &[BoardEdit],
+) -> Result<HouseBoardUpdate, diesel::result::Error> {
+    apply_house_board_edits(conn, puzzle, guest_id, edits, true)
+}
+
+/// Applies edits to a house board as in `edit_house_board`. Words the board solves afterwards are
+/// recorded as the guest's solves unless `record_solves` is unset (for words revealed by a hint).
+#[cfg(feature = "ssr")]
+fn apply_house_board_edits(
+    conn: &mut SqliteConnection,
+    puzzle: &Puzzle,
+    guest_id: i32,
+    edits: &[BoardEdit],
+    record_solves: bool,
+) -> Result<HouseBoardUpdate, diesel::result::Error> {
+    write_transaction(conn, |conn| {
+        let house_id = active_guest_house_id(conn, guest_id)?;
+
+        let solved = get_house_board(conn, puzzle, house_id)?.completions;
+        let mut applied = 0;
+        let mut rejected = Vec::new();
+        for edit in edits {
+            let (row, col) = (edit.row, edit.col);
+            let letter = edit.letter.map(|c| c.to_ascii_uppercase());
+            let in_solved_word = puzzle
+                .words
+                .iter()
+                .zip(&solved)
+                .any(|(word, &done)| done && word.contains(row, col));
+            if !puzzle.is_word_cell(row, col)
+                || in_solved_word
+                || letter.is_some_and(|c| !c.is_ascii_uppercase())
+            {
+                rejected.push((row, col));
+                continue;
+            }
+
+            let cell_filter = house_board_cells::house_id
+                .eq(house_id)
+                .and(house_board_cells::row_index.eq(row as i32))
+                .and(house_board_cells::col_index.eq(col as i32));
+            let existing: Option<HouseBoardCell> = house_board_cells::table
+                .filter(cell_filter)
+                .select(HouseBoardCell::as_select())
+                .first(conn)
+                .optional()?;
+            let letter = letter.map(String::from);
+            match existing {
+                Some(cell) if cell.version != edit.version && cell.guest_id != Some(guest_id) => {
+                    rejected.push((row, col));
+                    continue;
+                }
+                Some(cell) => {
+                    diesel::update(house_board_cells::table.find(cell.id))
+                        .set((
+                            house_board_cells::letter.eq(letter),
+                            house_board_cells::guest_id.eq(Some(guest_id)),
+                            house_board_cells::version.eq(house_board_cells::version + 1),
+                            house_board_cells::updated_at.eq(Utc::now().naive_utc()),
+                        ))
+                        .execute(conn)?;
+                }
+                None => {
+                    diesel::insert_into(house_board_cells::table)
+                        .values(&NewHouseBoardCell {
+                            house_id,
+                            row_index: row as i32,
+                            col_index: col as i32,
+                            letter,
+                            guest_id: Some(guest_id),
+                            version: 1,
+                        })
+                        .execute(conn)?;
+                }
+            }
+            applied += 1;
+        }
+
+        let completions = get_house_board(conn, puzzle, house_id)?.completions;
+        let solved_words = (0..completions.len()).filter(|&i| completions[i]);
+        let new_house_words = award_house_crossword_words(conn, puzzle, house_id, solved_words)?;
+        if record_solves {
+            record_crossword_solves(conn, guest_id, house_id, &new_house_words)?;
+        }
+
+        Ok(HouseBoardUpdate {
+            house_id,
+            applied,
+            rejected,
+            new_house_words,
+        })
+    })
+}
+
+/// Returns every house's crossword hint allowance and usage, ordered by house name. Houses whose
+/// limit the admin hasn't set may take `default_limit` hints.
+#[cfg(feature = "ssr")]
+pub fn get_hint_usage(
+    conn: &mut SqliteConnection,
+    default_limit: i32,
+) -> Result<Vec<HouseHintUsage>, diesel::result::Error> {
+    let limits: Vec<(i32, String, Option<i32>)> = houses::table
+        .left_join(crossword_hint_limits::table)
+        .order(houses::name.asc())
+        .select((
+            houses::id,
+            houses::name,
+            crossword_hint_limits::hint_limit.nullable(),
+        ))
+        .load(conn)?;
+    let hints: Vec<(i32, i32)> = crossword_hints::table
+        .select((crossword_hints::house_id, crossword_hints::cost))
+        .load(conn)?;
+
+    Ok(limits
+        .into_iter()
+        .map(|(house_id, house_name, limit)| {
+            let costs: Vec<i32> = hints
+                .iter()
+                .filter(|(hint_house, _)| *hint_house == house_id)
+                .map(|(_, cost)| *cost)
+                .collect();
+            HouseHintUsage {
+                house_id,
+                house_name,
+                used: costs.len() as i32,
+                limit: limit.unwrap_or(default_limit),
+                spent: costs.iter().sum(),
+            }
+        })
+        .collect())
+}
+
+/// Sets how many crossword hints a house may take in total. Fails with `NotFound` if there is no
+/// such house.
+#[cfg(feature = "ssr")]
+pub fn set_house_hint_limit(
+    conn: &mut SqliteConnection,
+    house_id: i32,
+    limit: i32,
+) -> Result<(), diesel::result::Error> {
+    if limit < 0 {
+        return Err(diesel::result::Error::QueryBuilderError(Box::new(
+            IoError::other("Hint limit can't be negative"),
+        )));
+    }
+    write_transaction(conn, |conn| {
+        houses::table
+            .find(house_id)
+            .select(houses::id)
+            .first::<i32>(conn)?;
+        diesel::insert_into(crossword_hint_limits::table)
+            .values((
+                crossword_hint_limits::house_id.eq(house_id),
+                crossword_hint_limits::hint_limit.eq(limit),
+            ))
+            .on_conflict(crossword_hint_limits::house_id)
+            .do_update()
+            .set(crossword_hint_limits::hint_limit.eq(limit))
+            .execute(conn)?;
+        Ok(())
+    })
+}
+
+/// Returns every crossword hint taken, most recent first.
+#[cfg(feature = "ssr")]
+pub fn get_crossword_hint_log(
+    conn: &mut SqliteConnection,
+) -> Result<Vec<CrosswordHintLog>, diesel::result::Error> {
+    // id, house, guest, word index, kind, cost, time.
+    type HintLogRow = (
+        i32,
+        String,
+        Option<String>,
+        i32,
+        String,
+        i32,
+        chrono::NaiveDateTime,
+    );
+    let rows: Vec<HintLogRow> = crossword_hints::table
+        .inner_join(houses::table)
+        .left_join(guests::table)
+        .order((crossword_hints::used_at.desc(), crossword_hints::id.desc()))
+        .select((
+            crossword_hints::id,
+            houses::name,
+            guests::name.nullable(),
+            crossword_hints::word_index,
+            crossword_hints::kind,
+            crossword_hints::cost,
+            crossword_hints::used_at,
+        ))
+        .load(conn)?;
+    Ok(rows
+        .into_iter()
+        .map(
+            |(id, house_name, guest_name, word_index, kind, cost, used_at)| CrosswordHintLog {
+                id,
+                house_name,
+                guest_name,
+                word_index: word_index as usize,
+                kind: HintKind::from_db(&kind),
+                cost,
+                used_at,
+            },
+        )
+        .collect())
+}
+
+/// Reveals the first wrong or missing letter of a crossword word, or the whole word, for a guest,
+/// and charges their house for it through a negative house point award.
+///
+/// The letters are written on the guest's own grid, or on their house's board when `house_board`
+/// is set, and score for the house like letters the guest typed, but words they complete don't
+/// count as the guest's solves. Fails if the house has used up its hints or the word is already
+/// solved.
+#[cfg(feature = "ssr")]
+pub fn take_crossword_hint(
+    conn: &mut SqliteConnection,
+    puzzle: &Puzzle,
+    hints: &HintConfig,
+    guest_id: i32,
+    word_index: usize,
+    kind: HintKind,
+    house_board: bool,
+) -> Result<HintTaken, diesel::result::Error> {
+    write_transaction(conn, |conn| {
+        let guest: Guest = guests::table
+            .filter(guests::id.eq(guest_id))
+            .filter(guests::is_active.eq(1i32))
+            .select(Guest::as_select())
+            .first(conn)?;
+        let house_id = guest.house_id.ok_or(diesel::result::Error::NotFound)?;
+        let word = puzzle
+            .words
+            .get(word_index)
+            .ok_or(diesel::result::Error::NotFound)?;
+
+        let usage = get_hint_usage(conn, hints.default_limit)?
+            .into_iter()
+            .find(|usage| usage.house_id == house_id)
+            .ok_or(diesel::result::Error::NotFound)?;
+        if usage.used >= usage.limit {
+            return Err(diesel::result::Error::QueryBuilderError(Box::new(
+                IoError::other("Your house has no hints left"),
+            )));
+        }
+
+        let board = if house_board {
+            Some(get_house_board(conn, puzzle, house_id)?)
+        } else {
+            None
+        };
+        let mut grid = match &board {
+            Some(board) => {
+                let filled = board.filled.clone();
+                CrosswordState::from_sparse(SparseState { filled }, puzzle).grid
+            }
+            None => get_or_init_crossword_state(conn, puzzle, guest_id)?.grid,
+        };
+
+        let mut missing = word
+            .cells()
+            .zip(word.answer.chars())
+            .filter(|&((row, col), letter)| grid[row][col] != Some(letter))
+            .collect::<Vec<_>>();
+        if missing.is_empty() {
+            return Err(diesel::result::Error::QueryBuilderError(Box::new(
+                IoError::other("That word is already solved"),
+            )));
+        }
+        if kind == HintKind::Letter {
+            missing.truncate(1);
+        }
+
+        let new_house_words = match board.and_then(|board| board.board) {
+            Some(board) => {
+                let edits: Vec<BoardEdit> = missing
+                    .iter()
+                    .map(|&((row, col), letter)| BoardEdit {
+                        row,
+                        col,
+                        letter: Some(letter),
+                        version: board
+                            .cells
+                            .iter()
+                            .find(|cell| (cell.row, cell.col) == (row, col))
+                            .map_or(0, |cell| cell.version),
+                    })
+                    .collect();
+                apply_house_board_edits(conn, puzzle, guest_id, &edits, false)?.new_house_words
+            }
+            None => {
+                for &((row, col), letter) in &missing {
+                    grid[row][col] = Some(letter);
+                }
+                let state = CrosswordState::new_full_grid(grid, vec![]);
+                let old_db_state = get_or_init_db_crossword_state(conn, puzzle, guest_id)?;
+                save_crossword_state(conn, puzzle, house_id, &old_db_state, &state, false)?
+            }
+        };
+
+        let cost = match kind {
+            HintKind::Letter => hints.letter_cost,
+            HintKind::Word => hints.word_cost,
+        };
+        let award = award_points_to_house(
+            conn,
+            house_id,
+            -cost,
+            &format!(
+                "Crossword hint ({} of word {}) for {}",
+                kind.as_str(),
+                word_index,
+                guest.name
+            ),
+        )?;
+        diesel::insert_into(crossword_hints::table)
+            .values(&NewCrosswordHint {
+                house_id,
+                guest_id: Some(guest_id),
+                word_index: word_index as i32,
+                kind: kind.as_str().to_string(),
+                cost,
+                point_award_id: Some(award.id),
+            })
+            .execute(conn)?;
+
+        Ok(HintTaken {
+            house_id,
+            cost,
+            new_house_words,
+        })
+    })
+}
+
+/// Largest photo accepted with a horcrux claim, in bytes.
+#[cfg(feature = "ssr")]
+pub const MAX_HORCRUX_PHOTO_BYTES: usize = 10 * 1024 * 1024;
+
+/// Saves a photo sent with a horcrux claim in `dir`, under a new random name, and returns that
+/// name. Only JPEG, PNG, WebP and HEIC photos up to `MAX_HORCRUX_PHOTO_BYTES` are accepted.
+#[cfg(feature = "ssr")]
+pub fn save_horcrux_photo(dir: &str, photo: &PhotoUpload) -> Result<String, String> {
+    use base64::Engine;
+
+    let extension = match photo.content_type.as_str() {
+        "image/jpeg" => "jpg",
+        "image/png" => "png",
+        "image/webp" => "webp",
+        "image/heic" => "heic",
+        other => return Err(format!("Photos of type {} are not supported", other)),
+    };
+    let data = base64::engine::general_purpose::STANDARD
+        .decode(&photo.data)
+        .map_err(|e| format!("Invalid photo data: {}", e))?;
+    if data.is_empty() {
+        return Err("The photo is empty".to_string());
+    }
+    if data.len() > MAX_HORCRUX_PHOTO_BYTES {
+        return Err(format!(
+            "The photo is larger than {} MB",
+            MAX_HORCRUX_PHOTO_BYTES / (1024 * 1024)
+        ));
+    }
+
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir, e))?;
+    let file_name = format!("{}.{}", Uuid::new_v4(), extension);
+    let path = std::path::Path::new(dir).join(&file_name);
+    std::fs::write(&path, data).map_err(|e| format!("Failed to save photo: {}", e))?;
+    Ok(file_name)
+}
+
+/// Reads a photo saved by `save_horcrux_photo` back as a `data:` URL, for the admin to view.
+#[cfg(feature = "ssr")]
+pub fn load_horcrux_photo(dir: &str, file_name: &str) -> Result<String, String> {
+    use base64::Engine;
+
+    // Only ever a name made by `save_horcrux_photo`, never a path.
+    if file_name.contains(['/', '\\']) || file_name.starts_with('.') {
+        return Err(format!("Invalid photo name {}", file_name));
+    }
+    let content_type = match file_name.rsplit('.').next() {
+        Some("jpg") => "image/jpeg",
+        Some("png") => "image/png",
+        Some("webp") => "image/webp",
+        Some("heic") => "image/heic",
+        _ => return Err(format!("Invalid photo name {}", file_name)),
+    };
+    let data = std::fs::read(std::path::Path::new(dir).join(file_name))
+        .map_err(|e| format!("Failed to read photo: {}", e))?;
+    Ok(format!(
+        "data:{};base64,{}",
+        content_type,
+        base64::engine::general_purpose::STANDARD.encode(data)
+    ))
+}
+
+/// Records a guest's claim that their house found the horcrux crossword word `word_index` leads
+/// to, with the name of its photo (see `save_horcrux_photo`), if any. The house must have solved
+/// the word, and must not have a pending or approved claim for that horcrux already.
+#[cfg(feature = "ssr")]
+pub fn create_horcrux_claim(
+    conn: &mut SqliteConnection,
+    puzzle: &Puzzle,
+    guest_id: i32,
+    word_index: usize,
+    photo_path: Option<String>,
+) -> Result<HorcruxClaim, diesel::result::Error> {
+    write_transaction(conn, |conn| {
+        let guest: Guest = guests::table
+            .filter(guests::id.eq(guest_id))
+            .filter(guests::is_active.eq(1i32))
+            .select(Guest::as_select())
+            .first(conn)?;
+        let house_id = guest.house_id.ok_or(diesel::result::Error::NotFound)?;
+        let word = puzzle
+            .words
+            .get(word_index)
+            .ok_or(diesel::result::Error::NotFound)?;
+
+        if !house_has_completed_word(conn, house_id, word_index as i32)? {
+            return Err(diesel::result::Error::QueryBuilderError(Box::new(
+                IoError::other("Your house hasn't solved that word yet"),
+            )));
+        }
+        let open_claims: i64 = horcrux_claims::table
+            .filter(horcrux_claims::house_id.eq(house_id))
+            .filter(horcrux_claims::word_index.eq(word_index as i32))
+            .filter(horcrux_claims::status.ne(ClaimStatus::Rejected.as_str()))
+            .count()
+            .get_result(conn)?;
+        if open_claims > 0 {
+            return Err(diesel::result::Error::QueryBuilderError(Box::new(
+                IoError::other("Your house has already claimed that horcrux"),
+            )));
+        }
+
+        diesel::insert_into(horcrux_claims::table)
+            .values(&NewHorcruxClaim {
+                house_id,
+                guest_id: Some(guest_id),
+                word_index: word_index as i32,
+                horcrux: word.horcrux.clone(),
+                photo_path,
+            })
+            .returning(HorcruxClaim::as_returning())
+            .get_result(conn)
+    })
+}
+
+/// Returns where a house's latest claim for each of the puzzle's horcruxes stands, in puzzle
+/// order; None where the house hasn't claimed it.
+#[cfg(feature = "ssr")]
+pub fn get_house_horcrux_claims(
+    conn: &mut SqliteConnection,
+    house_id: i32,
+    word_count: usize,
+) -> Result<Vec<Option<ClaimStatus>>, diesel::result::Error> {
+    let claims: Vec<(i32, String)> = horcrux_claims::table
+        .filter(horcrux_claims::house_id.eq(house_id))
+        .order(horcrux_claims::id.asc())
+        .select((horcrux_claims::word_index, horcrux_claims::status))
+        .load(conn)?;
+    let mut statuses = vec![None; word_count];
+    for (word_index, status) in claims {
+        if let Some(slot) = statuses.get_mut(word_index as usize) {
+            *slot = Some(ClaimStatus::from_db(&status));
+        }
+    }
+    Ok(statuses)
+}
+
+/// Returns every horcrux claim for the admin review queue: pending claims first, then the rest,
+/// most recent first.
+#[cfg(feature = "ssr")]
+pub fn get_horcrux_claims(
+    conn: &mut SqliteConnection,
+    puzzle: &Puzzle,
+) -> Result<Vec<HorcruxClaimLog>, diesel::result::Error> {
+    let rows: Vec<(HorcruxClaim, String, Option<String>)> = horcrux_claims::table
+        .inner_join(houses::table)
+        .left_join(guests::table)
+        .order(horcrux_claims::id.desc())
+        .select((
+            HorcruxClaim::as_select(),
+            houses::name,
+            guests::name.nullable(),
+        ))
+        .load(conn)?;
+    let mut claims: Vec<HorcruxClaimLog> = rows
+        .into_iter()
+        .map(|(claim, house_name, guest_name)| HorcruxClaimLog {
+            id: claim.id,
+            house_name,
+            guest_name,
+            word_index: claim.word_index as usize,
+            location: puzzle
+                .words
+                .get(claim.word_index as usize)
+                .map(|word| word.location.clone())
+                .unwrap_or_default(),
+            horcrux: claim.horcrux,
+            has_photo: claim.photo_path.is_some(),
+            status: ClaimStatus::from_db(&claim.status),
+            claimed_at: claim.claimed_at,
+        })
+        .collect();
+    claims.sort_by_key(|claim| claim.status != ClaimStatus::Pending);
+    Ok(claims)
+}
+
+/// Approves or rejects a pending horcrux claim. Approving awards the house `points`. Returns the
+/// updated claim.
+#[cfg(feature = "ssr")]
+pub fn review_horcrux_claim(
+    conn: &mut SqliteConnection,
+    claim_id: i32,
+    approve: bool,
+    points: i32,
+) -> Result<HorcruxClaim, diesel::result::Error> {
+    write_transaction(conn, |conn| {
+        let claim: HorcruxClaim = horcrux_claims::table
+            .find(claim_id)
+            .select(HorcruxClaim::as_select())
+            .first(conn)?;
+        if claim.status != ClaimStatus::Pending.as_str() {
+            return Err(diesel::result::Error::QueryBuilderError(Box::new(
+                IoError::other("Claim has already been reviewed"),
+            )));
+        }
+
+        let (status, point_award_id) = if approve {
+            let award = award_points_to_house(
+                conn,
+                claim.house_id,
+                points,
+                &format!("Found a horcrux: {}", claim.horcrux),
+            )?;
+            (ClaimStatus::Approved, Some(award.id))
+        } else {
+            (ClaimStatus::Rejected, None)
+        };
+        diesel::update(horcrux_claims::table.find(claim_id))
+            .set((
+                horcrux_claims::status.eq(status.as_str()),
+                horcrux_claims::point_award_id.eq(point_award_id),
+                horcrux_claims::reviewed_at.eq(Some(Utc::now().naive_utc())),
+            ))
+            .returning(HorcruxClaim::as_returning())
+            .get_result(conn)
+    })
+}
+
+/// Returns the photo file name of a horcrux claim, if it has one.
+#[cfg(feature = "ssr")]
+pub fn get_horcrux_claim_photo(
+    conn: &mut SqliteConnection,
+    claim_id: i32,
+) -> Result<Option<String>, diesel::result::Error> {
+    horcrux_claims::table
+        .find(claim_id)
+        .select(horcrux_claims::photo_path)
+        .first(conn)
+}
+
+/// Fetches a guest's Wordle game for `date`, starting one with a random answer from `words` if
+/// they haven't played that day yet.
+#[cfg(feature = "ssr")]
+pub fn get_or_start_wordle_game(
+    conn: &mut SqliteConnection,
+    guest_id: i32,
+    date: NaiveDate,
+    words: &WordList,
+) -> Result<WordleGame, diesel::result::Error> {
+    write_transaction(conn, |conn| {
+        let game = get_or_start_db_wordle_game(conn, guest_id, date, words)?;
+        wordle_game_view(conn, &game)
+    })
+}
+
+#[cfg(feature = "ssr")]
+fn get_or_start_db_wordle_game(
+    conn: &mut SqliteConnection,
+    guest_id: i32,
+    date: NaiveDate,
+    words: &WordList,
+) -> Result<DbWordleGame, diesel::result::Error> {
+    let existing = wordle_games::table
+        .filter(wordle_games::guest_id.eq(guest_id))
+        .filter(wordle_games::game_date.eq(date))
+        .select(DbWordleGame::as_select())
+        .first(conn)
         .optional()?;
+    if let Some(game) = existing {
+        return Ok(game);
+    }
+
+    let target = words.answers().choose(&mut rand::rng()).ok_or_else(|| {
+        diesel::result::Error::QueryBuilderError(Box::new(IoError::other(
+            "There are no Wordle words",
+        )))
+    })?;
+    diesel::insert_into(wordle_games::table)
+        .values(&NewWordleGame {
+            guest_id,
+            game_date: date,
+            target: target.clone(),
+        })
+        .returning(DbWordleGame::as_returning())
+        .get_result(conn)
+}
+
+// What the Wordle page is told about a game: the statuses of each guess, and the answer only once
+// the game is over.
+#[cfg(feature = "ssr")]
+fn wordle_game_view(
+    conn: &mut SqliteConnection,
+    game: &DbWordleGame,
+) -> Result<WordleGame, diesel::result::Error> {
+    let status = WordleStatus::from_db(&game.status);
+    let points = match game.point_award_id {
+        Some(award_id) => point_awards::table
+            .find(award_id)
+            .select(point_awards::amount)
+            .first(conn)
+            .optional()?,
+        None => None,
+    };
+    Ok(WordleGame {
+        guesses: wordle_guesses(&game.target, &game.guesses),
+        status,
+        answer: (status != WordleStatus::Playing).then(|| game.target.clone()),
+        points,
+    })
+}
+
+// The guesses saved as a JSON array, with how each compares to `target`.
+#[cfg(feature = "ssr")]
+fn wordle_guesses(target: &str, guesses: &str) -> Vec<WordleGuess> {
+    let words: Vec<String> = serde_json::from_str(guesses).unwrap_or_default();
+    words
+        .into_iter()
+        .map(|word| WordleGuess {
+            statuses: compute_statuses(&word, target),
+            word,
+        })
+        .collect()
+}
+
+// Checks that `guess` is one of the `words` accepted as guesses and, in hard mode, uses every
+// letter revealed by the `previous` guesses. Returns it upper case.
+#[cfg(feature = "ssr")]
+fn check_wordle_guess(
+    guess: &str,
+    previous: &[WordleGuess],
+    words: &WordList,
+    hard_mode: bool,
+) -> Result<String, diesel::result::Error> {
+    let guess = guess.trim().to_uppercase();
+    let invalid = |message: String| {
+        diesel::result::Error::QueryBuilderError(Box::new(IoError::other(message)))
+    };
+    if guess.chars().count() != WORD_LENGTH {
+        return Err(invalid(format!(
+            "Guesses must have {} letters",
+            WORD_LENGTH
+        )));
+    }
+    if !words.is_valid_guess(&guess) {
+        return Err(invalid("Not in the word list".to_string()));
+    }
+    if hard_mode {
+        let previous = previous
+            .iter()
+            .map(|previous| (previous.word.as_str(), previous.statuses.as_slice()));
+        if let Some(message) = hard_mode_error(&guess, previous) {
+            return Err(invalid(message));
+        }
+    }
+    Ok(guess)
+}
+
+// Where a game stands after `guesses`, the last of which was just made.
+#[cfg(feature = "ssr")]
+fn wordle_status(guesses: &[String], target: &str) -> WordleStatus {
+    if guesses.last().is_some_and(|guess| guess == target) {
+        WordleStatus::Won
+    } else if guesses.len() >= MAX_GUESSES {
+        WordleStatus::Lost
+    } else {
+        WordleStatus::Playing
+    }
+}
+
+/// Checks a guest's Wordle guess for `date` against the target and saves it. A guess must be one of
+/// the `words` accepted as guesses and, in hard mode, use every letter revealed by the earlier
+/// ones; the game must still be on. Solving the word awards the guest (and so their house)
+/// `points[n - 1]` for a solve in `n` guesses, or the last entry of `points` for any later solve.
+/// Returns the updated game.
+#[cfg(feature = "ssr")]
+pub fn submit_wordle_guess(
+    conn: &mut SqliteConnection,
+    guest_id: i32,
+    date: NaiveDate,
+    guess: &str,
+    words: &WordList,
+    points: &[i32],
+    hard_mode: bool,
+) -> Result<WordleGame, diesel::result::Error> {
+    write_transaction(conn, |conn| {
+        active_guest_house_id(conn, guest_id)?;
+        let game = get_or_start_db_wordle_game(conn, guest_id, date, words)?;
+        if WordleStatus::from_db(&game.status) != WordleStatus::Playing {
+            return Err(diesel::result::Error::QueryBuilderError(Box::new(
+                IoError::other("Today's Wordle is already over"),
+            )));
+        }
+
+        let view = wordle_game_view(conn, &game)?;
+        let guess = check_wordle_guess(guess, &view.guesses, words, hard_mode)?;
+        let mut guesses: Vec<String> = view.guesses.into_iter().map(|g| g.word).collect();
+        guesses.push(guess);
+        let status = wordle_status(&guesses, &game.target);
+
+        let amount = points
+            .get(guesses.len() - 1)
+            .or(points.last())
+            .copied()
+            .unwrap_or(0);
+        let point_award_id = if status == WordleStatus::Won && amount != 0 {
+            let award = award_points_to_guest(
+                conn,
+                guest_id,
+                amount,
+                &format!("Solved the Wordle in {} guesses", guesses.len()),
+            )?;
+            Some(award.id)
+        } else {
+            None
+        };
+
+        let guesses = serde_json::to_string(&guesses).expect("Failed to serialize Wordle guesses");
+        let game: DbWordleGame = diesel::update(wordle_games::table.find(game.id))
+            .set((
+                wordle_games::guesses.eq(guesses),
+                wordle_games::status.eq(status.as_str()),
+                wordle_games::point_award_id.eq(point_award_id),
+                wordle_games::finished_at
+                    .eq((status != WordleStatus::Playing).then(|| Utc::now().naive_utc())),
+            ))
+            .returning(DbWordleGame::as_returning())
+            .get_result(conn)?;
+        wordle_game_view(conn, &game)
+    })
+}
+
+/// Starts a Wordle race with a random answer from `words`, which every guest who joins plays. The
+/// first house with `solvers_needed` of its guests solving it wins `points`. Only one race can be
+/// on at a time.
+#[cfg(feature = "ssr")]
+pub fn start_wordle_race(
+    conn: &mut SqliteConnection,
+    words: &WordList,
+    solvers_needed: i32,
+    points: i32,
+) -> Result<WordleRace, diesel::result::Error> {
+    write_transaction(conn, |conn| {
+        let invalid = |message: &str| {
+            diesel::result::Error::QueryBuilderError(Box::new(IoError::other(message)))
+        };
+        if latest_wordle_round(conn)?.is_some_and(|round| round.ended_at.is_none()) {
+            return Err(invalid("A Wordle race is already on"));
+        }
+        if solvers_needed < 1 {
+            return Err(invalid("A Wordle race needs at least one solver to win"));
+        }
+        let target = words
+            .answers()
+            .choose(&mut rand::rng())
+            .ok_or_else(|| invalid("There are no Wordle words"))?;
+        let round: DbWordleRound = diesel::insert_into(wordle_rounds::table)
+            .values(&NewWordleRound {
+                target: target.clone(),
+                solvers_needed,
+                points,
+            })
+            .returning(DbWordleRound::as_returning())
+            .get_result(conn)?;
+        wordle_race_view(conn, &round)
+    })
+}
+
+/// Ends the Wordle race that is on without a winner. Games still being played can't be finished.
+#[cfg(feature = "ssr")]
+pub fn stop_wordle_race(conn: &mut SqliteConnection) -> Result<WordleRace, diesel::result::Error> {
+    write_transaction(conn, |conn| {
+        let round = latest_wordle_round(conn)?
+            .filter(|round| round.ended_at.is_none())
+            .ok_or_else(|| {
+                diesel::result::Error::QueryBuilderError(Box::new(IoError::other(
+                    "No Wordle race is on",
+                )))
+            })?;
+        let round: DbWordleRound = diesel::update(wordle_rounds::table.find(round.id))
+            .set(wordle_rounds::ended_at.eq(Some(Utc::now().naive_utc())))
+            .returning(DbWordleRound::as_returning())
+            .get_result(conn)?;
+        wordle_race_view(conn, &round)
+    })
+}
+
+/// Fetches the Wordle race that is on, or else the last one. None before the first race.
+#[cfg(feature = "ssr")]
+pub fn get_wordle_race(
+    conn: &mut SqliteConnection,
+) -> Result<Option<WordleRace>, diesel::result::Error> {
+    match latest_wordle_round(conn)? {
+        Some(round) => wordle_race_view(conn, &round).map(Some),
+        None => Ok(None),
+    }
+}
+
+#[cfg(feature = "ssr")]
+fn latest_wordle_round(
+    conn: &mut SqliteConnection,
+) -> Result<Option<DbWordleRound>, diesel::result::Error> {
+    wordle_rounds::table
+        .order(wordle_rounds::id.desc())
+        .select(DbWordleRound::as_select())
+        .first(conn)
+        .optional()
+}
+
+// What every page is told about a race: each house's racers and solvers, and each racer's
+// statuses without the letters. The word is only included once the race is over.
+#[cfg(feature = "ssr")]
+fn wordle_race_view(
+    conn: &mut SqliteConnection,
+    round: &DbWordleRound,
+) -> Result<WordleRace, diesel::result::Error> {
+    let games: Vec<(DbWordleRoundGame, String)> = wordle_round_games::table
+        .inner_join(guests::table)
+        .filter(wordle_round_games::round_id.eq(round.id))
+        .order(wordle_round_games::id)
+        .select((DbWordleRoundGame::as_select(), guests::name))
+        .load(conn)?;
+    let racers: Vec<WordleRacer> = games
+        .into_iter()
+        .map(|(game, guest_name)| WordleRacer {
+            guest_name,
+            house_id: game.house_id,
+            statuses: wordle_guesses(&round.target, &game.guesses)
+                .into_iter()
+                .map(|guess| guess.statuses)
+                .collect(),
+            status: WordleStatus::from_db(&game.status),
+        })
+        .collect();
+    let houses = get_all_houses(conn)?
+        .into_iter()
+        .map(|house| {
+            let count = |won_only: bool| {
+                racers
+                    .iter()
+                    .filter(|racer| racer.house_id == house.id)
+                    .filter(|racer| !won_only || racer.status == WordleStatus::Won)
+                    .count() as i32
+            };
+            WordleRaceHouse {
+                house_id: house.id,
+                racers: count(false),
+                solvers: count(true),
+                name: house.name,
+            }
+        })
+        .collect();
+    Ok(WordleRace {
+        id: round.id,
+        solvers_needed: round.solvers_needed,
+        points: round.points,
+        started_at: round.started_at,
+        ended_at: round.ended_at,
+        winning_house_id: round.winning_house_id,
+        answer: round.ended_at.is_some().then(|| round.target.clone()),
+        houses,
+        racers,
+    })
+}
+
+/// Fetches a guest's game in the latest Wordle race, joining them to it for their house if it is
+/// still on. Guests who are unsorted, or didn't join a race before it ended, see an empty game.
+/// Fails if there has been no race yet.
+#[cfg(feature = "ssr")]
+pub fn get_or_join_wordle_race(
+    conn: &mut SqliteConnection,
+    guest_id: i32,
+) -> Result<WordleGame, diesel::result::Error> {
+    write_transaction(conn, |conn| {
+        let round = latest_wordle_round(conn)?.ok_or_else(|| {
+            diesel::result::Error::QueryBuilderError(Box::new(IoError::other(
+                "No Wordle race has been started yet",
+            )))
+        })?;
+        let game = get_or_join_db_wordle_round_game(conn, &round, guest_id)?;
+        Ok(wordle_round_game_view(&round, game.as_ref()))
+    })
+}
+
+#[cfg(feature = "ssr")]
+fn get_or_join_db_wordle_round_game(
+    conn: &mut SqliteConnection,
+    round: &DbWordleRound,
+    guest_id: i32,
+) -> Result<Option<DbWordleRoundGame>, diesel::result::Error> {
+    let existing = wordle_round_games::table
+        .filter(wordle_round_games::round_id.eq(round.id))
+        .filter(wordle_round_games::guest_id.eq(guest_id))
+        .select(DbWordleRoundGame::as_select())
+        .first(conn)
+        .optional()?;
+    if existing.is_some() || round.ended_at.is_some() {
+        return Ok(existing);
+    }
+    let Some(house_id) = active_guest_house_id(conn, guest_id).optional()? else {
+        return Ok(None);
+    };
+    diesel::insert_into(wordle_round_games::table)
+        .values(&NewWordleRoundGame {
+            round_id: round.id,
+            guest_id,
+            house_id,
+        })
+        .returning(DbWordleRoundGame::as_returning())
+        .get_result(conn)
+        .map(Some)
+}
+
+// What the Wordle page is told about a guest's game in a race. Racers score for their house rather
+// than themselves, and the answer is only revealed once the whole race is over, so that nobody who
+// has finished can pass it on.
+#[cfg(feature = "ssr")]
+fn wordle_round_game_view(round: &DbWordleRound, game: Option<&DbWordleRoundGame>) -> WordleGame {
+    WordleGame {
+        guesses: game
+            .map(|game| wordle_guesses(&round.target, &game.guesses))
+            .unwrap_or_default(),
+        status: game
+            .map(|game| WordleStatus::from_db(&game.status))
+            .unwrap_or(WordleStatus::Playing),
+        answer: round.ended_at.is_some().then(|| round.target.clone()),
+        points: None,
+    }
+}
+
+/// Checks a guest's guess in the Wordle race that is on and saves it, like `submit_wordle_guess`.
+/// When a solve gives the guest's house `solvers_needed` solvers, the house wins the race's points
+/// and the race ends. Returns the updated game, and the award if the house won.
+#[cfg(feature = "ssr")]
+pub fn submit_wordle_race_guess(
+    conn: &mut SqliteConnection,
+    guest_id: i32,
+    guess: &str,
+    words: &WordList,
+    hard_mode: bool,
+) -> Result<(WordleGame, Option<PointAward>), diesel::result::Error> {
+    write_transaction(conn, |conn| {
+        let invalid = |message: &str| {
+            diesel::result::Error::QueryBuilderError(Box::new(IoError::other(message)))
+        };
+        let round = latest_wordle_round(conn)?.ok_or_else(|| invalid("No Wordle race is on"))?;
+        if round.ended_at.is_some() {
+            return Err(invalid("The Wordle race is over"));
+        }
+        let game = get_or_join_db_wordle_round_game(conn, &round, guest_id)?
+            .ok_or(diesel::result::Error::NotFound)?;
+        if WordleStatus::from_db(&game.status) != WordleStatus::Playing {
+            return Err(invalid("You have already finished this race"));
+        }
+
+        let previous = wordle_guesses(&round.target, &game.guesses);
+        let guess = check_wordle_guess(guess, &previous, words, hard_mode)?;
+        let mut guesses: Vec<String> = previous.into_iter().map(|g| g.word).collect();
+        guesses.push(guess);
+        let status = wordle_status(&guesses, &round.target);
+        let guesses = serde_json::to_string(&guesses).expect("Failed to serialize Wordle guesses");
+        let game: DbWordleRoundGame = diesel::update(wordle_round_games::table.find(game.id))
+            .set((
+                wordle_round_games::guesses.eq(guesses),
+                wordle_round_games::status.eq(status.as_str()),
+                wordle_round_games::finished_at
+                    .eq((status != WordleStatus::Playing).then(|| Utc::now().naive_utc())),
+            ))
+            .returning(DbWordleRoundGame::as_returning())
+            .get_result(conn)?;
+
+        let solvers: i64 = wordle_round_games::table
+            .filter(wordle_round_games::round_id.eq(round.id))
+            .filter(wordle_round_games::house_id.eq(game.house_id))
+            .filter(wordle_round_games::status.eq(WordleStatus::Won.as_str()))
+            .count()
+            .get_result(conn)?;
+        if status != WordleStatus::Won || solvers < i64::from(round.solvers_needed) {
+            return Ok((wordle_round_game_view(&round, Some(&game)), None));
+        }
+
+        // This solve wins the race for the house.
+        let award = (round.points != 0)
+            .then(|| {
+                award_points_to_house(conn, game.house_id, round.points, "Won the Wordle race")
+            })
+            .transpose()?;
+        let round: DbWordleRound = diesel::update(wordle_rounds::table.find(round.id))
+            .set((
+                wordle_rounds::winning_house_id.eq(Some(game.house_id)),
+                wordle_rounds::point_award_id.eq(award.as_ref().map(|award| award.id)),
+                wordle_rounds::ended_at.eq(Some(Utc::now().naive_utc())),
+            ))
+            .returning(DbWordleRound::as_returning())
+            .get_result(conn)?;
+        Ok((wordle_round_game_view(&round, Some(&game)), award))
+    })
+}
+
+/// Fetches every house's progress in Catch the Keys, by house name.
+#[cfg(feature = "ssr")]
+pub fn get_house_keys(
+    conn: &mut SqliteConnection,
+) -> Result<Vec<HouseKeys>, diesel::result::Error> {
+    get_all_houses(conn)?
+        .iter()
+        .map(|house| house_keys_view(conn, house))
+        .collect()
+}
+
+// The keys a house turned in, what it still needs and, once it has everything, where it finished.
+#[cfg(feature = "ssr")]
+fn house_keys_view(
+    conn: &mut SqliteConnection,
+    house: &House,
+) -> Result<HouseKeys, diesel::result::Error> {
+    let turned_in: Vec<char> = turned_in_keys::table
+        .filter(turned_in_keys::house_id.eq(house.id))
+        .order(turned_in_keys::id)
+        .select(turned_in_keys::key)
+        .load::<String>(conn)?
+        .iter()
+        .filter_map(|key| key.chars().next())
+        .collect();
+    let completion: Option<(i32, Option<i32>)> = key_completions::table
+        .find(house.id)
+        .select((key_completions::place, key_completions::point_award_id))
+        .first(conn)
+        .optional()?;
+    let points = match completion.and_then(|(_, award_id)| award_id) {
+        Some(award_id) => point_awards::table
+            .find(award_id)
+            .select(point_awards::amount)
+            .first(conn)
+            .optional()?,
+        None => None,
+    };
+    Ok(HouseKeys {
+        house_id: house.id,
+        house_name: house.name.clone(),
+        keeper: keeper(&house.slug).to_string(),
+        keys: house_keys(&house.name),
+        missing: missing_keys(&house.name, &turned_in),
+        turned_in,
+        place: completion.map(|(place, _)| place),
+        points,
+    })
+}
 
-    match existing {
-        Some(db_state) => Ok(db_state.state.into()),
-        None => {
-            let initial_state = CrosswordState::new_full_grid(vec![vec![None; 12]; 15], [false; 7]);
-            let new_db_state = NewDbCrosswordState {
-                guest_id,
-                state: initial_state.clone().into(),
-                updated_at: chrono::Utc::now().naive_utc(),
-            };
-            diesel::insert_into(crossword_states::table)
-                .values(&new_db_state)
-                .execute(conn)?;
-            Ok(initial_state)
-        }
-    }
+/// Records a key turned in by a house: a letter of its name it doesn't have enough of yet, or a
+/// wildcard. When that gives the house all its keys, it finishes in the next place and scores
+/// `points[place - 1]`, if the list goes that far. Returns the house's updated progress.
+#[cfg(feature = "ssr")]
+pub fn turn_in_key(
+    conn: &mut SqliteConnection,
+    house_id: i32,
+    key: char,
+    points: &[i32],
+) -> Result<HouseKeys, diesel::result::Error> {
+    write_transaction(conn, |conn| {
+        let house = get_house(conn, house_id)?;
+        record_turned_in_key(conn, &house, key.to_ascii_uppercase(), points)?;
+        house_keys_view(conn, &house)
+    })
 }
 
-/// Updates the crossword state for a guest. Replaces the entire row in the database.
-/// Additionally, checks for new word completions by this guest, and awards house points if it's
-/// the house's first completion of that word. As a result of a first time completion, if all 7
-/// words are now complete by the house, awards an additional bonus.
+// Records `key` (upper case, or the wildcard) as turned in by `house`, and its finish if that was
+// its last key. Returns the id of the `turned_in_keys` row. Call within a transaction.
 #[cfg(feature = "ssr")]
-pub fn update_crossword_state(
+fn record_turned_in_key(
     conn: &mut SqliteConnection,
-    guest_id: i32,
-    new_state: &CrosswordState,
-) -> Result<(), diesel::result::Error> {
-    conn.transaction(|conn| {
-        // Getch the guest to get house_id.
-        let guest: Guest = guests::table
-            .filter(guests::id.eq(guest_id))
-            .filter(guests::is_active.eq(1i32))
-            .select(Guest::as_select())
-            .first(conn)?;
-        let house_id = guest.house_id.ok_or(diesel::result::Error::NotFound)?;
+    house: &House,
+    key: char,
+    points: &[i32],
+) -> Result<i32, diesel::result::Error> {
+    let mut turned_in = house_keys_view(conn, house)?.turned_in;
+    if let Some(message) = turn_in_error(&house.name, &turned_in, key) {
+        return Err(diesel::result::Error::QueryBuilderError(Box::new(
+            IoError::other(message),
+        )));
+    }
+    let turned_in_key_id = diesel::insert_into(turned_in_keys::table)
+        .values(&NewTurnedInKey {
+            house_id: house.id,
+            key: key.to_string(),
+        })
+        .returning(turned_in_keys::id)
+        .get_result(conn)?;
 
-        // Fetch the old state to compare completions.
-        let old_db_state: Option<DbCrosswordState> = crossword_states::table
-            .filter(crossword_states::guest_id.eq(guest_id))
-            .first(conn)
-            .optional()?;
-        let old_completions = match old_db_state {
-            Some(old) => CrosswordState::from(old.state.clone()).completions,
-            None => [false; 7],
+    turned_in.push(key);
+    if missing_keys(&house.name, &turned_in).is_empty() {
+        let finished: i64 = key_completions::table.count().get_result(conn)?;
+        let place = finished as i32 + 1;
+        let amount = points.get(finished as usize).copied().unwrap_or(0);
+        let award = (amount != 0)
+            .then(|| {
+                award_points_to_house(
+                    conn,
+                    house.id,
+                    amount,
+                    &format!("Turned in all their keys, place {}", place),
+                )
+            })
+            .transpose()?;
+        diesel::insert_into(key_completions::table)
+            .values(&NewKeyCompletion {
+                house_id: house.id,
+                place,
+                point_award_id: award.map(|award| award.id),
+            })
+            .execute(conn)?;
+    }
+    Ok(turned_in_key_id)
+}
+
+/// Takes back the last `key` a house turned in, e.g. one recorded by mistake. Once a house has all
+/// its keys its place is settled, so nothing can be taken back any more.
+#[cfg(feature = "ssr")]
+pub fn take_back_key(
+    conn: &mut SqliteConnection,
+    house_id: i32,
+    key: char,
+) -> Result<HouseKeys, diesel::result::Error> {
+    write_transaction(conn, |conn| {
+        let house = get_house(conn, house_id)?;
+        let invalid = |message: String| {
+            diesel::result::Error::QueryBuilderError(Box::new(IoError::other(message)))
         };
+        if house_keys_view(conn, &house)?.place.is_some() {
+            return Err(invalid(format!(
+                "{} has already turned in all its keys",
+                house.name
+            )));
+        }
+        let key = key.to_ascii_uppercase();
+        let last: i32 = turned_in_keys::table
+            .filter(turned_in_keys::house_id.eq(house_id))
+            .filter(turned_in_keys::key.eq(key.to_string()))
+            .order(turned_in_keys::id.desc())
+            .select(turned_in_keys::id)
+            .first(conn)
+            .optional()?
+            .ok_or_else(|| invalid(format!("{} hasn't turned in a {}", house.name, key)))?;
+        diesel::delete(turned_in_keys::table.find(last)).execute(conn)?;
+        house_keys_view(conn, &house)
+    })
+}
 
-        // Query the house's initial completion count before any inserts.
-        let initial_count: i64 = house_crossword_completions::table
-            .filter(house_crossword_completions::house_id.eq(house_id))
-            .count()
-            .get_result(conn)?;
+/// Fetches the physical keys with the house each was claimed for, if any, making them the first
+/// time: a set of letters for each house (see `key_set`) and `wildcards` wildcards, each with a
+/// new claim token.
+#[cfg(feature = "ssr")]
+pub fn get_physical_keys(
+    conn: &mut SqliteConnection,
+    wildcards: usize,
+) -> Result<Vec<(DbPhysicalKey, Option<String>)>, diesel::result::Error> {
+    write_transaction(conn, |conn| {
+        let made: i64 = physical_keys::table.count().get_result(conn)?;
+        if made == 0 {
+            let houses = get_all_houses(conn)?;
+            let names: Vec<&str> = houses.iter().map(|house| house.name.as_str()).collect();
+            let new_keys: Vec<NewPhysicalKey> = key_set(&names, wildcards)
+                .into_iter()
+                .map(|key| NewPhysicalKey {
+                    key: key.to_string(),
+                    token: Uuid::new_v4().to_string(),
+                })
+                .collect();
+            diesel::insert_into(physical_keys::table)
+                .values(&new_keys)
+                .execute(conn)?;
+        }
+        physical_keys::table
+            .left_join(turned_in_keys::table.inner_join(houses::table))
+            .order(physical_keys::id)
+            .select((DbPhysicalKey::as_select(), houses::name.nullable()))
+            .load(conn)
+    })
+}
 
-        // Check for new completions and award points if first for the house. Track any new
-        // insertions.
-        let mut new_inserts_count = 0;
-        for i in 0..7 {
-            if !old_completions[i] && new_state.completions[i] {
-                // This guest just completed word i.
-                if !house_has_completed_word(conn, house_id, i as i32)? {
-                    // First time for for the house; award 5 points and mark completed.
-                    award_points_to_house(
-                        conn,
-                        house_id,
-                        5,
-                        &format!("Crossword word {} completed by house", i),
-                    )?;
-                    insert_house_word_completion(conn, house_id, i as i32)?;
-                    new_inserts_count += 1;
-                }
-            }
+/// A guest scanning physical key `key_id`, whose QR code carries `token`, to turn it in for their
+/// house. A key the house can use is recorded like `turn_in_key` (scoring `points` if it is the
+/// house's last), and can't be claimed again unless it is taken back. A letter that isn't in the
+/// house's name costs the guest `penalty` points and isn't turned in; scanning it again returns
+/// the same outcome without charging the guest twice.
+#[cfg(feature = "ssr")]
+pub fn claim_physical_key(
+    conn: &mut SqliteConnection,
+    guest_id: i32,
+    key_id: i32,
+    token: &str,
+    points: &[i32],
+    penalty: i32,
+) -> Result<KeyClaim, diesel::result::Error> {
+    write_transaction(conn, |conn| {
+        let invalid = |message: String| {
+            diesel::result::Error::QueryBuilderError(Box::new(IoError::other(message)))
+        };
+        let physical_key: DbPhysicalKey = physical_keys::table
+            .find(key_id)
+            .filter(physical_keys::token.eq(token))
+            .select(DbPhysicalKey::as_select())
+            .first(conn)
+            .optional()?
+            .ok_or_else(|| invalid("That isn't one of the keys".to_string()))?;
+        let key = physical_key.key.chars().next().unwrap_or(WILDCARD);
+        if let Some(turned_in_key_id) = physical_key.turned_in_key_id {
+            let claimed_by: String = turned_in_keys::table
+                .find(turned_in_key_id)
+                .inner_join(houses::table)
+                .select(houses::name)
+                .first(conn)?;
+            return Err(invalid(format!(
+                "That {} has already been turned in for {}",
+                key, claimed_by
+            )));
         }
 
-        // Check if this update caused the house to reach all 7 completions.
-        let effective_final_count = initial_count + new_inserts_count as i64;
-        if effective_final_count == 7 {
-            award_points_to_house(conn, house_id, 15, "Crossword completion bonus")?;
+        let house_id = active_guest_house_id(conn, guest_id)?;
+        let house = get_house(conn, house_id)?;
+        if !is_house_key(&house.name, key) {
+            // A key the guest already scanned (say, on reloading the page) isn't charged again.
+            let earlier_penalty: Option<i32> = wrong_key_claims::table
+                .filter(wrong_key_claims::guest_id.eq(guest_id))
+                .filter(wrong_key_claims::physical_key_id.eq(key_id))
+                .select(wrong_key_claims::penalty)
+                .first(conn)
+                .optional()?;
+            let penalty = match earlier_penalty {
+                Some(earlier_penalty) => earlier_penalty,
+                None => {
+                    let award = (penalty != 0)
+                        .then(|| {
+                            award_points_to_guest(
+                                conn,
+                                guest_id,
+                                -penalty,
+                                &format!("Claimed a {} key, which isn't in {}", key, house.name),
+                            )
+                        })
+                        .transpose()?;
+                    diesel::insert_into(wrong_key_claims::table)
+                        .values(&NewWrongKeyClaim {
+                            guest_id,
+                            physical_key_id: key_id,
+                            penalty,
+                            point_award_id: award.map(|award| award.id),
+                        })
+                        .execute(conn)?;
+                    penalty
+                }
+            };
+            return Ok(KeyClaim {
+                key,
+                penalty: Some(penalty),
+                repeat: earlier_penalty.is_some(),
+                house: house_keys_view(conn, &house)?,
+            });
         }
 
-        // Replace the state in DB.
-        diesel::delete(crossword_states::table.filter(crossword_states::guest_id.eq(guest_id)))
-            .execute(conn)?;
-        let db_state = NewDbCrosswordState {
-            guest_id,
-            state: new_state.clone().into(),
-            updated_at: Utc::now().naive_utc(),
-        };
-        diesel::insert_into(crossword_states::table)
-            .values(&db_state)
+        let turned_in_key_id = record_turned_in_key(conn, &house, key, points)?;
+        diesel::update(physical_keys::table.find(key_id))
+            .set(physical_keys::turned_in_key_id.eq(Some(turned_in_key_id)))
             .execute(conn)?;
-
-        Ok(())
+        Ok(KeyClaim {
+            key,
+            penalty: None,
+            repeat: false,
+            house: house_keys_view(conn, &house)?,
+        })
     })
 }
 
@@ -1150,6 +3294,40 @@ mod tests {
     use crate::schema::houses::dsl::*;
     use chrono::Utc;
 
+    fn horcrux_hunt() -> Puzzle {
+        Puzzle::load(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/puzzles/horcrux_hunt.json"
+        ))
+        .expect("Failed to load the Horcrux Hunt puzzle")
+    }
+
+    // Types the answers of some words into a guest's own crossword in one batch of edits, on top
+    // of its current version.
+    fn type_words(
+        conn: &mut SqliteConnection,
+        puzzle: &Puzzle,
+        guest_id: i32,
+        word_idxs: impl IntoIterator<Item = usize>,
+    ) -> Result<CrosswordStateUpdate, diesel::result::Error> {
+        let version = get_or_init_crossword_state(conn, puzzle, guest_id)?.version;
+        let edits: Vec<CellEdit> = word_idxs
+            .into_iter()
+            .flat_map(|word_idx| {
+                let word = &puzzle.words[word_idx];
+                word.cells()
+                    .zip(word.answer.chars())
+                    .map(|((row, col), letter)| CellEdit {
+                        row,
+                        col,
+                        letter: Some(letter),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        edit_crossword_state(conn, puzzle, guest_id, version, &edits)
+    }
+
     // Helper to run a test in a transaction. This always rolls back the transaction at the end of
     // the test to maintain a clean slate in the database.
     fn run_test_in_transaction<F>(test_fn: F)
@@ -1234,6 +3412,94 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_compute_house_targets_staged() {
+        // The original party: 37 guests, 4 houses, with a reveal stage at the halfway point.
+        let targets = compute_house_targets(37, &[0, 0, 0, 0], &[0.5], &[]);
+        assert_eq!(targets.iter().sum::<usize>(), 18);
+        assert!(targets.iter().all(|&target| target == 4 || target == 5));
+
+        // Once the first stage is done, the whole party is balanced.
+        let targets = compute_house_targets(37, &[5, 5, 4, 4], &[0.5], &[]);
+        assert_eq!(targets.iter().sum::<usize>(), 37);
+        assert!(targets.iter().all(|&target| target == 9 || target == 10));
+
+        // Without stages, targets cover everyone from the start.
+        let targets = compute_house_targets(100, &[0; 5], &[], &[]);
+        assert_eq!(targets, vec![20; 5]);
+
+        // Stages outside (0, 1) are ignored.
+        let targets = compute_house_targets(8, &[0, 0], &[0.0, 1.0, 1.5], &[]);
+        assert_eq!(targets, vec![4, 4]);
+    }
+
+    #[test]
+    fn test_compute_house_targets_extras_and_caps() {
+        // Leftover places go to houses with fewer guests right now.
+        let targets = compute_house_targets(10, &[0, 2, 0], &[], &[]);
+        assert_eq!(targets, vec![4, 3, 3]);
+
+        // A capped house gives its share to the others.
+        let targets = compute_house_targets(12, &[0, 0, 0], &[], &[Some(2), None, None]);
+        assert_eq!(targets, vec![2, 5, 5]);
+
+        // If every house is capped below the stage size, targets stop at the caps.
+        let targets = compute_house_targets(12, &[1, 1], &[], &[Some(3), Some(4)]);
+        assert_eq!(targets, vec![3, 4]);
+
+        // Odd party sizes and house counts.
+        let targets = compute_house_targets(7, &[0; 3], &[], &[]);
+        assert_eq!(targets, vec![3, 2, 2]);
+        assert_eq!(compute_house_targets(1, &[0], &[0.5], &[]), vec![1]);
+    }
+
+    #[test]
+    fn test_register_guest_sorting_hat() {
+        run_test_in_transaction(|conn| {
+            let inserted_id: i32 = diesel::insert_into(guests::table)
+                .values(&NewGuest {
+                    name: "Sorted Guest",
+                    house_id: None,
+                    character: None,
+                    registered_at: None,
+                })
+                .returning(guests::id)
+                .get_result(conn)?;
+
+            // Only house 3 has room left.
+            let plan = SortingPlan {
+                reveal_stages: vec![],
+                house_caps: HashMap::from([(1, 0), (2, 0), (4, 0)]),
+            };
+            let (guest, _) = register_guest_with_plan(conn, inserted_id, None, "Luna", &plan)?;
+            assert_eq!(guest.house_id, Some(3));
+
+            // With every house full, the Sorting Hat refuses.
+            let other_id: i32 = diesel::insert_into(guests::table)
+                .values(&NewGuest {
+                    name: "Unsorted Guest",
+                    house_id: None,
+                    character: None,
+                    registered_at: None,
+                })
+                .returning(guests::id)
+                .get_result(conn)?;
+            let full_plan = SortingPlan {
+                reveal_stages: vec![],
+                house_caps: HashMap::from([(1, 0), (2, 0), (3, 1), (4, 0)]),
+            };
+            let err = register_guest_with_plan(conn, other_id, None, "Ginny", &full_plan)
+                .expect_err("Should fail when all houses are full");
+            assert!(matches!(err, diesel::result::Error::QueryBuilderError(_)));
+
+            // The default plan sorts into one of the houses regardless of party size.
+            let (guest, _) = register_guest(conn, other_id, None, "Ginny")?;
+            assert!(matches!(guest.house_id, Some(1..=4)));
+
+            Ok(())
+        });
+    }
+
     #[test]
     fn test_get_guest_by_token() {
         run_test_in_transaction(|conn| {
@@ -1387,6 +3653,98 @@ mod tests {
             assert!(all_houses.iter().find(|h| h.name == "Ravenclaw").is_some());
             assert!(all_houses.iter().find(|h| h.name == "Slytherin").is_some());
 
+            // Display metadata comes with each house.
+            let gryffindor = all_houses.iter().find(|h| h.name == "Gryffindor").unwrap();
+            assert_eq!(gryffindor.slug, "gryffindor");
+            assert_eq!(gryffindor.primary_color, "#d32f2f");
+            assert_eq!(gryffindor.secondary_color, "#ffd700");
+            assert_eq!(gryffindor.crest.as_deref(), Some("/crests/gryffindor.svg"));
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_get_house_crossword_progress_extra_house() {
+        run_test_in_transaction(|conn| {
+            // A fifth house gets its own row, placed by name like get_all_houses.
+            diesel::insert_into(crate::schema::houses::table)
+                .values((
+                    crate::schema::houses::name.eq("Beauxbatons"),
+                    crate::schema::houses::slug.eq("beauxbatons"),
+                ))
+                .execute(conn)?;
+            let beauxbatons_id: i32 = crate::schema::houses::table
+                .filter(crate::schema::houses::slug.eq("beauxbatons"))
+                .select(crate::schema::houses::id)
+                .first(conn)?;
+            insert_house_word_completion(conn, beauxbatons_id, 2)?;
+
+            let all_houses = get_all_houses(conn)?;
+            let matrix = get_house_crossword_progress(conn, 7)?;
+            assert_eq!(matrix.len(), 5);
+            assert_eq!(all_houses[0].name, "Beauxbatons");
+            assert_eq!(
+                matrix[0],
+                vec![false, false, true, false, false, false, false]
+            );
+            assert!(matrix[1..].iter().all(|row| row.iter().all(|&done| !done)));
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_sorting_hat_delivery_log() {
+        run_test_in_transaction(|conn| {
+            let guest_id: i32 = diesel::insert_into(guests::table)
+                .values(&NewGuest {
+                    name: "Hat Tester",
+                    house_id: Some(1),
+                    character: None,
+                    registered_at: None,
+                })
+                .returning(guests::id)
+                .get_result(conn)?;
+            let first = create_sorting_hat_delivery(conn, Some(guest_id), 1)?;
+            let second = create_sorting_hat_delivery(conn, None, 2)?;
+            assert_eq!(first.status, DELIVERY_PENDING);
+            assert_eq!(first.attempts, 0);
+
+            // A failed attempt keeps the delivery pending until we give up on it.
+            let retried = record_sorting_hat_attempt(conn, first.id, Some("timed out"), false)?;
+            assert_eq!(retried.status, DELIVERY_PENDING);
+            assert_eq!(retried.attempts, 1);
+            assert_eq!(retried.last_error.as_deref(), Some("timed out"));
+            let failed = record_sorting_hat_attempt(conn, first.id, Some("timed out"), true)?;
+            assert_eq!(failed.status, DELIVERY_FAILED);
+            assert_eq!(failed.attempts, 2);
+
+            // Finished deliveries can't be attempted again.
+            assert!(record_sorting_hat_attempt(conn, first.id, None, false).is_err());
+
+            let pending: Vec<i32> = get_pending_sorting_hat_deliveries(conn)?
+                .iter()
+                .map(|delivery| delivery.id)
+                .collect();
+            assert_eq!(pending, vec![second.id]);
+
+            let delivered = record_sorting_hat_attempt(conn, second.id, None, false)?;
+            assert_eq!(delivered.status, DELIVERY_DELIVERED);
+            assert_eq!(delivered.last_error, None);
+
+            let log = get_sorting_hat_deliveries(conn, 10)?;
+            assert_eq!(log.len(), 2);
+            assert_eq!(log[0].id, second.id); // newest first
+            assert_eq!(log[0].guest_name, None);
+            assert_eq!(log[1].guest_name.as_deref(), Some("Hat Tester"));
+            assert_eq!(log[1].status, DELIVERY_FAILED);
+            assert_eq!(
+                log[1].house_name,
+                get_house(conn, 1)?.name,
+                "Delivery should name its house"
+            );
+
             Ok(())
         });
     }
@@ -1665,6 +4023,131 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_parse_roster_csv() {
+        let names = parse_roster(
+            "name,notes\n\
+             Leila S,vegetarian\n\
+             \n\
+             # late RSVPs\n\
+             \"Potter, Harry\",\n\
+             \"Quoted \"\"Nick\"\"\"\n",
+        )
+        .expect("CSV should parse");
+        assert_eq!(names, vec!["Leila S", "Potter, Harry", "Quoted \"Nick\""]);
+
+        let err = parse_roster("\"Unterminated\n").expect_err("Should reject open quote");
+        assert!(err.contains("line 1"));
+    }
+
+    #[test]
+    fn test_parse_roster_json() {
+        let names = parse_roster(r#"["Leila S", {"name": "Gautam A", "plus_one": true}]"#)
+            .expect("JSON should parse");
+        assert_eq!(names, vec!["Leila S", "Gautam A"]);
+
+        assert!(parse_roster(r#"[{"first": "Leila"}]"#).is_err());
+        assert!(parse_roster(r#"[42]"#).is_err());
+        assert!(parse_roster("[not json").is_err());
+    }
+
+    #[test]
+    fn test_import_guest_roster() {
+        run_test_in_transaction(|conn| {
+            let before: i64 = guests::table.count().get_result(conn)?;
+
+            let names: Vec<String> = vec![
+                "  Roster   Guest One ".to_string(),
+                "Roster Guest Two".to_string(),
+                "roster guest one".to_string(),
+            ];
+            let report = import_guest_roster(conn, &names, false)?;
+            assert_eq!(
+                report.imported,
+                vec!["Roster Guest One", "Roster Guest Two"]
+            );
+            assert_eq!(report.duplicates, vec!["roster guest one"]);
+            assert!(report.invalid.is_empty());
+            assert_eq!(report.removed, 0);
+
+            let after: i64 = guests::table.count().get_result(conn)?;
+            assert_eq!(after, before + 2);
+            let imported: Guest = guests::table
+                .filter(guests::name.eq("Roster Guest One"))
+                .select(Guest::as_select())
+                .first(conn)?;
+            assert_eq!(imported.is_active, 0);
+            assert!(imported.house_id.is_none());
+            assert!(imported.registered_at.is_none());
+
+            // Importing again only reports duplicates against the existing guests.
+            let report = import_guest_roster(conn, &names[..2], false)?;
+            assert!(report.imported.is_empty());
+            assert_eq!(report.duplicates.len(), 2);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_import_guest_roster_invalid() {
+        run_test_in_transaction(|conn| {
+            let before: i64 = guests::table.count().get_result(conn)?;
+
+            let names: Vec<String> = vec![
+                "Valid Name".to_string(),
+                "   ".to_string(),
+                "x".repeat(MAX_GUEST_NAME_LEN + 1),
+            ];
+            let report = import_guest_roster(conn, &names, false)?;
+            assert!(report.imported.is_empty());
+            assert_eq!(report.invalid.len(), 2);
+            assert!(report.invalid[0].starts_with("Entry 2"));
+            assert!(report.invalid[1].starts_with("Entry 3"));
+
+            // Nothing is imported when any entry is invalid.
+            let after: i64 = guests::table.count().get_result(conn)?;
+            assert_eq!(after, before);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_import_guest_roster_replace() {
+        run_test_in_transaction(|conn| {
+            let registered_id: i32 = diesel::insert_into(guests::table)
+                .values(&NewGuest {
+                    name: "Already Sorted",
+                    house_id: None,
+                    character: None,
+                    registered_at: None,
+                })
+                .returning(guests::id)
+                .get_result(conn)?;
+            register_guest(conn, registered_id, Some(1i32), "Neville Longbottom")?;
+            let never_registered: i64 = guests::table
+                .filter(guests::registered_at.is_null())
+                .count()
+                .get_result(conn)?;
+
+            let names = vec!["New Party Guest".to_string(), "Already Sorted".to_string()];
+            let report = import_guest_roster(conn, &names, true)?;
+            assert_eq!(report.removed as i64, never_registered);
+            assert_eq!(report.imported, vec!["New Party Guest"]);
+            assert_eq!(report.duplicates, vec!["Already Sorted"]);
+
+            // Only the registered guest and the newly imported one remain.
+            let remaining: Vec<String> = guests::table
+                .select(guests::name)
+                .order(guests::name)
+                .load(conn)?;
+            assert_eq!(remaining, vec!["Already Sorted", "New Party Guest"]);
+
+            Ok(())
+        });
+    }
+
     #[test]
     fn test_create_admin_session() {
         run_test_in_transaction(|conn| {
@@ -1806,7 +4289,7 @@ mod tests {
             let log_entry = &awards[0];
             assert_eq!(log_entry.id, award.id);
             assert_eq!(log_entry.guest_name, Some("Award Guest".to_string()));
-            assert_eq!(log_entry.house_name, None);
+            assert_eq!(log_entry.house_name, Some("Gryffindor".to_string()));
             assert_eq!(log_entry.amount, 10);
             assert_eq!(log_entry.reason, "No reason".to_string());
             assert!(log_entry.awarded_at.and_utc().timestamp() > 0);
@@ -1849,35 +4332,266 @@ mod tests {
                 .get_result(conn)?;
             let id_2: i32 = diesel::insert_into(guests::table)
                 .values(&NewGuest {
-                    name: "Guest 2",
+                    name: "Guest 2",
+                    house_id: None,
+                    character: None,
+                    registered_at: None,
+                })
+                .returning(guests::id)
+                .get_result(conn)?;
+
+            let (guest_1, _) = register_guest(conn, id_1, Some(1i32), "Fred Weasley")?;
+            award_points_to_guest(conn, guest_1.id, 10, "First")?;
+            std::thread::sleep(std::time::Duration::from_millis(1));
+            award_points_to_house(conn, 4, 5, "Second")?;
+            std::thread::sleep(std::time::Duration::from_millis(1));
+            let (guest_2, _) = register_guest(conn, id_2, Some(3i32), "George Weasley")?;
+            award_points_to_guest(conn, guest_2.id, 5, "Third")?;
+            std::thread::sleep(std::time::Duration::from_millis(1));
+            award_points_to_guest(conn, guest_1.id, 20, "Fourth")?;
+
+            let awards = get_all_point_awards(conn)?;
+            assert_eq!(awards.len(), 4);
+            assert_eq!(awards[0].reason, "Fourth".to_string());
+            assert_eq!(awards[1].reason, "Third".to_string());
+            assert_eq!(awards[2].reason, "Second".to_string());
+            assert_eq!(awards[3].reason, "First".to_string());
+
+            let recent = get_recent_point_awards(conn, 2)?;
+            assert_eq!(recent.len(), 2);
+            assert_eq!(recent[0].reason, "Fourth".to_string());
+            assert_eq!(recent[1].reason, "Third".to_string());
+
+            let guest_1_awards = get_guest_point_awards(conn, guest_1.id)?;
+            assert_eq!(guest_1_awards.len(), 2);
+            assert_eq!(guest_1_awards[0].reason, "Fourth".to_string());
+            assert_eq!(guest_1_awards[1].reason, "First".to_string());
+            assert_eq!(guest_1_awards[1].amount, 10);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_revert_point_award() {
+        run_test_in_transaction(|conn| {
+            let guest_id: i32 = diesel::insert_into(guests::table)
+                .values(&NewGuest {
+                    name: "Guest 1",
+                    house_id: None,
+                    character: None,
+                    registered_at: None,
+                })
+                .returning(guests::id)
+                .get_result(conn)?;
+            let (guest, _) = register_guest(conn, guest_id, Some(2i32), "Neville Longbottom")?;
+            let house_score = |conn: &mut SqliteConnection, house_id: i32| {
+                crate::schema::houses::table
+                    .filter(crate::schema::houses::id.eq(house_id))
+                    .select(crate::schema::houses::score)
+                    .first::<i32>(conn)
+            };
+            let hufflepuff_before = house_score(conn, 2)?;
+            let slytherin_before = house_score(conn, 4)?;
+
+            award_points_to_guest(conn, guest.id, 15, "Costume")?;
+            award_points_to_house(conn, 4, 7, "Trivia")?;
+            let awards = get_all_point_awards(conn)?;
+            let guest_award = awards.iter().find(|a| a.reason == "Costume").unwrap().id;
+            let house_award = awards.iter().find(|a| a.reason == "Trivia").unwrap().id;
+
+            let reversal = revert_point_award(conn, guest_award)?;
+            assert_eq!(reversal.amount, -15);
+            assert_eq!(reversal.guest_id, Some(guest.id));
+            assert_eq!(reversal.reverts_award_id, Some(guest_award));
+            assert_eq!(reversal.reason, "Reverted: Costume");
+            revert_point_award(conn, house_award)?;
+
+            assert_eq!(house_score(conn, 2)?, hufflepuff_before);
+            assert_eq!(house_score(conn, 4)?, slytherin_before);
+            let (guest, _) = get_guest_details(conn, guest.id)?;
+            assert_eq!(guest.personal_score, 0);
+
+            // Both entries stay in the log, linked to each other.
+            let awards = get_all_point_awards(conn)?;
+            assert_eq!(awards.len(), 4);
+            let original = awards.iter().find(|a| a.id == guest_award).unwrap();
+            assert_eq!(original.reverted_by, Some(reversal.id));
+            let logged_reversal = awards.iter().find(|a| a.id == reversal.id).unwrap();
+            assert_eq!(logged_reversal.reverts_award_id, Some(guest_award));
+            assert_eq!(logged_reversal.reverted_by, None);
+
+            // Neither an award nor its reversal can be reverted again.
+            assert!(revert_point_award(conn, guest_award).is_err());
+            assert!(revert_point_award(conn, reversal.id).is_err());
+            assert_eq!(house_score(conn, 2)?, hufflepuff_before);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_verify_and_reconcile_scores() {
+        run_test_in_transaction(|conn| {
+            // Start from a ledger that matches, whatever the database held before.
+            reconcile_scores(conn)?;
+            assert!(verify_scores(conn)?.is_empty());
+
+            let guest_id: i32 = diesel::insert_into(guests::table)
+                .values(&NewGuest {
+                    name: "Guest 1",
+                    house_id: None,
+                    character: None,
+                    registered_at: None,
+                })
+                .returning(guests::id)
+                .get_result(conn)?;
+            let (guest, _) = register_guest(conn, guest_id, Some(3i32), "Luna Lovegood")?;
+            award_points_to_guest(conn, guest.id, 12, "Costume")?;
+            award_points_to_house(conn, 1, 4, "Trivia")?;
+            assert!(verify_scores(conn)?.is_empty());
+
+            // Drift the cached scores behind the ledger's back.
+            diesel::update(crate::schema::houses::table.filter(crate::schema::houses::id.eq(1)))
+                .set(crate::schema::houses::score.eq(crate::schema::houses::score + 100))
+                .execute(conn)?;
+            diesel::update(guests::table.filter(guests::id.eq(guest.id)))
+                .set(guests::personal_score.eq(0))
+                .execute(conn)?;
+
+            let mismatches = verify_scores(conn)?;
+            assert_eq!(mismatches.len(), 2);
+            assert_eq!(mismatches[0].house_id, Some(1));
+            assert_eq!(mismatches[0].cached - mismatches[0].ledger, 100);
+            assert_eq!(mismatches[1].guest_id, Some(guest.id));
+            assert_eq!(mismatches[1].cached, 0);
+            assert_eq!(mismatches[1].ledger, 12);
+
+            // Verifying doesn't change anything; reconciling fixes both.
+            assert_eq!(verify_scores(conn)?, mismatches);
+            assert_eq!(reconcile_scores(conn)?, mismatches);
+            assert!(verify_scores(conn)?.is_empty());
+            let (guest, _) = get_guest_details(conn, guest.id)?;
+            assert_eq!(guest.personal_score, 12);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_awards_stay_with_house_after_resort() {
+        run_test_in_transaction(|conn| {
+            reconcile_scores(conn)?;
+
+            let guest_id: i32 = diesel::insert_into(guests::table)
+                .values(&NewGuest {
+                    name: "Guest 1",
                     house_id: None,
                     character: None,
                     registered_at: None,
                 })
                 .returning(guests::id)
                 .get_result(conn)?;
-
-            let (guest_1, _) = register_guest(conn, id_1, Some(1i32), "Fred Weasley")?;
-            award_points_to_guest(conn, guest_1.id, 10, "First")?;
-            std::thread::sleep(std::time::Duration::from_millis(1));
-            award_points_to_house(conn, 4, 5, "Second")?;
-            std::thread::sleep(std::time::Duration::from_millis(1));
-            let (guest_2, _) = register_guest(conn, id_2, Some(3i32), "George Weasley")?;
-            award_points_to_guest(conn, guest_2.id, 5, "Third")?;
-            std::thread::sleep(std::time::Duration::from_millis(1));
-            award_points_to_guest(conn, guest_1.id, 20, "Fourth")?;
-
-            let awards = get_all_point_awards(conn)?;
-            assert_eq!(awards.len(), 4);
-            assert_eq!(awards[0].reason, "Fourth".to_string());
-            assert_eq!(awards[1].reason, "Third".to_string());
-            assert_eq!(awards[2].reason, "Second".to_string());
-            assert_eq!(awards[3].reason, "First".to_string());
-
+            let (guest, _) = register_guest(conn, guest_id, Some(3i32), "Luna Lovegood")?;
+            let award = award_points_to_guest(conn, guest.id, 12, "Costume")?;
+            assert_eq!(award.house_id, Some(3));
+            let ravenclaw_before = get_house(conn, 3)?.score;
+            let gryffindor_before = get_house(conn, 1)?.score;
+
+            // Re-sorting the guest leaves their earlier points with the old house.
+            reregister_guest(conn, guest.id, Some(1), None)?;
+            assert!(verify_scores(conn)?.is_empty());
+            assert!(reconcile_scores(conn)?.is_empty());
+
+            // Reverting takes the points back from the house the award counted for.
+            let reversal = revert_point_award(conn, award.id)?;
+            assert_eq!(reversal.house_id, Some(3));
+            assert_eq!(get_house(conn, 3)?.score, ravenclaw_before - 12);
+            assert_eq!(get_house(conn, 1)?.score, gryffindor_before);
+            assert!(verify_scores(conn)?.is_empty());
             Ok(())
         });
     }
 
+    #[test]
+    fn test_concurrent_awards_are_not_lost() {
+        use diesel::r2d2::{ConnectionManager, Pool};
+
+        // Hammer a copy of the database from several pooled connections at once, like concurrent
+        // admins and crossword completions do, and check that every award was counted.
+        let path = std::env::temp_dir().join(format!("hp-halloween-stress-{}.db", Uuid::new_v4()));
+        let path = path.to_str().unwrap().to_string();
+        establish_connection()
+            .batch_execute(&format!("VACUUM INTO '{}'", path))
+            .expect("Failed to copy the database");
+
+        let mut conn = establish_connection_to(&path);
+        reconcile_scores(&mut conn).unwrap();
+        let guest_id: i32 = diesel::insert_into(guests::table)
+            .values(&NewGuest {
+                name: "Stress Guest",
+                house_id: None,
+                character: None,
+                registered_at: None,
+            })
+            .returning(guests::id)
+            .get_result(&mut conn)
+            .unwrap();
+        register_guest(&mut conn, guest_id, Some(1i32), "Colin Creevey").unwrap();
+        let house_score = |conn: &mut SqliteConnection, house_id: i32| {
+            crate::schema::houses::table
+                .filter(crate::schema::houses::id.eq(house_id))
+                .select(crate::schema::houses::score)
+                .first::<i32>(conn)
+                .unwrap()
+        };
+        let (house_1_before, house_2_before) =
+            (house_score(&mut conn, 1), house_score(&mut conn, 2));
+
+        const THREADS: i32 = 8;
+        const AWARDS_PER_THREAD: i32 = 25;
+        let pool = Pool::builder()
+            .max_size(THREADS as u32)
+            .connection_customizer(Box::new(SqlitePragmas))
+            .build(ConnectionManager::<SqliteConnection>::new(&path))
+            .unwrap();
+        let workers: Vec<_> = (0..THREADS)
+            .map(|_| {
+                let pool = pool.clone();
+                std::thread::spawn(move || {
+                    let mut conn = pool.get().unwrap();
+                    for i in 0..AWARDS_PER_THREAD {
+                        if i % 2 == 0 {
+                            award_points_to_guest(&mut conn, guest_id, 1, "Stress").unwrap();
+                        } else {
+                            award_points_to_house(&mut conn, 2, 1, "Stress").unwrap();
+                        }
+                    }
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .for_each(|worker| worker.join().unwrap());
+
+        let guest_awards = THREADS * ((AWARDS_PER_THREAD + 1) / 2);
+        let house_awards = THREADS * (AWARDS_PER_THREAD / 2);
+        assert_eq!(house_score(&mut conn, 1), house_1_before + guest_awards);
+        assert_eq!(house_score(&mut conn, 2), house_2_before + house_awards);
+        let (guest, _) = get_guest_details(&mut conn, guest_id).unwrap();
+        assert_eq!(guest.personal_score, guest_awards);
+        let logged: i64 = point_awards::table
+            .filter(point_awards::reason.eq("Stress"))
+            .count()
+            .get_result(&mut conn)
+            .unwrap();
+        assert_eq!(logged, (THREADS * AWARDS_PER_THREAD) as i64);
+        assert!(verify_scores(&mut conn).unwrap().is_empty());
+
+        drop((conn, pool));
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", path, suffix));
+        }
+    }
+
     #[test]
     fn test_house_has_completed_word_nominal() {
         run_test_in_transaction(|conn| {
@@ -1947,14 +4661,13 @@ mod tests {
                 .expect_err("Should fail for non-existent house");
             assert!(matches!(err, diesel::result::Error::DatabaseError { .. }));
 
-            // Invalid word index (out of 0-6) -> fails with DatabaseError (CHECK constraint).
+            // Negative word index -> fails with DatabaseError (CHECK constraint).
             let err = insert_house_word_completion(conn, 1, -1)
                 .expect_err("Should fail for negative word index");
             assert!(matches!(err, diesel::result::Error::DatabaseError { .. }));
 
-            let err = insert_house_word_completion(conn, 1, 7)
-                .expect_err("Should fail for too high word index");
-            assert!(matches!(err, diesel::result::Error::DatabaseError { .. }));
+            // Word indices past 6 are fine; puzzles may have more than seven words.
+            insert_house_word_completion(conn, 1, 7)?;
 
             // Duplicate insertion -> fails with DatabaseError (UNIQUE constraint).
             insert_house_word_completion(conn, 1, 0)?;
@@ -1969,7 +4682,7 @@ mod tests {
     #[test]
     fn test_get_house_crossword_progress_nominal() {
         run_test_in_transaction(|conn| {
-            let matrix = get_house_crossword_progress(conn)?;
+            let matrix = get_house_crossword_progress(conn, 7)?;
             assert_eq!(matrix.len(), 4);
             for row in &matrix {
                 assert_eq!(row.len(), 7);
@@ -1982,7 +4695,7 @@ mod tests {
             insert_house_word_completion(conn, 4, 3)?;
             insert_house_word_completion(conn, 1, 4)?;
 
-            let matrix = get_house_crossword_progress(conn)?;
+            let matrix = get_house_crossword_progress(conn, 7)?;
             assert_eq!(
                 matrix[0],
                 vec![true, false, false, false, true, false, false]
@@ -2003,7 +4716,7 @@ mod tests {
             for i in 0..7i32 {
                 let _ = insert_house_word_completion(conn, 1, i);
             }
-            let matrix = get_house_crossword_progress(conn)?;
+            let matrix = get_house_crossword_progress(conn, 7)?;
             assert!(matrix[0].iter().all(|&c| c));
 
             Ok(())
@@ -2021,7 +4734,7 @@ mod tests {
                     word_index: 0,
                 })
                 .execute(conn)?;
-            let matrix = get_house_crossword_progress(conn)?;
+            let matrix = get_house_crossword_progress(conn, 7)?;
             assert!(matrix.iter().flatten().all(|&c| !c));
 
             // Invalid word_index (>=7) -> ignored.
@@ -2031,7 +4744,7 @@ mod tests {
                     word_index: 7, // invalid
                 })
                 .execute(conn)?;
-            let matrix = get_house_crossword_progress(conn)?;
+            let matrix = get_house_crossword_progress(conn, 7)?;
             assert!(matrix.iter().flatten().all(|&c| !c));
 
             // Negative word_index -> ignored.
@@ -2041,13 +4754,873 @@ mod tests {
                     word_index: -1, // invalid
                 })
                 .execute(conn)?;
-            let matrix = get_house_crossword_progress(conn)?;
+            let matrix = get_house_crossword_progress(conn, 7)?;
             assert!(matrix.iter().flatten().all(|&c| !c));
 
             Ok(())
         });
     }
 
+    #[test]
+    fn test_edit_crossword_state_new_house_words() {
+        run_test_in_transaction(|conn| {
+            let inserted_id: i32 = diesel::insert_into(guests::table)
+                .values(&NewGuest {
+                    name: "Solver",
+                    house_id: None,
+                    character: None,
+                    registered_at: None,
+                })
+                .returning(guests::id)
+                .get_result(conn)?;
+            register_guest(conn, inserted_id, Some(2), "Hermione Granger")?;
+            // Another guest of the same house already solved word 4.
+            insert_house_word_completion(conn, 2, 4)?;
+
+            let puzzle = horcrux_hunt();
+            assert!(!house_has_completed_word(conn, 2, 1)?);
+            assert_eq!(
+                type_words(conn, &puzzle, inserted_id, [1, 4])?.new_house_words,
+                vec![1]
+            );
+
+            // Typing the same words again is not news.
+            assert!(type_words(conn, &puzzle, inserted_id, [1, 4])?
+                .new_house_words
+                .is_empty());
+            let mut expected = vec![false; 7];
+            expected[1] = true;
+            expected[4] = true;
+            assert_eq!(
+                get_or_init_crossword_state(conn, &puzzle, inserted_id)?.completions,
+                expected
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_edit_crossword_state_larger_puzzle() {
+        run_test_in_transaction(|conn| {
+            let inserted_id: i32 = diesel::insert_into(guests::table)
+                .values(&NewGuest {
+                    name: "Solver",
+                    house_id: None,
+                    character: None,
+                    registered_at: None,
+                })
+                .returning(guests::id)
+                .get_result(conn)?;
+            register_guest(conn, inserted_id, Some(3), "Luna Lovegood")?;
+            let score_before = get_house(conn, 3)?.score;
+
+            // A puzzle with more words than the original seven.
+            let mut puzzle = horcrux_hunt();
+            let extra = puzzle.words[0].clone();
+            puzzle.words.push(extra);
+            let new_words = type_words(conn, &puzzle, inserted_id, 0..8)?.new_house_words;
+            assert_eq!(new_words, (0..8).collect::<Vec<_>>());
+            assert!(house_has_completed_word(conn, 3, 7)?);
+            assert_eq!(get_house_crossword_progress(conn, 8)?[2], vec![true; 8]);
+
+            // 5 points per word, plus the bonus for finishing, awarded once.
+            assert_eq!(get_house(conn, 3)?.score, score_before + 8 * 5 + 15);
+            type_words(conn, &puzzle, inserted_id, 0..8)?;
+            assert_eq!(get_house(conn, 3)?.score, score_before + 8 * 5 + 15);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_edit_house_board() {
+        run_test_in_transaction(|conn| {
+            let mut teammates = Vec::new();
+            for (guest_name, house) in [("Padma", 3), ("Cho", 3), ("Draco", 4)] {
+                let guest_id: i32 = diesel::insert_into(guests::table)
+                    .values(&NewGuest {
+                        name: guest_name,
+                        house_id: None,
+                        character: None,
+                        registered_at: None,
+                    })
+                    .returning(guests::id)
+                    .get_result(conn)?;
+                register_guest(conn, guest_id, Some(house), guest_name)?;
+                teammates.push(guest_id);
+            }
+            let (padma, cho, draco) = (teammates[0], teammates[1], teammates[2]);
+            let puzzle = horcrux_hunt();
+            let word = puzzle.words[0].clone();
+            let (row, col) = word.cells().next().unwrap();
+            let edit = |letter: char, version: i32| BoardEdit {
+                row,
+                col,
+                letter: Some(letter),
+                version,
+            };
+
+            // The first write of a cell is attributed to its writer.
+            let update = edit_house_board(conn, &puzzle, padma, &[edit('x', 0)])?;
+            assert_eq!((update.house_id, update.applied), (3, 1));
+            let board = get_house_board(conn, &puzzle, 3)?;
+            assert_eq!(board.filled, vec![(row, col, 'X')]);
+            let cells = board.board.unwrap().cells;
+            assert_eq!(cells[0].guest_name.as_deref(), Some("Padma"));
+            assert_eq!(cells[0].version, 1);
+
+            // A teammate who hasn't seen that write loses; one who has wins.
+            let update = edit_house_board(conn, &puzzle, cho, &[edit('Y', 0)])?;
+            assert_eq!((update.applied, update.rejected), (0, vec![(row, col)]));
+            edit_house_board(conn, &puzzle, cho, &[edit('Y', 1)])?;
+            let cells = get_house_board(conn, &puzzle, 3)?.board.unwrap().cells;
+            assert_eq!(cells[0].guest_name.as_deref(), Some("Cho"));
+            assert_eq!(cells[0].version, 2);
+
+            // Own earlier writes never conflict, but cells outside words and non-letters are refused.
+            edit_house_board(conn, &puzzle, cho, &[edit('Z', 1)])?;
+            assert_eq!(
+                get_house_board(conn, &puzzle, 3)?.filled,
+                vec![(row, col, 'Z')]
+            );
+            let blank = (0..puzzle.rows)
+                .flat_map(|r| (0..puzzle.cols).map(move |c| (r, c)))
+                .find(|&(r, c)| !puzzle.is_word_cell(r, c))
+                .unwrap();
+            let refused = [
+                BoardEdit {
+                    row: blank.0,
+                    col: blank.1,
+                    letter: Some('A'),
+                    version: 0,
+                },
+                edit('7', 3),
+            ];
+            let update = edit_house_board(conn, &puzzle, cho, &refused)?;
+            assert_eq!(update.rejected, vec![blank, (row, col)]);
+
+            // Other houses have boards of their own.
+            assert!(get_house_board(conn, &puzzle, 4)?.filled.is_empty());
+            edit_house_board(conn, &puzzle, draco, &[edit('Q', 0)])?;
+            assert_eq!(
+                get_house_board(conn, &puzzle, 3)?.filled,
+                vec![(row, col, 'Z')]
+            );
+
+            // Solving a word together scores once for the house and freezes the word.
+            let score_before = get_house(conn, 3)?.score;
+            let versions: HashMap<(usize, usize), i32> = get_house_board(conn, &puzzle, 3)?
+                .board
+                .unwrap()
+                .cells
+                .iter()
+                .map(|cell| ((cell.row, cell.col), cell.version))
+                .collect();
+            let edits: Vec<BoardEdit> = word
+                .cells()
+                .zip(word.answer.chars())
+                .map(|((r, c), letter)| BoardEdit {
+                    row: r,
+                    col: c,
+                    letter: Some(letter),
+                    version: versions.get(&(r, c)).copied().unwrap_or(0),
+                })
+                .collect();
+            let (first_half, second_half) = edits.split_at(edits.len() / 2);
+            assert!(edit_house_board(conn, &puzzle, padma, first_half)?
+                .new_house_words
+                .is_empty());
+            let update = edit_house_board(conn, &puzzle, cho, second_half)?;
+            assert_eq!(update.new_house_words, vec![0]);
+            assert_eq!(get_house(conn, 3)?.score, score_before + 5);
+            assert!(get_house_board(conn, &puzzle, 3)?.completions[0]);
+
+            let update = edit_house_board(conn, &puzzle, cho, &[edit('Q', 9)])?;
+            assert_eq!(update.rejected, vec![(row, col)]);
+            assert_eq!(get_house(conn, 3)?.score, score_before + 5);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_take_crossword_hint() {
+        run_test_in_transaction(|conn| {
+            let mut solvers = Vec::new();
+            for (guest_name, house) in [("Ernie", 2), ("Pansy", 4)] {
+                let guest_id: i32 = diesel::insert_into(guests::table)
+                    .values(&NewGuest {
+                        name: guest_name,
+                        house_id: None,
+                        character: None,
+                        registered_at: None,
+                    })
+                    .returning(guests::id)
+                    .get_result(conn)?;
+                register_guest(conn, guest_id, Some(house), guest_name)?;
+                solvers.push(guest_id);
+            }
+            let (ernie, pansy) = (solvers[0], solvers[1]);
+            let puzzle = horcrux_hunt();
+            let hints = HintConfig::default();
+            let word = puzzle.words[0].clone();
+            let (row, col) = word.cells().next().unwrap();
+            let first_letter = word.answer.chars().next();
+            set_house_hint_limit(conn, 2, 2)?;
+            let score_before = get_house(conn, 2)?.score;
+
+            // A letter hint fills in the first missing letter and costs the house.
+            let taken =
+                take_crossword_hint(conn, &puzzle, &hints, ernie, 0, HintKind::Letter, false)?;
+            assert_eq!((taken.house_id, taken.cost), (2, hints.letter_cost));
+            assert!(taken.new_house_words.is_empty());
+            let state = get_or_init_crossword_state(conn, &puzzle, ernie)?;
+            assert_eq!(state.grid[row][col], first_letter);
+            assert_eq!(get_house(conn, 2)?.score, score_before - hints.letter_cost);
+
+            // A word hint solves the word, which scores as usual.
+            let taken =
+                take_crossword_hint(conn, &puzzle, &hints, ernie, 0, HintKind::Word, false)?;
+            assert_eq!(taken.new_house_words, vec![0]);
+            assert!(get_or_init_crossword_state(conn, &puzzle, ernie)?.completions[0]);
+            assert_eq!(
+                get_house(conn, 2)?.score,
+                score_before - hints.letter_cost - hints.word_cost + 5
+            );
+
+            // The house is out of hints; raising the limit still doesn't allow hints for solved words.
+            assert!(
+                take_crossword_hint(conn, &puzzle, &hints, ernie, 1, HintKind::Letter, false)
+                    .is_err()
+            );
+            set_house_hint_limit(conn, 2, 3)?;
+            assert!(
+                take_crossword_hint(conn, &puzzle, &hints, ernie, 0, HintKind::Letter, false)
+                    .is_err()
+            );
+            assert!(set_house_hint_limit(conn, 2, -1).is_err());
+
+            let usage = get_hint_usage(conn, hints.default_limit)?;
+            let hufflepuff = &usage[1];
+            assert_eq!((hufflepuff.used, hufflepuff.limit), (2, 3));
+            assert_eq!(hufflepuff.spent, hints.letter_cost + hints.word_cost);
+            assert_eq!((usage[3].used, usage[3].limit), (0, hints.default_limit));
+
+            // On a house board the letter goes on the board.
+            take_crossword_hint(conn, &puzzle, &hints, pansy, 0, HintKind::Letter, true)?;
+            let board = get_house_board(conn, &puzzle, 4)?;
+            assert_eq!(board.filled, vec![(row, col, first_letter.unwrap())]);
+            assert_eq!(
+                board.board.unwrap().cells[0].guest_name.as_deref(),
+                Some("Pansy")
+            );
+
+            let log = get_crossword_hint_log(conn)?;
+            assert_eq!(log.len(), 3);
+            assert_eq!(log[0].guest_name.as_deref(), Some("Pansy"));
+            assert_eq!((log[1].kind, log[1].word_index), (HintKind::Word, 0));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_edit_crossword_state() {
+        run_test_in_transaction(|conn| {
+            let guest_id: i32 = diesel::insert_into(guests::table)
+                .values(&NewGuest {
+                    name: "Dean",
+                    house_id: None,
+                    character: None,
+                    registered_at: None,
+                })
+                .returning(guests::id)
+                .get_result(conn)?;
+            register_guest(conn, guest_id, Some(1), "Dean Thomas")?;
+            let puzzle = horcrux_hunt();
+            assert_eq!(
+                get_or_init_crossword_state(conn, &puzzle, guest_id)?.version,
+                0
+            );
+
+            let word = &puzzle.words[0];
+            let mut edits: Vec<CellEdit> = word
+                .cells()
+                .zip(word.answer.chars())
+                .map(|((row, col), letter)| CellEdit {
+                    row,
+                    col,
+                    letter: Some(letter.to_ascii_lowercase()),
+                })
+                .collect();
+            let blank = (0..puzzle.rows)
+                .flat_map(|row| (0..puzzle.cols).map(move |col| (row, col)))
+                .find(|&(row, col)| !puzzle.is_word_cell(row, col))
+                .expect("The puzzle should have a blank cell");
+            edits.push(CellEdit {
+                row: blank.0,
+                col: blank.1,
+                letter: Some('A'),
+            });
+            let (row, col) = puzzle.words[1].cells().next().unwrap();
+            edits.push(CellEdit {
+                row,
+                col,
+                letter: Some('7'),
+            });
+
+            let update = edit_crossword_state(conn, &puzzle, guest_id, 0, &edits)?;
+            assert_eq!(update.state.version, 1);
+            assert_eq!(update.rejected, vec![blank, (row, col)]);
+            assert_eq!(update.new_house_words, vec![0]);
+            assert!(update.state.completions[0]);
+            assert!(house_has_completed_word(conn, 1, 0)?);
+
+            // A second device still on version 0 is turned away, and nothing changes.
+            let stale = [CellEdit {
+                row,
+                col,
+                letter: Some('X'),
+            }];
+            let update = edit_crossword_state(conn, &puzzle, guest_id, 0, &stale)?;
+            assert_eq!(update.rejected, vec![(row, col)]);
+            assert_eq!(update.state.version, 1);
+            assert_eq!(update.state.grid[row][col], None);
+
+            // Letters of solved words stay put; erasing elsewhere is fine.
+            let (solved_row, solved_col) = word.cells().next().unwrap();
+            let edits = [
+                CellEdit {
+                    row: solved_row,
+                    col: solved_col,
+                    letter: None,
+                },
+                CellEdit {
+                    row,
+                    col,
+                    letter: None,
+                },
+            ];
+            let update = edit_crossword_state(conn, &puzzle, guest_id, 1, &edits)?;
+            assert_eq!(update.rejected, vec![(solved_row, solved_col)]);
+            assert_eq!(update.state.version, 2);
+
+            // Every save edits the one row in place.
+            assert_eq!(
+                get_or_init_crossword_state(conn, &puzzle, guest_id)?.version,
+                2
+            );
+            let rows: i64 = crossword_states::table
+                .filter(crossword_states::guest_id.eq(guest_id))
+                .count()
+                .get_result(conn)?;
+            assert_eq!(rows, 1);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_crossword_solves() {
+        run_test_in_transaction(|conn| {
+            let mut guest_ids = Vec::new();
+            for (guest_name, house) in [("Ron", 1), ("Ginny", 1), ("Cedric", 2)] {
+                let guest_id: i32 = diesel::insert_into(guests::table)
+                    .values(&NewGuest {
+                        name: guest_name,
+                        house_id: None,
+                        character: None,
+                        registered_at: None,
+                    })
+                    .returning(guests::id)
+                    .get_result(conn)?;
+                register_guest(conn, guest_id, Some(house), guest_name)?;
+                guest_ids.push(guest_id);
+            }
+            let (ron, ginny) = (guest_ids[0], guest_ids[1]);
+            let puzzle = horcrux_hunt();
+
+            // Ron opened the crossword ten minutes ago, Ginny five.
+            for (guest_id, minutes) in [(ron, 10), (ginny, 5)] {
+                get_or_init_crossword_state(conn, &puzzle, guest_id)?;
+                diesel::update(crossword_states::table)
+                    .filter(crossword_states::guest_id.eq(guest_id))
+                    .set(
+                        crossword_states::started_at
+                            .eq(Utc::now().naive_utc() - chrono::Duration::minutes(minutes)),
+                    )
+                    .execute(conn)?;
+            }
+
+            type_words(conn, &puzzle, ron, [0])?;
+            type_words(conn, &puzzle, ginny, [0, 1])?;
+
+            // Typing a solved word again keeps its first solve.
+            let update = type_words(conn, &puzzle, ron, [0])?;
+            assert_eq!(update.rejected.len(), puzzle.words[0].answer.len());
+
+            // Words revealed by a hint score for the house but aren't anyone's solve.
+            let hints = HintConfig::default();
+            let taken = take_crossword_hint(conn, &puzzle, &hints, ron, 2, HintKind::Word, false)?;
+            assert_eq!(taken.new_house_words, vec![2]);
+            let cedric = guest_ids[2];
+            let taken =
+                take_crossword_hint(conn, &puzzle, &hints, cedric, 0, HintKind::Word, true)?;
+            assert_eq!(taken.new_house_words, vec![0]);
+
+            let timeline = get_crossword_timeline(conn)?;
+            let solves: Vec<(&str, usize, bool)> = timeline
+                .iter()
+                .map(|solve| {
+                    (
+                        solve.guest_name.as_str(),
+                        solve.word_index,
+                        solve.first_for_house,
+                    )
+                })
+                .collect();
+            assert_eq!(
+                solves,
+                [("Ron", 0, true), ("Ginny", 0, false), ("Ginny", 1, true)]
+            );
+            let ron_elapsed = timeline[0].elapsed_seconds.unwrap();
+            assert!((600..610).contains(&ron_elapsed));
+            assert!(get_or_init_crossword_state(conn, &puzzle, ron)?.completions[0]);
+
+            // Ginny solved more words; Cedric solved nothing.
+            let leaderboard = get_crossword_leaderboard(conn)?;
+            assert_eq!(leaderboard.len(), 4);
+            let gryffindor = &leaderboard[0];
+            assert_eq!(gryffindor.house.id, 1);
+            let ranking: Vec<(&str, usize, usize)> = gryffindor
+                .solvers
+                .iter()
+                .map(|s| (s.guest_name.as_str(), s.words_solved, s.first_solves))
+                .collect();
+            assert_eq!(ranking, [("Ginny", 2, 1), ("Ron", 1, 1)]);
+            assert_eq!(gryffindor.solvers[1].elapsed_seconds, Some(ron_elapsed));
+            assert!(leaderboard
+                .iter()
+                .all(|entry| entry.solvers.iter().all(|s| s.guest_name != "Cedric")));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_horcrux_claims() {
+        run_test_in_transaction(|conn| {
+            let guest_id: i32 = diesel::insert_into(guests::table)
+                .values(&NewGuest {
+                    name: "Neville",
+                    house_id: None,
+                    character: None,
+                    registered_at: None,
+                })
+                .returning(guests::id)
+                .get_result(conn)?;
+            register_guest(conn, guest_id, Some(1), "Neville Longbottom")?;
+            let puzzle = horcrux_hunt();
+
+            // The word has to be solved before its horcrux can be claimed.
+            assert!(create_horcrux_claim(conn, &puzzle, guest_id, 0, None).is_err());
+            insert_house_word_completion(conn, 1, 0)?;
+            insert_house_word_completion(conn, 1, 1)?;
+            let claim =
+                create_horcrux_claim(conn, &puzzle, guest_id, 0, Some("photo.jpg".to_string()))?;
+            assert_eq!(claim.horcrux, puzzle.words[0].horcrux);
+            assert_eq!(claim.status, "pending");
+            assert!(create_horcrux_claim(conn, &puzzle, guest_id, 0, None).is_err());
+            let other = create_horcrux_claim(conn, &puzzle, guest_id, 1, None)?;
+
+            let statuses = get_house_horcrux_claims(conn, 1, puzzle.words.len())?;
+            assert_eq!(statuses[0], Some(ClaimStatus::Pending));
+            assert_eq!(statuses[2], None);
+            assert_eq!(
+                get_horcrux_claim_photo(conn, claim.id)?.as_deref(),
+                Some("photo.jpg")
+            );
+
+            // Approving scores for the house; a claim is only reviewed once.
+            let score_before = get_house(conn, 1)?.score;
+            let approved = review_horcrux_claim(conn, claim.id, true, 10)?;
+            assert_eq!(approved.status, "approved");
+            assert!(approved.point_award_id.is_some());
+            assert_eq!(get_house(conn, 1)?.score, score_before + 10);
+            assert!(review_horcrux_claim(conn, claim.id, false, 10).is_err());
+
+            // A rejected claim can be made again, and pending claims head the queue.
+            review_horcrux_claim(conn, other.id, false, 10)?;
+            assert_eq!(get_house(conn, 1)?.score, score_before + 10);
+            let retry = create_horcrux_claim(conn, &puzzle, guest_id, 1, None)?;
+            let queue = get_horcrux_claims(conn, &puzzle)?;
+            assert_eq!(queue.len(), 3);
+            assert_eq!(
+                (queue[0].id, queue[0].status),
+                (retry.id, ClaimStatus::Pending)
+            );
+            assert_eq!(queue[0].location, puzzle.words[1].location);
+            assert!(queue.iter().any(|c| c.id == claim.id && c.has_photo));
+            assert_eq!(
+                get_house_horcrux_claims(conn, 1, puzzle.words.len())?[..2],
+                [Some(ClaimStatus::Approved), Some(ClaimStatus::Pending)]
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_horcrux_photo_storage() {
+        use base64::Engine;
+
+        let dir = std::env::temp_dir().join(format!("hp-horcrux-photos-{}", Uuid::new_v4()));
+        let dir = dir.to_str().unwrap();
+        let bytes = b"\x89PNG not really a png";
+        let encoded = base64::engine::general_purpose::STANDARD.encode(bytes);
+        let photo = PhotoUpload {
+            content_type: "image/png".to_string(),
+            data: encoded.clone(),
+        };
+
+        let file_name = save_horcrux_photo(dir, &photo).expect("Photo should be saved");
+        assert!(file_name.ends_with(".png"));
+        assert_eq!(
+            load_horcrux_photo(dir, &file_name).unwrap(),
+            format!("data:image/png;base64,{}", encoded)
+        );
+
+        let gif = PhotoUpload {
+            content_type: "image/gif".to_string(),
+            ..photo.clone()
+        };
+        assert!(save_horcrux_photo(dir, &gif).is_err());
+        let garbage = PhotoUpload {
+            data: "not base64!".to_string(),
+            ..photo
+        };
+        assert!(save_horcrux_photo(dir, &garbage).is_err());
+        assert!(load_horcrux_photo(dir, "../secrets.png").is_err());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_wordle_games() {
+        run_test_in_transaction(|conn| {
+            let guest_id: i32 = diesel::insert_into(guests::table)
+                .values(&NewGuest {
+                    name: "Luna",
+                    house_id: None,
+                    character: None,
+                    registered_at: None,
+                })
+                .returning(guests::id)
+                .get_result(conn)?;
+            let today = NaiveDate::from_ymd_opt(2025, 10, 31).unwrap();
+            let words = WordList::new(&["ghost"], &["stone", "shout", "chase"]).unwrap();
+            let points = [10, 8, 6];
+            let guess =
+                |conn: &mut SqliteConnection, date: NaiveDate, word: &str, hard_mode: bool| {
+                    submit_wordle_guess(conn, guest_id, date, word, &words, &points, hard_mode)
+                };
+
+            // Unsorted guests can look but not play.
+            let game = get_or_start_wordle_game(conn, guest_id, today, &words)?;
+            assert_eq!(game.status, WordleStatus::Playing);
+            assert!(game.guesses.is_empty() && game.answer.is_none());
+            assert!(guess(conn, today, "stone", false).is_err());
+            register_guest(conn, guest_id, Some(3), "Luna Lovegood")?;
+
+            // Words off the list don't count as guesses.
+            let err = guess(conn, today, "zzzzz", false).unwrap_err();
+            assert_eq!(err.to_string(), "Not in the word list");
+            let err = guess(conn, today, "ghosts", false).unwrap_err();
+            assert_eq!(err.to_string(), "Guesses must have 5 letters");
+            let game = guess(conn, today, "stone", false)?;
+            assert_eq!(game.status, WordleStatus::Playing);
+            assert_eq!(game.guesses.len(), 1);
+            assert_eq!(game.guesses[0].word, "STONE");
+            assert_eq!(game.guesses[0].statuses, compute_statuses("STONE", "GHOST"));
+            assert!(game.answer.is_none() && game.points.is_none());
+
+            // Solving in two guesses scores the second entry, for the guest and the house.
+            let score_before = get_house(conn, 3)?.score;
+            let game = guess(conn, today, "Ghost", false)?;
+            assert_eq!(game.status, WordleStatus::Won);
+            assert_eq!(game.answer.as_deref(), Some("GHOST"));
+            assert_eq!(game.points, Some(8));
+            assert_eq!(get_house(conn, 3)?.score, score_before + 8);
+            assert_eq!(
+                get_or_start_wordle_game(conn, guest_id, today, &words)?,
+                game
+            );
+            assert!(guess(conn, today, "stone", false).is_err());
+
+            // A new day is a new game; running out of guesses scores nothing.
+            let tomorrow = today.succ_opt().unwrap();
+            for _ in 0..MAX_GUESSES {
+                guess(conn, tomorrow, "stone", false)?;
+            }
+            let lost = get_or_start_wordle_game(conn, guest_id, tomorrow, &words)?;
+            assert_eq!(lost.status, WordleStatus::Lost);
+            assert_eq!(lost.answer.as_deref(), Some("GHOST"));
+            assert_eq!(lost.points, None);
+            assert_eq!(get_house(conn, 3)?.score, score_before + 8);
+
+            // In hard mode, the O found in place by STONE has to stay there.
+            let day_after = tomorrow.succ_opt().unwrap();
+            guess(conn, day_after, "stone", true)?;
+            let err = guess(conn, day_after, "chase", true).unwrap_err();
+            assert_eq!(err.to_string(), "Letter 3 must be O");
+            assert_eq!(guess(conn, day_after, "shout", true)?.guesses.len(), 2);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_wordle_races() {
+        run_test_in_transaction(|conn| {
+            let mut guest_ids = vec![];
+            for (guest_name, house) in [
+                ("Fred", Some(1)),
+                ("George", Some(1)),
+                ("Draco", Some(2)),
+                ("Colin", None),
+            ] {
+                let guest_id: i32 = diesel::insert_into(guests::table)
+                    .values(&NewGuest {
+                        name: guest_name,
+                        house_id: None,
+                        character: None,
+                        registered_at: None,
+                    })
+                    .returning(guests::id)
+                    .get_result(conn)?;
+                if house.is_some() {
+                    register_guest(conn, guest_id, house, guest_name)?;
+                }
+                guest_ids.push(guest_id);
+            }
+            let [fred, george, draco, colin] = guest_ids[..] else {
+                unreachable!()
+            };
+            let words = WordList::new(&["ghost"], &["stone"]).unwrap();
+            let guess = |conn: &mut SqliteConnection, guest_id: i32, word: &str| {
+                submit_wordle_race_guess(conn, guest_id, word, &words, false)
+            };
+
+            assert_eq!(get_wordle_race(conn)?, None);
+            assert!(get_or_join_wordle_race(conn, fred).is_err());
+            assert!(stop_wordle_race(conn).is_err());
+            assert!(start_wordle_race(conn, &words, 0, 30).is_err());
+
+            // The word stays on the server while the race is on.
+            let race = start_wordle_race(conn, &words, 2, 30)?;
+            assert!(race.is_on() && race.answer.is_none());
+            let err = start_wordle_race(conn, &words, 2, 30).unwrap_err();
+            assert_eq!(err.to_string(), "A Wordle race is already on");
+            let game = guess(conn, fred, "stone")?.0;
+            assert_eq!(game.guesses[0].statuses, compute_statuses("STONE", "GHOST"));
+            assert_eq!(game.answer, None);
+
+            // Unsorted guests can look but not play.
+            assert!(get_or_join_wordle_race(conn, colin)?.guesses.is_empty());
+            assert!(guess(conn, colin, "ghost").is_err());
+
+            // Draco solves first, but it takes two Weasleys to win it for their house.
+            let (game, award) = guess(conn, draco, "ghost")?;
+            assert_eq!(game.status, WordleStatus::Won);
+            assert!(award.is_none());
+            assert!(guess(conn, draco, "ghost").is_err());
+            assert!(guess(conn, fred, "ghost")?.1.is_none());
+            let race = get_wordle_race(conn)?.unwrap();
+            let standing = |house_id: i32| {
+                let house = race.houses.iter().find(|house| house.house_id == house_id);
+                house.map(|house| (house.racers, house.solvers))
+            };
+            assert_eq!(standing(1), Some((1, 1)));
+            assert_eq!(standing(2), Some((1, 1)));
+            assert_eq!(race.racers.len(), 2);
+            assert_eq!(race.racers[0].guest_name, "Fred");
+            assert_eq!(race.racers[0].statuses.len(), 2);
+
+            let score_before = get_house(conn, 1)?.score;
+            let (game, award) = guess(conn, george, "ghost")?;
+            assert_eq!(game.answer.as_deref(), Some("GHOST"));
+            let award = award.expect("The second Weasley should win the race");
+            assert_eq!((award.house_id, award.amount), (Some(1), 30));
+            assert_eq!(get_house(conn, 1)?.score, score_before + 30);
+            let race = get_wordle_race(conn)?.unwrap();
+            assert!(!race.is_on());
+            assert_eq!(race.winning_house_id, Some(1));
+            assert_eq!(race.answer.as_deref(), Some("GHOST"));
+            let err = guess(conn, colin, "ghost").unwrap_err();
+            assert_eq!(err.to_string(), "The Wordle race is over");
+
+            // A race stopped by the admin has no winner.
+            let race = start_wordle_race(conn, &words, 1, 30)?;
+            assert!(get_or_join_wordle_race(conn, fred)?.guesses.is_empty());
+            let race_id = race.id;
+            let race = stop_wordle_race(conn)?;
+            assert_eq!((race.id, race.winning_house_id), (race_id, None));
+            assert_eq!(race.houses.iter().map(|house| house.racers).sum::<i32>(), 1);
+            assert_eq!(get_house(conn, 1)?.score, score_before + 30);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_catch_the_keys() {
+        run_test_in_transaction(|conn| {
+            let points = [40, 30];
+            let all_houses = get_all_houses(conn)?;
+            let house_id_of = |house_name: &str| {
+                all_houses
+                    .iter()
+                    .find(|house| house.name == house_name)
+                    .map(|house| house.id)
+                    .unwrap()
+            };
+            let gryffindor = house_id_of("Gryffindor");
+            let slytherin = house_id_of("Slytherin");
+            let ravenclaw = house_id_of("Ravenclaw");
+
+            let progress = get_house_keys(conn)?;
+            assert_eq!(progress.len(), 4);
+            assert!(progress.iter().all(|house| house.turned_in.is_empty()));
+            let keys = &progress[0];
+            assert_eq!(keys.house_name, "Gryffindor");
+            assert_eq!(keys.keeper, "Hagrid");
+            assert_eq!(keys.missing, keys.keys);
+
+            // Keys that aren't the house's, or that it has enough of, are refused.
+            let err = turn_in_key(conn, gryffindor, 'w', &points).unwrap_err();
+            assert_eq!(err.to_string(), "W is not a letter of Gryffindor");
+            turn_in_key(conn, gryffindor, 'f', &points)?;
+            turn_in_key(conn, gryffindor, 'F', &points)?;
+            assert!(turn_in_key(conn, gryffindor, 'F', &points).is_err());
+            let keys = take_back_key(conn, gryffindor, 'F')?;
+            assert_eq!(keys.turned_in, vec!['F']);
+            assert!(take_back_key(conn, gryffindor, 'G').is_err());
+
+            // Slytherin finishes first, with three wildcards; Gryffindor second.
+            let score_before = get_house(conn, slytherin)?.score;
+            for key in "SLYTHE".chars().chain([WILDCARD; 2]) {
+                let keys = turn_in_key(conn, slytherin, key, &points)?;
+                assert_eq!(keys.place, None);
+            }
+            let keys = turn_in_key(conn, slytherin, WILDCARD, &points)?;
+            assert!(keys.missing.is_empty());
+            assert_eq!((keys.place, keys.points), (Some(1), Some(40)));
+            assert_eq!(get_house(conn, slytherin)?.score, score_before + 40);
+            let err = turn_in_key(conn, slytherin, 'S', &points).unwrap_err();
+            assert_eq!(err.to_string(), "Slytherin has all its keys already");
+            assert!(take_back_key(conn, slytherin, WILDCARD).is_err());
+
+            for key in "GRYFINDOR".chars() {
+                turn_in_key(conn, gryffindor, key, &points)?;
+            }
+            let keys = &get_house_keys(conn)?[0];
+            assert_eq!((keys.place, keys.points), (Some(2), Some(30)));
+
+            // Houses past the end of the points list still get their place.
+            for key in house_keys("Ravenclaw") {
+                turn_in_key(conn, ravenclaw, key, &points)?;
+            }
+            let keys = &get_house_keys(conn)?[2];
+            assert_eq!(keys.house_name, "Ravenclaw");
+            assert_eq!((keys.place, keys.points), (Some(3), None));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_physical_keys() {
+        run_test_in_transaction(|conn| {
+            let points = [40, 30];
+            let ravenclaw = get_all_houses(conn)?
+                .into_iter()
+                .find(|house| house.name == "Ravenclaw")
+                .unwrap()
+                .id;
+            let luna: i32 = diesel::insert_into(guests::table)
+                .values(&NewGuest {
+                    name: "Luna",
+                    house_id: None,
+                    character: None,
+                    registered_at: None,
+                })
+                .returning(guests::id)
+                .get_result(conn)?;
+            register_guest(conn, luna, Some(ravenclaw), "Luna")?;
+
+            // The keys are made once: every house's letters, then the wildcards.
+            let keys = get_physical_keys(conn, 2)?;
+            assert_eq!(keys.len(), 10 + 10 + 9 + 9 + 2);
+            assert_eq!(get_physical_keys(conn, 2)?[0].0.token, keys[0].0.token);
+            assert!(keys.iter().all(|(_, claimed_by)| claimed_by.is_none()));
+            let find = |letter: &str| {
+                keys.iter()
+                    .map(|(key, _)| key)
+                    .find(|key| key.key == letter)
+                    .unwrap()
+                    .clone()
+            };
+            let r = find("R");
+            let g = find("G");
+            let wildcard = find(&WILDCARD.to_string());
+
+            let err = claim_physical_key(conn, luna, r.id, "forged", &points, 5).unwrap_err();
+            assert_eq!(err.to_string(), "That isn't one of the keys");
+
+            // A letter that isn't in Ravenclaw costs Luna (and so Ravenclaw) points.
+            let score_before = get_house(conn, ravenclaw)?.score;
+            let claim = claim_physical_key(conn, luna, g.id, &g.token, &points, 5)?;
+            assert_eq!((claim.key, claim.penalty), ('G', Some(5)));
+            assert!(!claim.repeat);
+            assert!(claim.house.turned_in.is_empty());
+            assert_eq!(get_house(conn, ravenclaw)?.score, score_before - 5);
+            let personal_score: i32 = guests::table
+                .find(luna)
+                .select(guests::personal_score)
+                .first(conn)?;
+            assert_eq!(personal_score, -5);
+
+            // Scanning the same key again (or reloading the page) repeats that without charging
+            // her twice.
+            let claim = claim_physical_key(conn, luna, g.id, &g.token, &points, 5)?;
+            assert_eq!((claim.key, claim.penalty), ('G', Some(5)));
+            assert!(claim.repeat);
+            assert_eq!(get_house(conn, ravenclaw)?.score, score_before - 5);
+            let personal_score: i32 = guests::table
+                .find(luna)
+                .select(guests::personal_score)
+                .first(conn)?;
+            assert_eq!(personal_score, -5);
+
+            let claim = claim_physical_key(conn, luna, r.id, &r.token, &points, 5)?;
+            assert_eq!(claim.penalty, None);
+            assert_eq!(claim.house.turned_in, vec!['R']);
+            claim_physical_key(conn, luna, wildcard.id, &wildcard.token, &points, 5)?;
+            let err = claim_physical_key(conn, luna, r.id, &r.token, &points, 5).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "That R has already been turned in for Ravenclaw"
+            );
+            let claimed_by = |conn: &mut SqliteConnection, key_id: i32| {
+                get_physical_keys(conn, 2).map(|keys| {
+                    keys.into_iter()
+                        .find(|(key, _)| key.id == key_id)
+                        .and_then(|(_, claimed_by)| claimed_by)
+                })
+            };
+            assert_eq!(claimed_by(conn, r.id)?.as_deref(), Some("Ravenclaw"));
+
+            // A key taken back can be claimed again.
+            take_back_key(conn, ravenclaw, 'R')?;
+            assert_eq!(claimed_by(conn, r.id)?, None);
+            let claim = claim_physical_key(conn, luna, r.id, &r.token, &points, 5)?;
+            assert_eq!(claim.house.turned_in, vec![WILDCARD, 'R']);
+            Ok(())
+        });
+    }
+
     #[test]
     fn test_init_voting_status() {
         run_test_in_transaction(|conn| {
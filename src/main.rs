@@ -1,25 +1,53 @@
 #[cfg(feature = "ssr")]
 #[tokio::main]
 async fn main() {
-    use axum::Router;
+    use axum::{routing::get, Extension, Router};
     use diesel::r2d2::{ConnectionManager, Pool};
     use diesel::SqliteConnection;
     use dotenvy::dotenv;
     use hp_halloween_25::app::*;
+    use hp_halloween_25::config::AppConfig;
+    use hp_halloween_25::crossword::Puzzle;
+    use hp_halloween_25::events::{live_events_handler, EventBus, EVENTS_PATH};
+    use hp_halloween_25::sorting_hat::{HttpSortingHat, SortingHatQueue};
+    use hp_halloween_25::wordle::WordList;
+    use hp_halloween_25::SqlitePragmas;
     use leptos::logging::log;
     use leptos::prelude::*;
     use leptos_axum::{generate_route_list, LeptosRoutes};
     use std::env;
+    use std::sync::Arc;
 
     dotenv().ok();
     let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set in .env.");
     let _ = env::var("ADMIN_PASSWORD").expect("ADMIN_PASSWORD must be set in .env.");
+    let config = AppConfig::load().expect("Failed to load configuration.");
+    log!("public base URL is {}", config.public_base_url);
+    let puzzle = Arc::new(Puzzle::load(&config.crossword_puzzle).expect("Failed to load puzzle."));
+    let wordle_words = Arc::new(
+        WordList::load(&config.wordle_answers, &config.wordle_guesses)
+            .expect("Failed to load Wordle words."),
+    );
 
     let manager = ConnectionManager::<SqliteConnection>::new(&database_url);
     let pool = Pool::builder()
+        .connection_customizer(Box::new(SqlitePragmas))
         .build(manager)
         .expect("Failed to create pool.");
 
+    // Deliveries to the Sorting Hat run on a background thread, retrying until the hat answers.
+    let sorting_hat = config.sorting_hat_url.as_deref().map(|url| {
+        log!("Sorting Hat is at {}", url);
+        SortingHatQueue::start(
+            pool.clone(),
+            Arc::new(HttpSortingHat::new(url)),
+            config.sorting_hat_retry_policy(),
+        )
+    });
+
+    // Live updates for open pages.
+    let event_bus = EventBus::new(64);
+
     let conf = get_configuration(None).unwrap();
     let addr = conf.leptos_options.site_addr;
     let leptos_options = conf.leptos_options;
@@ -28,11 +56,23 @@ async fn main() {
 
     let leptos_options_clone = leptos_options.clone();
     let app = Router::new()
+        .route(EVENTS_PATH, get(live_events_handler))
+        .layer(Extension(event_bus.clone()))
         .leptos_routes_with_context(
             &leptos_options,
             routes,
-            // Provide pool for server functions.
-            move || provide_context(pool.clone()),
+            // Provide pool, configuration, crossword puzzle, Wordle words, event bus and the Sorting
+            // Hat queue (if any) for server functions.
+            move || {
+                provide_context(pool.clone());
+                provide_context(config.clone());
+                provide_context(puzzle.clone());
+                provide_context(wordle_words.clone());
+                provide_context(event_bus.clone());
+                if let Some(queue) = &sorting_hat {
+                    provide_context(queue.clone());
+                }
+            },
             // Use App for main routes.
             move || shell(leptos_options_clone.clone()),
         )
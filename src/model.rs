@@ -1,3 +1,5 @@
+#[cfg(feature = "ssr")]
+use chrono::NaiveDate;
 use chrono::NaiveDateTime;
 #[cfg(feature = "ssr")]
 use diesel::prelude::*;
@@ -7,6 +9,9 @@ use diesel::sql_types::Text;
 use diesel::sqlite::Sqlite;
 use serde::{Deserialize, Serialize};
 
+use crate::crossword::Puzzle;
+use crate::wordle::LetterStatus;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "ssr", derive(Queryable, Selectable))]
 #[cfg_attr(feature = "ssr", diesel(table_name = crate::schema::houses))]
@@ -14,6 +19,21 @@ pub struct House {
     pub id: i32,
     pub name: String,
     pub score: i32,
+    pub slug: String, // stable identifier used for CSS classes, e.g. "gryffindor"
+    pub primary_color: String, // CSS color, used for backgrounds and borders
+    pub secondary_color: String, // CSS color, used for text on the primary color
+    pub crest: Option<String>, // URL of the crest image, relative to the site root
+}
+
+impl House {
+    /// Inline CSS custom properties carrying the house colors, for styles to pick up via
+    /// `var(--house-primary)` and `var(--house-secondary)`.
+    pub fn color_style(&self) -> String {
+        format!(
+            "--house-primary: {}; --house-secondary: {};",
+            self.primary_color, self.secondary_color
+        )
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -40,6 +60,15 @@ pub struct NewGuest<'a> {
     // personal_score and is_active use defaults
 }
 
+// Outcome of importing a guest roster (used in app and the import_roster bin).
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct RosterImportReport {
+    pub imported: Vec<String>,   // names inserted as unregistered guests
+    pub duplicates: Vec<String>, // names skipped because they already exist
+    pub invalid: Vec<String>,    // validation errors; nothing is imported if non-empty
+    pub removed: usize,          // never-registered guests deleted when replacing the roster
+}
+
 #[cfg(feature = "ssr")]
 #[derive(Queryable, Debug, Serialize, Deserialize)]
 #[diesel(table_name = crate::schema::sessions)]
@@ -72,6 +101,7 @@ pub struct PointAward {
     pub amount: i32,
     pub reason: String,
     pub awarded_at: NaiveDateTime,
+    pub reverts_award_id: Option<i32>, // set on entries that cancel an earlier award
 }
 
 #[cfg(feature = "ssr")]
@@ -83,6 +113,7 @@ pub struct NewPointAward {
     pub amount: i32,
     pub reason: String,
     pub awarded_at: chrono::NaiveDateTime,
+    pub reverts_award_id: Option<i32>,
 }
 
 #[cfg(feature = "ssr")]
@@ -113,6 +144,73 @@ pub struct PointAwardLog {
     pub amount: i32,
     pub reason: String,
     pub awarded_at: NaiveDateTime,
+    pub reverts_award_id: Option<i32>, // the award this entry cancels, if it is a reversal
+    pub reverted_by: Option<i32>,      // the entry that cancelled this award, if any
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::sorting_hat_deliveries)]
+#[diesel(check_for_backend(Sqlite))]
+pub struct SortingHatDelivery {
+    pub id: i32,
+    pub guest_id: Option<i32>,
+    pub house_id: i32,
+    pub status: String, // "pending", "delivered" or "failed"
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::sorting_hat_deliveries)]
+pub struct NewSortingHatDelivery {
+    pub guest_id: Option<i32>,
+    pub house_id: i32,
+    // status, attempts and timestamps use defaults
+}
+
+// Everything the projector scoreboard shows, fetched in one go.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Scoreboard {
+    pub houses: Vec<House>,
+    pub recent_awards: Vec<PointAwardLog>,  // newest first
+    pub crossword_progress: Vec<Vec<bool>>, // one row per house, in the order of `houses`
+    pub voting_open: bool,
+    pub votes_cast: i64,
+    pub eligible_voters: i64, // active guests
+}
+
+// Sorting Hat delivery with guest and house names, for the admin delivery log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(Queryable))]
+pub struct SortingHatDeliveryLog {
+    pub id: i32,
+    pub guest_name: Option<String>,
+    pub house_name: String,
+    pub status: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub updated_at: NaiveDateTime,
+}
+
+// The admin dashboard's view of the Sorting Hat.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SortingHatOverview {
+    pub endpoint: Option<String>, // configured URL, None if the hat is switched off
+    pub deliveries: Vec<SortingHatDeliveryLog>, // most recent first
+}
+
+// A cached score (house or guest) that disagrees with the total of its point awards.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoreMismatch {
+    pub house_id: Option<i32>, // set for a house score
+    pub guest_id: Option<i32>, // set for a guest's personal score
+    pub name: String,
+    pub cached: i32, // houses.score or guests.personal_score
+    pub ledger: i32, // total of the point_awards entries
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -121,10 +219,99 @@ pub struct SparseGrid {
     pub filled: Vec<(usize, usize, char)>,
 }
 
+// A guest's letters as (row, col, char). Which words they solve is worked out by the server.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct SparseState {
     pub filled: Vec<(usize, usize, char)>,
-    pub completions: [bool; 7],
+}
+
+// A guest's crossword as sent to the browser. Reveal texts are only included for solved words.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct GuestCrossword {
+    pub filled: Vec<(usize, usize, char)>,
+    pub completions: Vec<bool>,
+    pub reveals: Vec<Option<String>>,
+    // Set when the crossword is the guest's house board (house board mode).
+    #[serde(default)]
+    pub board: Option<HouseBoard>,
+    // Version of the guest's own crossword, to send back with edits to it (unused on house boards).
+    #[serde(default)]
+    pub version: i32,
+}
+
+impl GuestCrossword {
+    pub fn new(state: CrosswordState, puzzle: &Puzzle) -> Self {
+        Self {
+            reveals: puzzle.reveals(&state.completions),
+            filled: state.sparse.filled,
+            completions: state.completions,
+            board: None,
+            version: state.version,
+        }
+    }
+}
+
+// Who last wrote a cell of a house board. Cells nobody has written yet are left out.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BoardCell {
+    pub row: usize,
+    pub col: usize,
+    pub guest_name: Option<String>, // None if that guest has since been deleted
+    pub version: i32,               // number of times the cell has been written
+}
+
+// The shared crossword board of a house; its letters are in the enclosing `GuestCrossword`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HouseBoard {
+    pub house_id: i32,
+    pub cells: Vec<BoardCell>,
+}
+
+// A letter typed on a house board (None erases the cell). `version` is the version of the cell
+// the guest last saw, 0 if it was never written; the edit is rejected if another guest has
+// written the cell since.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BoardEdit {
+    pub row: usize,
+    pub col: usize,
+    pub letter: Option<char>,
+    pub version: i32,
+}
+
+// A letter typed on the guest's own crossword (None erases the cell). Sent in batches along with
+// the version of the crossword they were typed on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CellEdit {
+    pub row: usize,
+    pub col: usize,
+    pub letter: Option<char>,
+}
+
+// The crossword (house board or the guest's own) after a batch of edits, and the (row, col) of the
+// edits that were rejected.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoardEditResult {
+    pub crossword: GuestCrossword,
+    pub rejected: Vec<(usize, usize)>,
+}
+
+// What applying board edits did, see `edit_house_board`.
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct HouseBoardUpdate {
+    pub house_id: i32,
+    pub applied: usize,
+    pub rejected: Vec<(usize, usize)>,
+    pub new_house_words: Vec<usize>, // words the house completed for the first time
+}
+
+// What applying edits to a guest's own crossword did, see `edit_crossword_state`.
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone)]
+pub struct CrosswordStateUpdate {
+    pub state: CrosswordState, // the state afterwards, with its new version
+    pub rejected: Vec<(usize, usize)>,
+    pub new_house_words: Vec<usize>, // words the house completed for the first time
 }
 
 #[cfg(feature = "ssr")]
@@ -136,6 +323,8 @@ pub struct DbCrosswordState {
     #[diesel(sql_type = Text)]
     pub state: String,
     pub updated_at: chrono::NaiveDateTime,
+    pub started_at: Option<chrono::NaiveDateTime>, // when the guest first opened the crossword
+    pub version: i32,                              // bumped on every save
 }
 
 #[cfg(feature = "ssr")]
@@ -145,26 +334,44 @@ pub struct NewDbCrosswordState {
     pub guest_id: i32,
     pub state: String,
     pub updated_at: chrono::NaiveDateTime,
+    pub started_at: Option<chrono::NaiveDateTime>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct CrosswordState {
-    // 15 rows x 12 cols; None = unfilled, Some(char) = filled
+    // puzzle.rows x puzzle.cols; None = unfilled, Some(char) = filled
     pub grid: Vec<Vec<Option<char>>>,
     // Sparse state of grid (for send).
     pub sparse: SparseGrid,
-    // Which of the 7 words are completed correctly
-    pub completions: [bool; 7],
+    // Which of the puzzle's words are completed correctly, in puzzle order
+    pub completions: Vec<bool>,
+    // Version of the stored state this was read from; 0 if it was not read from the database
+    #[serde(default)]
+    pub version: i32,
 }
 
 impl CrosswordState {
-    pub fn new_full_grid(grid: Vec<Vec<Option<char>>>, completions: [bool; 7]) -> Self {
+    pub fn new_full_grid(grid: Vec<Vec<Option<char>>>, completions: Vec<bool>) -> Self {
         let sparse = Self::build_sparse(&grid);
         Self {
             grid,
             sparse,
             completions,
+            version: 0,
+        }
+    }
+
+    /// Rebuilds the full grid for `puzzle` from a sparse state, and checks which words it solves.
+    /// Letters outside the puzzle's words are dropped.
+    pub fn from_sparse(sparse: SparseState, puzzle: &Puzzle) -> Self {
+        let mut grid = puzzle.empty_grid();
+        for &(r, c, ch) in &sparse.filled {
+            if r < puzzle.rows && c < puzzle.cols && puzzle.is_word_cell(r, c) {
+                grid[r][c] = Some(ch.to_ascii_uppercase());
+            }
         }
+        let completions = puzzle.completions(&grid);
+        Self::new_full_grid(grid, completions)
     }
 
     pub fn to_sparse(&self) -> SparseGrid {
@@ -189,31 +396,18 @@ impl From<CrosswordState> for String {
     fn from(state: CrosswordState) -> Self {
         let sparse = SparseState {
             filled: state.sparse.filled,
-            completions: state.completions,
         };
         serde_json::to_string(&sparse).expect("Failed to serialize sparse state")
     }
 }
 
 #[cfg(feature = "ssr")]
-impl From<String> for CrosswordState {
-    fn from(json: String) -> Self {
-        let sparse: SparseState = serde_json::from_str(&json).unwrap_or_default();
-        let mut grid = vec![vec![None; 12]; 15];
-        for (r, c, ch) in &sparse.filled {
-            if *r < 15 && *c < 12 {
-                grid[*r][*c] = Some(*ch);
-            }
-        }
-        let sparse_grid = SparseGrid {
-            filled: sparse.filled,
-        };
-
-        Self {
-            grid,
-            sparse: sparse_grid,
-            completions: sparse.completions,
-        }
+impl CrosswordState {
+    /// Parses a state stored in `crossword_states` for `puzzle`. Unreadable states start over.
+    /// Completions stored by older versions are ignored and worked out again.
+    pub fn from_json(json: &str, puzzle: &Puzzle) -> Self {
+        let sparse: SparseState = serde_json::from_str(json).unwrap_or_default();
+        Self::from_sparse(sparse, puzzle)
     }
 }
 
@@ -237,6 +431,460 @@ pub struct NewHouseCrosswordCompletion {
     // completed_at uses default (CURRENT_TIMESTAMP)
 }
 
+#[cfg(feature = "ssr")]
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::crossword_solves)]
+pub struct NewCrosswordSolve {
+    pub guest_id: i32,
+    pub house_id: i32,
+    pub word_index: i32,
+    pub elapsed_seconds: Option<i32>,
+    // solved_at uses default (CURRENT_TIMESTAMP)
+}
+
+// A crossword word solved by a guest, for the admin timeline.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrosswordSolveLog {
+    pub guest_name: String,
+    pub house_name: String,
+    pub word_index: usize,
+    pub solved_at: NaiveDateTime,
+    pub elapsed_seconds: Option<i32>, // since the guest opened the crossword
+    pub first_for_house: bool,        // whether this solve scored the word for the house
+}
+
+// One guest's crossword solving, for the leaderboard.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SolverStats {
+    pub guest_name: String,
+    pub words_solved: usize,
+    pub first_solves: usize, // words the guest solved before anyone else in their house
+    pub elapsed_seconds: Option<i32>, // from opening the crossword to their latest solve
+}
+
+// A house's crossword solvers, fastest first: most words solved, then least time taken.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HouseLeaderboard {
+    pub house: House,
+    pub solvers: Vec<SolverStats>,
+}
+
+// What a crossword hint reveals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HintKind {
+    Letter, // the first wrong or missing letter of a word
+    Word,   // the whole word
+}
+
+impl HintKind {
+    // As stored in `crossword_hints.kind`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HintKind::Letter => "letter",
+            HintKind::Word => "word",
+        }
+    }
+
+    pub fn from_db(kind: &str) -> Self {
+        if kind == "word" {
+            HintKind::Word
+        } else {
+            HintKind::Letter
+        }
+    }
+}
+
+// A house's hint allowance and what its hints have cost so far.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HouseHintUsage {
+    pub house_id: i32,
+    pub house_name: String,
+    pub used: i32,
+    pub limit: i32,
+    pub spent: i32, // points paid for hints
+}
+
+// What the crossword page shows about hints: their prices and the guest's house allowance.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HintStatus {
+    pub letter_cost: i32,
+    pub word_cost: i32,
+    pub used: i32,
+    pub limit: i32,
+}
+
+// A hint in the admin hint log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrosswordHintLog {
+    pub id: i32,
+    pub house_name: String,
+    pub guest_name: Option<String>,
+    pub word_index: usize,
+    pub kind: HintKind,
+    pub cost: i32,
+    pub used_at: NaiveDateTime,
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::crossword_hints)]
+pub struct NewCrosswordHint {
+    pub house_id: i32,
+    pub guest_id: Option<i32>,
+    pub word_index: i32,
+    pub kind: String,
+    pub cost: i32,
+    pub point_award_id: Option<i32>,
+}
+
+// What taking a hint did, see `take_crossword_hint`.
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone)]
+pub struct HintTaken {
+    pub house_id: i32,
+    pub cost: i32,
+    pub new_house_words: Vec<usize>, // words the hint completed for the house for the first time
+}
+
+// Where a house's claim to have found a horcrux stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClaimStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl ClaimStatus {
+    // As stored in `horcrux_claims.status`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ClaimStatus::Pending => "pending",
+            ClaimStatus::Approved => "approved",
+            ClaimStatus::Rejected => "rejected",
+        }
+    }
+
+    pub fn from_db(status: &str) -> Self {
+        match status {
+            "approved" => ClaimStatus::Approved,
+            "rejected" => ClaimStatus::Rejected,
+            _ => ClaimStatus::Pending,
+        }
+    }
+}
+
+// A photo sent with a horcrux claim, base64-encoded.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PhotoUpload {
+    pub content_type: String, // e.g. "image/jpeg"
+    pub data: String,
+}
+
+// A horcrux claim in the admin review queue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HorcruxClaimLog {
+    pub id: i32,
+    pub house_name: String,
+    pub guest_name: Option<String>,
+    pub word_index: usize,
+    pub horcrux: String,
+    pub location: String, // from the puzzle; empty if the puzzle has changed since
+    pub has_photo: bool,
+    pub status: ClaimStatus,
+    pub claimed_at: NaiveDateTime,
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::horcrux_claims)]
+#[diesel(check_for_backend(Sqlite))]
+pub struct HorcruxClaim {
+    pub id: i32,
+    pub house_id: i32,
+    pub guest_id: Option<i32>,
+    pub word_index: i32,
+    pub horcrux: String,
+    pub photo_path: Option<String>, // file name in the photo directory
+    pub status: String,
+    pub point_award_id: Option<i32>,
+    pub claimed_at: NaiveDateTime,
+    pub reviewed_at: Option<NaiveDateTime>,
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::horcrux_claims)]
+pub struct NewHorcruxClaim {
+    pub house_id: i32,
+    pub guest_id: Option<i32>,
+    pub word_index: i32,
+    pub horcrux: String,
+    pub photo_path: Option<String>,
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Queryable, Selectable, Debug)]
+#[diesel(table_name = crate::schema::house_board_cells)]
+#[diesel(check_for_backend(Sqlite))]
+pub struct HouseBoardCell {
+    pub id: i32,
+    pub house_id: i32,
+    pub row_index: i32,
+    pub col_index: i32,
+    pub letter: Option<String>, // None once erased
+    pub guest_id: Option<i32>,  // last writer
+    pub version: i32,
+    pub updated_at: NaiveDateTime,
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::house_board_cells)]
+pub struct NewHouseBoardCell {
+    pub house_id: i32,
+    pub row_index: i32,
+    pub col_index: i32,
+    pub letter: Option<String>,
+    pub guest_id: Option<i32>,
+    pub version: i32,
+}
+
+// A house's progress in Catch the Keys.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HouseKeys {
+    pub house_id: i32,
+    pub house_name: String,
+    pub keeper: String,       // who the house turns its keys in to
+    pub keys: Vec<char>,      // one per letter of the house name
+    pub turned_in: Vec<char>, // letters and wildcards, in the order they were turned in
+    pub missing: Vec<char>,   // letters still to turn in, after the wildcards
+    pub place: Option<i32>,   // 1 for the first house to turn in all its keys, and so on
+    pub points: Option<i32>,  // house points scored by finishing
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::turned_in_keys)]
+pub struct NewTurnedInKey {
+    pub house_id: i32,
+    pub key: String,
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::key_completions)]
+pub struct NewKeyCompletion {
+    pub house_id: i32,
+    pub place: i32,
+    pub point_award_id: Option<i32>,
+}
+
+// A physical Catch the Keys key, for the printable key sheet.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeyQr {
+    pub id: i32,
+    pub key: char,
+    pub qr_svg: String,             // QR code of the key's claim URL
+    pub claimed_by: Option<String>, // house the key was turned in for, if it was
+}
+
+// What scanning a physical key did.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeyClaim {
+    pub key: char,
+    pub penalty: Option<i32>, // points lost for a key that isn't the house's, which isn't turned in
+    pub repeat: bool,         // the guest had already scanned this key, so nothing changed
+    pub house: HouseKeys,     // the guest's house's progress
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::physical_keys)]
+#[diesel(check_for_backend(Sqlite))]
+pub struct DbPhysicalKey {
+    pub id: i32,
+    pub key: String,
+    pub token: String,
+    pub turned_in_key_id: Option<i32>,
+    pub created_at: NaiveDateTime,
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::physical_keys)]
+pub struct NewPhysicalKey {
+    pub key: String,
+    pub token: String,
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::wrong_key_claims)]
+pub struct NewWrongKeyClaim {
+    pub guest_id: i32,
+    pub physical_key_id: i32,
+    pub penalty: i32,
+    pub point_award_id: Option<i32>,
+}
+
+// Where a guest's Wordle game of the day stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WordleStatus {
+    Playing,
+    Won,
+    Lost,
+}
+
+impl WordleStatus {
+    // As stored in `wordle_games.status`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WordleStatus::Playing => "playing",
+            WordleStatus::Won => "won",
+            WordleStatus::Lost => "lost",
+        }
+    }
+
+    pub fn from_db(status: &str) -> Self {
+        match status {
+            "won" => WordleStatus::Won,
+            "lost" => WordleStatus::Lost,
+            _ => WordleStatus::Playing,
+        }
+    }
+}
+
+// A Wordle guess, upper case, with how each letter compares to the target.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WordleGuess {
+    pub word: String,
+    pub statuses: Vec<LetterStatus>,
+}
+
+// A guest's Wordle game of the day or in a race, as the Wordle page sees it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WordleGame {
+    pub guesses: Vec<WordleGuess>,
+    pub status: WordleStatus,
+    pub answer: Option<String>, // only once the game is over
+    pub points: Option<i32>,    // house points scored by a solve
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::wordle_games)]
+#[diesel(check_for_backend(Sqlite))]
+pub struct DbWordleGame {
+    pub id: i32,
+    pub guest_id: i32,
+    pub game_date: NaiveDate,
+    pub target: String,  // upper case
+    pub guesses: String, // JSON array of upper-case words
+    pub status: String,
+    pub point_award_id: Option<i32>,
+    pub started_at: NaiveDateTime,
+    pub finished_at: Option<NaiveDateTime>,
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::wordle_games)]
+pub struct NewWordleGame {
+    pub guest_id: i32,
+    pub game_date: NaiveDate,
+    pub target: String,
+}
+
+// A Wordle race, as every page sees it: how each house and racer is doing, but not the word until
+// the race is over.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WordleRace {
+    pub id: i32,
+    pub solvers_needed: i32,
+    pub points: i32,
+    pub started_at: NaiveDateTime,
+    pub ended_at: Option<NaiveDateTime>,
+    pub winning_house_id: Option<i32>,
+    pub answer: Option<String>, // only once the race is over
+    pub houses: Vec<WordleRaceHouse>,
+    pub racers: Vec<WordleRacer>,
+}
+
+impl WordleRace {
+    pub fn is_on(&self) -> bool {
+        self.ended_at.is_none()
+    }
+}
+
+// A house's standing in a Wordle race.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WordleRaceHouse {
+    pub house_id: i32,
+    pub name: String,
+    pub racers: i32,
+    pub solvers: i32,
+}
+
+// A guest's progress in a Wordle race: the statuses of their guesses without the letters.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WordleRacer {
+    pub guest_name: String,
+    pub house_id: i32,
+    pub statuses: Vec<Vec<LetterStatus>>,
+    pub status: WordleStatus,
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::wordle_rounds)]
+#[diesel(check_for_backend(Sqlite))]
+pub struct DbWordleRound {
+    pub id: i32,
+    pub target: String, // upper case
+    pub solvers_needed: i32,
+    pub points: i32,
+    pub winning_house_id: Option<i32>,
+    pub point_award_id: Option<i32>,
+    pub started_at: NaiveDateTime,
+    pub ended_at: Option<NaiveDateTime>,
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::wordle_rounds)]
+pub struct NewWordleRound {
+    pub target: String,
+    pub solvers_needed: i32,
+    pub points: i32,
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::wordle_round_games)]
+#[diesel(check_for_backend(Sqlite))]
+pub struct DbWordleRoundGame {
+    pub id: i32,
+    pub round_id: i32,
+    pub guest_id: i32,
+    pub house_id: i32,
+    pub guesses: String, // JSON array of upper-case words
+    pub status: String,
+    pub started_at: NaiveDateTime,
+    pub finished_at: Option<NaiveDateTime>,
+}
+
+#[cfg(feature = "ssr")]
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crate::schema::wordle_round_games)]
+pub struct NewWordleRoundGame {
+    pub round_id: i32,
+    pub guest_id: i32,
+    pub house_id: i32,
+}
+
 #[cfg(feature = "ssr")]
 #[derive(Queryable, Selectable, Debug, Serialize, Deserialize)]
 #[diesel(table_name = crate::schema::voting_status)]
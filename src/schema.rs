@@ -9,12 +9,45 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    crossword_hint_limits (house_id) {
+        house_id -> Integer,
+        hint_limit -> Integer,
+    }
+}
+
+diesel::table! {
+    crossword_hints (id) {
+        id -> Integer,
+        house_id -> Integer,
+        guest_id -> Nullable<Integer>,
+        word_index -> Integer,
+        kind -> Text,
+        cost -> Integer,
+        point_award_id -> Nullable<Integer>,
+        used_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    crossword_solves (id) {
+        id -> Integer,
+        guest_id -> Integer,
+        house_id -> Integer,
+        word_index -> Integer,
+        solved_at -> Timestamp,
+        elapsed_seconds -> Nullable<Integer>,
+    }
+}
+
 diesel::table! {
     crossword_states (id) {
         id -> Integer,
         guest_id -> Integer,
         state -> Text,
         updated_at -> Timestamp,
+        started_at -> Nullable<Timestamp>,
+        version -> Integer,
     }
 }
 
@@ -30,6 +63,34 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    horcrux_claims (id) {
+        id -> Integer,
+        house_id -> Integer,
+        guest_id -> Nullable<Integer>,
+        word_index -> Integer,
+        horcrux -> Text,
+        photo_path -> Nullable<Text>,
+        status -> Text,
+        point_award_id -> Nullable<Integer>,
+        claimed_at -> Timestamp,
+        reviewed_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    house_board_cells (id) {
+        id -> Integer,
+        house_id -> Integer,
+        row_index -> Integer,
+        col_index -> Integer,
+        letter -> Nullable<Text>,
+        guest_id -> Nullable<Integer>,
+        version -> Integer,
+        updated_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     house_crossword_completions (id) {
         id -> Integer,
@@ -44,6 +105,29 @@ diesel::table! {
         id -> Integer,
         name -> Text,
         score -> Integer,
+        slug -> Text,
+        primary_color -> Text,
+        secondary_color -> Text,
+        crest -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    key_completions (house_id) {
+        house_id -> Integer,
+        place -> Integer,
+        point_award_id -> Nullable<Integer>,
+        completed_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    physical_keys (id) {
+        id -> Integer,
+        key -> Text,
+        token -> Text,
+        turned_in_key_id -> Nullable<Integer>,
+        created_at -> Timestamp,
     }
 }
 
@@ -55,6 +139,7 @@ diesel::table! {
         amount -> Integer,
         reason -> Text,
         awarded_at -> Timestamp,
+        reverts_award_id -> Nullable<Integer>,
     }
 }
 
@@ -68,6 +153,28 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    sorting_hat_deliveries (id) {
+        id -> Integer,
+        guest_id -> Nullable<Integer>,
+        house_id -> Integer,
+        status -> Text,
+        attempts -> Integer,
+        last_error -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    turned_in_keys (id) {
+        id -> Integer,
+        house_id -> Integer,
+        key -> Text,
+        turned_in_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     votes (id) {
         id -> Integer,
@@ -89,22 +196,113 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    wordle_games (id) {
+        id -> Integer,
+        guest_id -> Integer,
+        game_date -> Date,
+        target -> Text,
+        guesses -> Text,
+        status -> Text,
+        point_award_id -> Nullable<Integer>,
+        started_at -> Timestamp,
+        finished_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    wordle_round_games (id) {
+        id -> Integer,
+        round_id -> Integer,
+        guest_id -> Integer,
+        house_id -> Integer,
+        guesses -> Text,
+        status -> Text,
+        started_at -> Timestamp,
+        finished_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    wordle_rounds (id) {
+        id -> Integer,
+        target -> Text,
+        solvers_needed -> Integer,
+        points -> Integer,
+        winning_house_id -> Nullable<Integer>,
+        point_award_id -> Nullable<Integer>,
+        started_at -> Timestamp,
+        ended_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    wrong_key_claims (id) {
+        id -> Integer,
+        guest_id -> Integer,
+        physical_key_id -> Integer,
+        penalty -> Integer,
+        point_award_id -> Nullable<Integer>,
+        claimed_at -> Timestamp,
+    }
+}
+
+diesel::joinable!(crossword_hint_limits -> houses (house_id));
+diesel::joinable!(crossword_hints -> guests (guest_id));
+diesel::joinable!(crossword_hints -> houses (house_id));
+diesel::joinable!(crossword_hints -> point_awards (point_award_id));
+diesel::joinable!(crossword_solves -> guests (guest_id));
+diesel::joinable!(crossword_solves -> houses (house_id));
 diesel::joinable!(crossword_states -> guests (guest_id));
 diesel::joinable!(guests -> houses (house_id));
+diesel::joinable!(horcrux_claims -> guests (guest_id));
+diesel::joinable!(horcrux_claims -> houses (house_id));
+diesel::joinable!(horcrux_claims -> point_awards (point_award_id));
+diesel::joinable!(house_board_cells -> guests (guest_id));
+diesel::joinable!(house_board_cells -> houses (house_id));
 diesel::joinable!(house_crossword_completions -> houses (house_id));
+diesel::joinable!(key_completions -> houses (house_id));
+diesel::joinable!(key_completions -> point_awards (point_award_id));
+diesel::joinable!(physical_keys -> turned_in_keys (turned_in_key_id));
 diesel::joinable!(point_awards -> guests (guest_id));
 diesel::joinable!(point_awards -> houses (house_id));
 diesel::joinable!(sessions -> guests (guest_id));
+diesel::joinable!(sorting_hat_deliveries -> guests (guest_id));
+diesel::joinable!(sorting_hat_deliveries -> houses (house_id));
+diesel::joinable!(turned_in_keys -> houses (house_id));
 diesel::joinable!(votes -> guests (voter_id));
+diesel::joinable!(wordle_games -> guests (guest_id));
+diesel::joinable!(wordle_games -> point_awards (point_award_id));
+diesel::joinable!(wordle_round_games -> guests (guest_id));
+diesel::joinable!(wordle_round_games -> houses (house_id));
+diesel::joinable!(wordle_round_games -> wordle_rounds (round_id));
+diesel::joinable!(wordle_rounds -> houses (winning_house_id));
+diesel::joinable!(wordle_rounds -> point_awards (point_award_id));
+diesel::joinable!(wrong_key_claims -> guests (guest_id));
+diesel::joinable!(wrong_key_claims -> physical_keys (physical_key_id));
+diesel::joinable!(wrong_key_claims -> point_awards (point_award_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     admin_sessions,
+    crossword_hint_limits,
+    crossword_hints,
+    crossword_solves,
     crossword_states,
     guests,
+    horcrux_claims,
+    house_board_cells,
     house_crossword_completions,
     houses,
+    key_completions,
+    physical_keys,
     point_awards,
     sessions,
+    sorting_hat_deliveries,
+    turned_in_keys,
     votes,
     voting_status,
+    wordle_games,
+    wordle_round_games,
+    wordle_rounds,
+    wrong_key_claims,
 );
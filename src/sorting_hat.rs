@@ -0,0 +1,253 @@
+// Server-side Sorting Hat integration.
+//
+// Registering a guest logs a delivery in `sorting_hat_deliveries` and queues it here. A background
+// thread asks the hat to announce the house, retrying with exponential backoff, and records every
+// attempt so failures show up on the admin dashboard, where they can be re-flashed.
+use leptos::logging::log;
+use std::sync::mpsc::{self, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crate::app::DbPool;
+use crate::model::{House, SortingHatDelivery};
+use crate::{
+    get_house, get_pending_sorting_hat_deliveries, get_sorting_hat_delivery,
+    record_sorting_hat_attempt, DELIVERY_PENDING,
+};
+
+// How long a single request to the hat may take.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Upper bound for the delay between attempts.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+/// Something that can make the Sorting Hat announce a house.
+pub trait SortingHatNotifier: Send + Sync {
+    /// Asks the hat to announce `house`. Returns a description of the problem if it didn't
+    /// acknowledge the request.
+    fn flash(&self, house: &House) -> Result<(), String>;
+}
+
+/// The Sorting Hat device, reached over HTTP. The house is passed as the `house` (id) and `slug`
+/// query parameters of a GET request to the configured URL.
+pub struct HttpSortingHat {
+    url: String,
+    agent: ureq::Agent,
+}
+
+impl HttpSortingHat {
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            agent: ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build(),
+        }
+    }
+}
+
+impl SortingHatNotifier for HttpSortingHat {
+    fn flash(&self, house: &House) -> Result<(), String> {
+        self.agent
+            .get(&self.url)
+            .query("house", &house.id.to_string())
+            .query("slug", &house.slug)
+            .call()
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// How often, and how patiently, a delivery is attempted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first, before a delivery is marked failed.
+    pub max_attempts: u32,
+    /// Delay after the first failed attempt. It doubles after each further failure.
+    pub initial_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Delay before the next attempt, after `attempts` failed ones.
+    pub fn delay_after(&self, attempts: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempts.saturating_sub(1));
+        self.initial_delay
+            .saturating_mul(factor)
+            .min(MAX_RETRY_DELAY)
+    }
+}
+
+/// Attempts a pending delivery until the hat acknowledges it or the policy runs out of attempts,
+/// recording each attempt. Returns the delivery in its final state; deliveries that are no longer
+/// pending are returned untouched. A connection is only taken from `pool` to read or record the
+/// delivery, so one isn't held while the hat is called or between attempts.
+pub fn deliver(
+    pool: &DbPool,
+    notifier: &dyn SortingHatNotifier,
+    policy: &RetryPolicy,
+    delivery_id: i32,
+) -> Result<SortingHatDelivery, String> {
+    let (mut delivery, house) = {
+        let mut conn = pool.get().map_err(|e| e.to_string())?;
+        let delivery =
+            get_sorting_hat_delivery(&mut conn, delivery_id).map_err(|e| e.to_string())?;
+        let house = get_house(&mut conn, delivery.house_id).map_err(|e| e.to_string())?;
+        (delivery, house)
+    };
+    while delivery.status == DELIVERY_PENDING {
+        let result = notifier.flash(&house);
+        let give_up = delivery.attempts as u32 + 1 >= policy.max_attempts;
+        let mut conn = pool.get().map_err(|e| e.to_string())?;
+        delivery =
+            record_sorting_hat_attempt(&mut conn, delivery_id, result.err().as_deref(), give_up)
+                .map_err(|e| e.to_string())?;
+        drop(conn);
+        if delivery.status == DELIVERY_PENDING {
+            thread::sleep(policy.delay_after(delivery.attempts as u32));
+        }
+    }
+    Ok(delivery)
+}
+
+/// Hands deliveries to a background thread that works through them in order. Cheap to clone;
+/// provided to server functions via context when a Sorting Hat is configured.
+#[derive(Clone)]
+pub struct SortingHatQueue {
+    sender: Sender<i32>,
+}
+
+impl SortingHatQueue {
+    /// Starts the delivery thread and queues any deliveries left pending by a previous run.
+    pub fn start(pool: DbPool, notifier: Arc<dyn SortingHatNotifier>, policy: RetryPolicy) -> Self {
+        let (sender, receiver) = mpsc::channel::<i32>();
+        let worker_pool = pool.clone();
+        thread::Builder::new()
+            .name("sorting-hat".to_string())
+            .spawn(move || {
+                for delivery_id in receiver {
+                    match deliver(&worker_pool, notifier.as_ref(), &policy, delivery_id) {
+                        Ok(delivery) => log!(
+                            "Sorting Hat delivery {} {} after {} attempt(s)",
+                            delivery_id,
+                            delivery.status,
+                            delivery.attempts
+                        ),
+                        Err(e) => log!("Sorting Hat delivery {} errored: {}", delivery_id, e),
+                    }
+                }
+            })
+            .expect("Failed to start the Sorting Hat thread");
+
+        let queue = Self { sender };
+        match pool.get().map_err(|e| e.to_string()).and_then(|mut conn| {
+            get_pending_sorting_hat_deliveries(&mut conn).map_err(|e| e.to_string())
+        }) {
+            Ok(pending) => pending
+                .iter()
+                .for_each(|delivery| queue.enqueue(delivery.id)),
+            Err(e) => log!("Failed to load pending Sorting Hat deliveries: {}", e),
+        }
+        queue
+    }
+
+    /// Queues a pending delivery for the background thread.
+    pub fn enqueue(&self, delivery_id: i32) {
+        if self.sender.send(delivery_id).is_err() {
+            log!(
+                "Sorting Hat thread has stopped; delivery {} stays pending",
+                delivery_id
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{create_sorting_hat_delivery, DELIVERY_DELIVERED};
+    use crate::{DELIVERY_FAILED, DELIVERY_PENDING};
+    use diesel::r2d2::{ConnectionManager, Pool, TestCustomizer};
+    use diesel::SqliteConnection;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    // A pool of one connection, kept in a transaction that is rolled back when the pool is
+    // dropped, so `deliver` can take its connection as often as it likes without the test writing
+    // anything. Connections must be returned before calling `deliver`.
+    fn test_pool() -> DbPool {
+        dotenvy::dotenv().ok();
+        let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set in .env");
+        Pool::builder()
+            .max_size(1)
+            .connection_customizer(Box::new(TestCustomizer))
+            .build(ConnectionManager::<SqliteConnection>::new(database_url))
+            .expect("Failed to create the test pool")
+    }
+
+    // A hat that fails the first `failures` requests.
+    struct FlakyHat {
+        failures: u32,
+        calls: AtomicU32,
+    }
+
+    impl SortingHatNotifier for FlakyHat {
+        fn flash(&self, _house: &House) -> Result<(), String> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            if call < self.failures {
+                Err(format!("hat asleep ({})", call + 1))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    const NO_WAIT: RetryPolicy = RetryPolicy {
+        max_attempts: 3,
+        initial_delay: Duration::ZERO,
+    };
+
+    #[test]
+    fn test_retry_policy_backoff() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            initial_delay: Duration::from_millis(500),
+        };
+        assert_eq!(policy.delay_after(1), Duration::from_millis(500));
+        assert_eq!(policy.delay_after(2), Duration::from_secs(1));
+        assert_eq!(policy.delay_after(4), Duration::from_secs(4));
+        assert_eq!(policy.delay_after(40), MAX_RETRY_DELAY);
+    }
+
+    #[test]
+    fn test_deliver_retries_until_acknowledged() {
+        let pool = test_pool();
+        let delivery = create_sorting_hat_delivery(&mut pool.get().unwrap(), None, 1).unwrap();
+        assert_eq!(delivery.status, DELIVERY_PENDING);
+
+        let hat = FlakyHat {
+            failures: 2,
+            calls: AtomicU32::new(0),
+        };
+        let delivered = deliver(&pool, &hat, &NO_WAIT, delivery.id).unwrap();
+        assert_eq!(delivered.status, DELIVERY_DELIVERED);
+        assert_eq!(delivered.attempts, 3);
+        assert_eq!(delivered.last_error, None);
+
+        // Delivered requests are not sent again.
+        let again = deliver(&pool, &hat, &NO_WAIT, delivery.id).unwrap();
+        assert_eq!(again.attempts, 3);
+        assert_eq!(hat.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_deliver_gives_up() {
+        let pool = test_pool();
+        let delivery = create_sorting_hat_delivery(&mut pool.get().unwrap(), None, 2).unwrap();
+        let hat = FlakyHat {
+            failures: u32::MAX,
+            calls: AtomicU32::new(0),
+        };
+        let failed = deliver(&pool, &hat, &NO_WAIT, delivery.id).unwrap();
+        assert_eq!(failed.status, DELIVERY_FAILED);
+        assert_eq!(failed.attempts, 3);
+        assert_eq!(failed.last_error.as_deref(), Some("hat asleep (3)"));
+    }
+}
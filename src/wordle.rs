@@ -0,0 +1,277 @@
+// Wordle.
+//
+// Every guest gets one game a day. The server picks the word from the answer list, checks each
+// guess against the (longer) list of allowed guesses and with `compute_statuses`, and keeps the
+// game in `wordle_games`; the page is only told the word once the game is over, and a solve scores
+// house points depending on how many guesses it took. Both lists are text files named by
+// `wordle_answers` and `wordle_guesses` in the configuration, loaded when the server starts.
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+pub const WORD_LENGTH: usize = 5;
+pub const MAX_GUESSES: usize = 6;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LetterStatus {
+    Correct, // green: right letter, right position
+    Present, // yellow: right letter, wrong position
+    Absent,  // gray: wrong letter
+    Unused,  // default for keyboard
+}
+
+/// The words a Wordle game can be played with. Build it with `WordList::parse` (or
+/// `WordList::load`), which validates it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WordList {
+    answers: Vec<String>,
+    /// Includes the answers.
+    guesses: HashSet<String>,
+}
+
+impl WordList {
+    /// Checks that there are answers and that every word is `WORD_LENGTH` letters A-Z, in any
+    /// case. Words are kept upper case, and every answer is also accepted as a guess.
+    pub fn new<S: AsRef<str>>(answers: &[S], guesses: &[S]) -> Result<Self, String> {
+        let check = |word: &S| {
+            let word = word.as_ref().to_uppercase();
+            if word.chars().count() == WORD_LENGTH && word.chars().all(|c| c.is_ascii_uppercase()) {
+                Ok(word)
+            } else {
+                Err(format!(
+                    "{:?} is not a word of {} letters A-Z",
+                    word, WORD_LENGTH
+                ))
+            }
+        };
+        let answers = answers.iter().map(check).collect::<Result<Vec<_>, _>>()?;
+        if answers.is_empty() {
+            return Err("There are no answers".to_string());
+        }
+        let mut guesses = guesses
+            .iter()
+            .map(check)
+            .collect::<Result<HashSet<_>, _>>()?;
+        guesses.extend(answers.iter().cloned());
+        Ok(Self { answers, guesses })
+    }
+
+    /// Parses the answer and guess lists: one word per line, skipping blank lines and lines
+    /// starting with `#`.
+    pub fn parse(answers: &str, guesses: &str) -> Result<Self, String> {
+        let words = |contents: &str| -> Vec<String> {
+            contents
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(str::to_string)
+                .collect()
+        };
+        Self::new(&words(answers), &words(guesses))
+    }
+
+    /// Reads, parses and validates the answer and guess list files.
+    #[cfg(feature = "ssr")]
+    pub fn load(answers_path: &str, guesses_path: &str) -> Result<Self, String> {
+        let read = |path: &str| {
+            std::fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read word list {}: {}", path, e))
+        };
+        Self::parse(&read(answers_path)?, &read(guesses_path)?).map_err(|e| {
+            format!(
+                "Invalid word lists {} and {}: {}",
+                answers_path, guesses_path, e
+            )
+        })
+    }
+
+    /// The words a game may pick, upper case.
+    pub fn answers(&self) -> &[String] {
+        &self.answers
+    }
+
+    /// Whether `word` (in any case) is accepted as a guess.
+    pub fn is_valid_guess(&self, word: &str) -> bool {
+        self.guesses.contains(&word.to_uppercase())
+    }
+}
+
+/// How each letter of `guess` compares to `target`; both upper case and `WORD_LENGTH` long. A
+/// letter repeated in the guess is only marked present as many times as the target still has it.
+pub fn compute_statuses(guess: &str, target: &str) -> Vec<LetterStatus> {
+    let mut statuses = vec![LetterStatus::Absent; WORD_LENGTH];
+    let mut target_counts: HashMap<char, usize> = HashMap::new();
+    for c in target.chars() {
+        *target_counts.entry(c).or_insert(0) += 1;
+    }
+
+    // First pass: Correct positions.
+    for (i, c) in guess.chars().enumerate() {
+        if target.chars().nth(i) == Some(c) {
+            statuses[i] = LetterStatus::Correct;
+            *target_counts.entry(c).or_insert(0) -= 1;
+        }
+    }
+
+    // Second pass: Present but wrong position.
+    for (i, c) in guess.chars().enumerate() {
+        if statuses[i] != LetterStatus::Correct && target_counts.get(&c).unwrap_or(&0) > &0 {
+            statuses[i] = LetterStatus::Present;
+            *target_counts.entry(c).or_insert(0) -= 1;
+        }
+    }
+
+    statuses
+}
+
+/// In hard mode every hint revealed so far has to be used: a letter found in the right place stays
+/// there, and a letter found elsewhere in the word is guessed again (as many times as it was
+/// found). `previous` are the earlier guesses with their statuses, all upper case. Returns what
+/// `guess` leaves out, if anything.
+pub fn hard_mode_error<'a>(
+    guess: &str,
+    previous: impl IntoIterator<Item = (&'a str, &'a [LetterStatus])>,
+) -> Option<String> {
+    let letters: Vec<char> = guess.chars().collect();
+    for (word, statuses) in previous {
+        for (i, (c, status)) in word.chars().zip(statuses).enumerate() {
+            if *status == LetterStatus::Correct && letters.get(i) != Some(&c) {
+                return Some(format!("Letter {} must be {}", i + 1, c));
+            }
+        }
+
+        let mut found: HashMap<char, usize> = HashMap::new();
+        for (c, status) in word.chars().zip(statuses) {
+            if matches!(status, LetterStatus::Correct | LetterStatus::Present) {
+                *found.entry(c).or_insert(0) += 1;
+            }
+        }
+        for c in word.chars() {
+            let needed = found.get(&c).copied().unwrap_or(0);
+            if letters.iter().filter(|&&letter| letter == c).count() < needed {
+                return Some(format!("Guess must contain {}", c));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_statuses() {
+        // Exact match.
+        assert_eq!(
+            compute_statuses("APPLE", "APPLE"),
+            vec![LetterStatus::Correct; 5]
+        );
+
+        // All absent.
+        assert_eq!(
+            compute_statuses("APPLE", "CROWD"),
+            vec![LetterStatus::Absent; 5]
+        );
+
+        // All present but jumbled with some correct.
+        assert_eq!(
+            compute_statuses("STARE", "TEARS"),
+            vec![
+                LetterStatus::Present,
+                LetterStatus::Present,
+                LetterStatus::Correct,
+                LetterStatus::Correct,
+                LetterStatus::Present,
+            ]
+        );
+
+        // Partial match with duplicates.
+        assert_eq!(
+            compute_statuses("PAPER", "APPLE"),
+            vec![
+                LetterStatus::Present,
+                LetterStatus::Present,
+                LetterStatus::Correct,
+                LetterStatus::Present,
+                LetterStatus::Absent
+            ]
+        );
+
+        // Duplicates exceeding target count.
+        assert_eq!(
+            compute_statuses("AAABB", "AACDD"),
+            vec![
+                LetterStatus::Correct,
+                LetterStatus::Correct,
+                LetterStatus::Absent,
+                LetterStatus::Absent,
+                LetterStatus::Absent
+            ]
+        );
+    }
+
+    #[test]
+    fn test_word_lists_are_valid() {
+        let answers =
+            std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/words/hp_answers.txt"))
+                .unwrap();
+        let guesses =
+            std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/words/guesses.txt"))
+                .unwrap();
+        let words = WordList::parse(&answers, &guesses).expect("Word lists should be valid");
+        assert!(words.answers().contains(&"ACCIO".to_string()));
+        assert!(words.is_valid_guess("accio"));
+        assert!(words.is_valid_guess("CRANE"));
+        assert!(!words.answers().contains(&"CRANE".to_string()));
+        assert!(!words.is_valid_guess("DREAB"));
+    }
+
+    #[test]
+    fn test_word_list_parsing() {
+        let words = WordList::parse("# Answers\nghost\n\n  Snake \n", "crane\nSTARE").unwrap();
+        assert_eq!(words.answers(), ["GHOST", "SNAKE"]);
+        assert!(words.is_valid_guess("Ghost"));
+        assert!(words.is_valid_guess("stare"));
+        assert!(!words.is_valid_guess("raven"));
+
+        assert!(WordList::parse("# None yet\n", "crane").is_err());
+        let err = WordList::parse("ghost\nghosts", "").unwrap_err();
+        assert!(err.contains("GHOSTS"), "{}", err);
+        assert!(WordList::parse("ghost", "cr4ne").is_err());
+    }
+
+    #[test]
+    fn test_hard_mode() {
+        let stare = compute_statuses("STARE", "TEARS");
+        let previous = [("STARE", stare.as_slice())];
+        // A and R were in place; S, T and E were found elsewhere.
+        assert_eq!(hard_mode_error("TEARS", previous), None);
+        assert_eq!(
+            hard_mode_error("CROWD", previous),
+            Some("Letter 3 must be A".to_string())
+        );
+        assert_eq!(
+            hard_mode_error("BEANS", previous),
+            Some("Letter 4 must be R".to_string())
+        );
+        assert_eq!(
+            hard_mode_error("TEARE", previous),
+            Some("Guess must contain S".to_string())
+        );
+        assert_eq!(hard_mode_error("CRANE", []), None);
+
+        // A letter found twice has to be guessed twice.
+        let paper = compute_statuses("PAPER", "APPLE");
+        let previous = [("PAPER", paper.as_slice())];
+        assert_eq!(
+            hard_mode_error("PLAPE", previous),
+            Some("Letter 3 must be P".to_string())
+        );
+        assert_eq!(
+            hard_mode_error("EAPLS", previous),
+            Some("Guess must contain P".to_string())
+        );
+        assert_eq!(hard_mode_error("APPLE", previous), None);
+    }
+}